@@ -1,10 +1,13 @@
 mod commands;
 
 use std::io::{self, BufRead, Write};
+use std::path::Path;
 
 use anyhow::{Context, Result, anyhow, ensure};
 use commands::{CommandRegistry, Context as CommandContext};
-use realgeopolitics_core::GameState;
+use realgeopolitics_core::{
+    DependencyGraph, GameState, GraphFormat, IndustryCatalog, TaxRateKind, TechTree,
+};
 
 pub fn run(game: &mut GameState) -> Result<()> {
     print_intro(game);
@@ -42,6 +45,38 @@ pub fn run(game: &mut GameState) -> Result<()> {
     }
 }
 
+/// 産業定義ディレクトリを検査して問題を一覧表示する。エラーが 1 件でもあれば失敗を返す。
+pub fn lint_industry_catalog(dir: &Path) -> Result<()> {
+    let catalog = IndustryCatalog::load_from_dir(dir)?;
+    let pending = TechTree::from_embedded()?.unlocked_sectors();
+    let lint = catalog.lint_with_pending(&pending);
+    for issue in lint.issues() {
+        println!("{issue}");
+    }
+    let errors = lint.errors().count();
+    let warnings = lint.issues().len() - errors;
+    println!(
+        "{}: エラー {} 件 / 警告 {} 件",
+        dir.display(),
+        errors,
+        warnings
+    );
+    ensure!(
+        errors == 0,
+        "産業定義に {} 件のエラーがあります: {}",
+        errors,
+        dir.display()
+    );
+    Ok(())
+}
+
+/// 産業定義ディレクトリの依存関係グラフを指定形式で標準出力へ書き出す。
+pub fn export_industry_graph(dir: &Path, format: GraphFormat) -> Result<()> {
+    let catalog = IndustryCatalog::load_from_dir(dir)?;
+    print!("{}", DependencyGraph::from_catalog(&catalog).render(format));
+    Ok(())
+}
+
 fn print_intro(game: &GameState) {
     println!("リアル・ジオポリティクス シミュレーター (リアルタイム版) へようこそ。");
     println!("現在 {} ヶ国が監視対象です。", game.countries().len());
@@ -56,8 +91,33 @@ pub(super) fn print_help() {
     println!("  set <国> <infra> <mil> <welfare> <diplo> <debt> <admin> <research> [core|nocore]");
     println!("                       各カテゴリのGDP比率(%)を入力 (core で必須支出を優先)");
     println!("  tick <分>             指定した分だけシミュレーションを進める");
+    println!("  next                  次に予定されたタスクの時刻まで進める");
+    println!("  until <YYYY-MM-DD>    指定した日付の 0 時まで進める");
+    println!("  until <条件式> [max <日数>]");
+    println!(
+        "                       条件が成り立つまで 1 時間ずつ進める (例: until Asteria.debt_ratio > 100、上限は既定 30 日)"
+    );
     println!("  speed <倍率|slow|normal|fast>  時間倍率を変更");
+    println!(
+        "  industry list        セクター別の生産量・資本ストック・投資と電力予備率・停電履歴を表示"
+    );
     println!("  industry subsidize <sector> <percent>  指定セクターへ補助金(%)を設定");
+    println!("  industry lint <dir>  産業定義 YAML の参照切れ・循環・不正な数値を検査");
+    println!("  industry graph <dot|mermaid> [flows] [出力先]");
+    println!("                       依存関係グラフを出力 (flows で直近の流量を線の太さに反映)");
+    println!("  tax set <国> [income=%] [corporate=%] [consumption=%] [deductions=額] [steps=n]");
+    println!("                       税制改定を予約し、日次の政策処理ごとに n 段階で移行");
+    println!("  debt restructure <国> <haircut|austerity>");
+    println!("                       デフォルト中の国の債務再編交渉を開始");
+    println!("  debt bailout <国> <accept|decline>");
+    println!("                       提示中の支援融資を受諾/辞退");
+    println!("  carbon tax <国> <価格> | carbon cap <国> <日次上限> | carbon off <国>");
+    println!("                       炭素税・排出枠を設定 (排出1単位あたりの価格 / 1日の排出上限)");
+    println!("  research list <国>    技術ツリーの研究状況と未配分ポイントを表示");
+    println!("  research prioritize <国> <tech> [tech...] | research clear <国>");
+    println!(
+        "                       研究の優先順位を設定/解除 (研究予算がポイントとして投入される)"
+    );
     println!("  quit                  終了");
 }
 
@@ -66,21 +126,25 @@ pub(super) fn print_overview(game: &GameState) {
         .next_event_minutes()
         .map(|m| format!("{:.1} 分", m as f64))
         .unwrap_or_else(|| "未定".to_string());
+    let date = game.calendar_date();
     println!(
-        "シミュレーション時間: {:.1} 分 (倍率 x{:.2}) / 次イベントまで: {} / 資源価格 {:.1}",
+        "{} ({}) / シミュレーション時間: {:.1} 分 (倍率 x{:.2}) / 次イベントまで: {} / 資源価格 {:.1}",
+        date,
+        date.weekday().label(),
         game.simulation_minutes(),
         game.time_multiplier(),
         next_event,
         game.commodity_price()
     );
     println!(
-        "ID | {:<18} | {:<22} | {:>9} | {:>4} | {:>4} | {:>4} | {:>9} | alloc%(i/m/w/d/debt/adm/res)",
-        "国名", "政体", "GDP", "安定", "軍事", "支持", "予算"
+        "ID | {:<18} | {:<22} | {:>9} | {:>4} | {:>4} | {:>4} | {:>9} | {:>6} | {:>6} | alloc%(i/m/w/d/debt/adm/res)",
+        "国名", "政体", "GDP", "安定", "軍事", "支持", "予算", "CPI", "物価%"
     );
     for (idx, country) in game.countries().iter().enumerate() {
         let alloc = country.allocations();
+        let prices = country.consumer_prices();
         println!(
-            "{:>2} | {:<18} | {:<22} | {:>9.1} | {:>4} | {:>4} | {:>4} | {:>9.1} | {:>6.1}%/{:>6.1}%/{:>6.1}%/{:>6.1}%/{:>6.1}%/{:>6.1}%/{:>6.1}%",
+            "{:>2} | {:<18} | {:<22} | {:>9.1} | {:>4.0} | {:>4.0} | {:>4.0} | {:>9.1} | {:>6.1} | {:>+6.1} | {:>6.1}%/{:>6.1}%/{:>6.1}%/{:>6.1}%/{:>6.1}%/{:>6.1}%/{:>6.1}%",
             idx + 1,
             country.name,
             country.government,
@@ -89,6 +153,8 @@ pub(super) fn print_overview(game: &GameState) {
             country.military,
             country.approval,
             country.cash_reserve(),
+            prices.cpi(),
+            prices.inflation(),
             alloc.infrastructure,
             alloc.military,
             alloc.welfare,
@@ -107,10 +173,58 @@ pub(super) fn print_country_details(game: &GameState, idx: usize) {
     println!("政体: {}", country.government);
     println!("人口: {:.1} 百万人", country.population_millions);
     println!("GDP: {:.1} 億ドル", country.gdp);
-    println!("安定度: {}", country.stability);
-    println!("軍事力: {}", country.military);
-    println!("国民支持率: {}", country.approval);
+    println!("安定度: {:.0}", country.stability);
+    println!("軍事力: {:.0}", country.military);
+    println!("国民支持率: {:.0}", country.approval);
     println!("予算残高: {:.1}", country.cash_reserve());
+    let fiscal = &country.fiscal;
+    println!(
+        "政府債務: {:.1} (格付け {:?} / 平均調達金利 {:.2}% / 新規発行金利 {:.2}%)",
+        fiscal.debt(),
+        fiscal.credit_rating,
+        fiscal.average_cost_of_debt() * 100.0,
+        fiscal.interest_rate * 100.0
+    );
+    let profile: Vec<String> = fiscal
+        .maturity_profile()
+        .iter()
+        .map(|bucket| format!("{} {:.1}", bucket.label, bucket.principal))
+        .collect();
+    println!("償還予定: {}", profile.join(" / "));
+    let sovereign = country.sovereign_debt();
+    println!(
+        "返済状況: {} (延滞 {} 回 / 延滞額 {:.1}){}",
+        sovereign.status().label(),
+        sovereign.missed_payments(),
+        sovereign.arrears(),
+        if fiscal.has_market_access() {
+            ""
+        } else {
+            " / 市場アクセス停止"
+        }
+    );
+    if let Some((terms, days)) = sovereign.negotiation() {
+        println!("債務再編: {} (妥結まで {} 日)", terms.label(), days);
+    }
+    if let Some(offer) = sovereign.offer() {
+        println!(
+            "支援提案: {} から {:.1} (条件: {} / 期限 {} 日)",
+            offer.lender,
+            offer.total,
+            offer.conditions.describe(),
+            offer.expires_in_days
+        );
+    }
+    if let Some(program) = sovereign.program() {
+        println!(
+            "支援プログラム: {} / 1 回 {:.1} × 残り {} 回 (次回まで {} 日, 条件違反 {} 回)",
+            program.lender,
+            program.tranche,
+            program.remaining_tranches,
+            program.days_to_next_tranche,
+            program.breaches
+        );
+    }
     println!(
         "今期収支: 収入 {:.1} / 支出 {:.1} / 差額 {:.1}",
         country.total_revenue(),
@@ -120,12 +234,61 @@ pub(super) fn print_country_details(game: &GameState, idx: usize) {
     let tax = country.tax_policy();
     println!(
         "税制: 所得 {:.1}% / 法人 {:.1}% / 消費 {:.1}% (控除 {:.1}, 次期繰越 {:.1})",
-        tax.income_rate * 100.0,
-        tax.corporate_rate * 100.0,
-        tax.consumption_rate * 100.0,
+        tax.effective_rate(TaxRateKind::Income) * 100.0,
+        tax.effective_rate(TaxRateKind::Corporate) * 100.0,
+        tax.effective_rate(TaxRateKind::Consumption) * 100.0,
         tax.deductions,
         tax.pending_revenue()
     );
+    if let Some(transition) = tax.transition() {
+        println!(
+            "税制改定中: 目標 所得 {:.1}% / 法人 {:.1}% / 消費 {:.1}% / 控除 {:.1} (残り {} 段階)",
+            transition.income_rate * 100.0,
+            transition.corporate_rate * 100.0,
+            transition.consumption_rate * 100.0,
+            transition.deductions,
+            transition.remaining_steps
+        );
+    }
+    let prices = country.consumer_prices();
+    println!(
+        "物価: CPI {:.1} / エネルギー価格指数 {:.1} / インフレ率 {:+.2}%",
+        prices.cpi(),
+        prices.energy_index(),
+        prices.inflation()
+    );
+    let environment = country.environment();
+    println!(
+        "環境: 排出 {:.0}/日 / 汚染指数 {:.1} / 炭素価格 {:.1} ({})",
+        environment.emissions_per_day(),
+        environment.pollution(),
+        environment.carbon_price(),
+        environment.policy().label()
+    );
+    let research = country.research();
+    println!(
+        "研究: 未配分ポイント {:.1} / 完了 {} 件 / 優先 {}",
+        research.points(),
+        research.completed().len(),
+        if research.priorities().is_empty() {
+            "なし".to_string()
+        } else {
+            research.priorities().join(" > ")
+        }
+    );
+    let story = country.story();
+    if !story.is_empty() {
+        let flags: Vec<&str> = story.flags().collect();
+        let variables: Vec<String> = story
+            .variables()
+            .map(|(name, value)| format!("{}={:.1}", name, value))
+            .collect();
+        println!(
+            "イベント状態: フラグ [{}] / 変数 [{}]",
+            flags.join(", "),
+            variables.join(", ")
+        );
+    }
     println!("資源指数: {}", country.resources);
     println!(
         "予算配分 (GDP比%): インフラ {:.1}% / 軍事 {:.1}% / 福祉 {:.1}% / 外交 {:.1}% / 債務 {:.1}% / 行政 {:.1}% / 研究 {:.1}{}",
@@ -211,7 +374,7 @@ pub(super) fn print_reports<W: Write>(
 mod tests {
     use super::commands::{CommandRegistry, Context as CommandContext};
     use super::*;
-    use realgeopolitics_core::CountryDefinition;
+    use realgeopolitics_core::{CarbonPolicy, CountryDefinition};
 
     #[test]
     fn print_reports_formats_industry_lines() {
@@ -286,4 +449,206 @@ mod tests {
             .expect("overview entry");
         assert!((overview.subsidy_percent - 12.5).abs() < 1e-6);
     }
+
+    #[test]
+    fn tax_set_command_schedules_transition() {
+        let mut game = GameState::from_definitions(sample_definitions()).expect("game");
+        {
+            let mut context = CommandContext::new(&mut game);
+            let registry = CommandRegistry::default();
+            registry
+                .execute_input(
+                    &mut context,
+                    "tax set Asteria income=24 deductions=30 steps=3",
+                )
+                .expect("dispatch tax set");
+            assert!(
+                registry
+                    .execute_input(&mut context, "tax set Asteria income=90")
+                    .is_err()
+            );
+        }
+
+        let transition = game.countries()[0]
+            .tax_policy()
+            .transition()
+            .expect("transition scheduled");
+        assert!((transition.income_rate - 0.24).abs() < 1e-9);
+        assert!((transition.deductions - 30.0).abs() < 1e-9);
+        assert_eq!(transition.remaining_steps, 3);
+    }
+
+    #[test]
+    fn debt_commands_validate_status_and_choice() {
+        let mut game = GameState::from_definitions(sample_definitions()).expect("game");
+        let mut context = CommandContext::new(&mut game);
+        let registry = CommandRegistry::default();
+        assert!(
+            registry
+                .execute_input(&mut context, "debt restructure Asteria haircut")
+                .is_err()
+        );
+        assert!(
+            registry
+                .execute_input(&mut context, "debt restructure Asteria partial")
+                .is_err()
+        );
+        assert!(
+            registry
+                .execute_input(&mut context, "debt bailout Asteria accept")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn until_command_runs_to_a_date_or_a_condition() {
+        let mut game = GameState::from_definitions(sample_definitions()).expect("game");
+        let mut context = CommandContext::new(&mut game);
+        let registry = CommandRegistry::default();
+        registry
+            .execute_input(&mut context, "until 2025-01-02")
+            .expect("until date");
+        assert_eq!(
+            context.game().calendar_date(),
+            realgeopolitics_core::CalendarDate::new(2025, 1, 2)
+        );
+        assert!(
+            registry
+                .execute_input(&mut context, "until 2025-01-01")
+                .is_err()
+        );
+        assert!(
+            registry
+                .execute_input(&mut context, "until 2025-02-30")
+                .is_err()
+        );
+
+        registry
+            .execute_input(&mut context, "until asteria.stability > 100 max 0.25")
+            .expect("until condition");
+        assert_eq!(context.game().simulation_minutes(), 24.0 * 60.0 + 360.0);
+        assert!(
+            registry
+                .execute_input(&mut context, "until Atlantis.gdp > 0")
+                .is_err()
+        );
+        assert!(
+            registry
+                .execute_input(&mut context, "until Asteria.gdp > 0 max -1")
+                .is_err()
+        );
+
+        registry
+            .execute_input(&mut context, "next")
+            .expect("next task");
+        assert!(context.game().simulation_minutes() > 24.0 * 60.0 + 360.0);
+    }
+
+    #[test]
+    fn carbon_command_sets_policy() {
+        let mut game = GameState::from_definitions(sample_definitions()).expect("game");
+        let mut context = CommandContext::new(&mut game);
+        let registry = CommandRegistry::default();
+        registry
+            .execute_input(&mut context, "carbon tax Asteria 25")
+            .expect("carbon tax");
+        assert!(
+            registry
+                .execute_input(&mut context, "carbon tax Asteria -5")
+                .is_err()
+        );
+        assert!(
+            registry
+                .execute_input(&mut context, "carbon ban Asteria")
+                .is_err()
+        );
+        assert_eq!(
+            game.countries()[0].environment().policy(),
+            CarbonPolicy::Tax { price: 25.0 }
+        );
+    }
+
+    #[test]
+    fn research_command_sets_priorities() {
+        let mut game = GameState::from_definitions(sample_definitions()).expect("game");
+        let mut context = CommandContext::new(&mut game);
+        let registry = CommandRegistry::default();
+        registry
+            .execute_input(
+                &mut context,
+                "research prioritize Asteria digital_government e_invoicing",
+            )
+            .expect("prioritize");
+        registry
+            .execute_input(&mut context, "research list Asteria")
+            .expect("list");
+        assert!(
+            registry
+                .execute_input(&mut context, "research prioritize Asteria warp_drive")
+                .is_err()
+        );
+        assert!(
+            registry
+                .execute_input(&mut context, "research prioritize Asteria")
+                .is_err()
+        );
+        assert_eq!(
+            game.countries()[0].research().priorities(),
+            ["digital_government", "e_invoicing"]
+        );
+    }
+
+    #[test]
+    fn industry_lint_rejects_broken_catalog() {
+        let bundled = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("config")
+            .join("industries");
+        lint_industry_catalog(&bundled).expect("同梱の産業定義");
+
+        let dir = std::env::temp_dir().join(format!("rg-industry-lint-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("temp dir");
+        std::fs::write(
+            dir.join("primary.yaml"),
+            r#"
+category: primary
+sectors:
+  - key: grains
+    name: 穀物
+    dependencies:
+      - sector: fertiliser
+        category: secondary
+        requirement: 0.2
+        dependency: input
+"#,
+        )
+        .expect("write yaml");
+        let result = lint_industry_catalog(&dir);
+        std::fs::remove_dir_all(&dir).ok();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn industry_graph_command_writes_weighted_mermaid() {
+        let mut game = GameState::from_definitions(sample_definitions()).expect("game");
+        game.tick_minutes(60.0).expect("tick");
+        let path =
+            std::env::temp_dir().join(format!("rg-industry-graph-{}.mmd", std::process::id()));
+        let input = format!("industry graph mermaid flows {}", path.display());
+        {
+            let mut context = CommandContext::new(&mut game);
+            let registry = CommandRegistry::default();
+            registry.execute_input(&mut context, &input).expect("graph");
+            assert!(
+                registry
+                    .execute_input(&mut context, "industry graph svg")
+                    .is_err()
+            );
+        }
+        let rendered = std::fs::read_to_string(&path).expect("read graph");
+        std::fs::remove_file(&path).ok();
+        assert!(rendered.starts_with("flowchart LR"));
+        assert!(rendered.contains("flow="));
+        assert!(rendered.contains("linkStyle"));
+    }
 }
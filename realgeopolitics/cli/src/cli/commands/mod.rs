@@ -1,13 +1,18 @@
 use std::collections::HashMap;
+use std::fs;
 use std::io;
+use std::path::Path;
 use std::process;
 
-use anyhow::{Result, anyhow, bail};
-use realgeopolitics_core::{BudgetAllocation, GameState};
+use anyhow::{Context as _, Result, anyhow, bail};
+use realgeopolitics_core::{
+    BudgetAllocation, CalendarDate, CarbonPolicy, GameState, GraphFormat, RestructuringTerms,
+    TaxPolicyChange,
+};
 
 use super::{
-    parse_percentage, parse_speed, print_country_details, print_help, print_overview,
-    print_reports, resolve_country_index,
+    lint_industry_catalog, parse_percentage, parse_speed, print_country_details, print_help,
+    print_overview, print_reports, resolve_country_index,
 };
 
 pub struct Context<'a> {
@@ -50,6 +55,13 @@ impl<'a> Args<'a> {
     pub fn next_required(&mut self, message: &str) -> Result<&'a str> {
         self.next().ok_or_else(|| anyhow!(message.to_owned()))
     }
+
+    /// 残りの引数をすべて取り出す。
+    pub fn rest(&mut self) -> Vec<&'a str> {
+        let rest = self.tokens[self.index..].to_vec();
+        self.index = self.tokens.len();
+        rest
+    }
 }
 
 pub trait Command {
@@ -106,8 +118,14 @@ impl Default for CommandRegistry {
         registry.register::<ShowCommand>();
         registry.register::<SetCommand>();
         registry.register::<TickCommand>();
+        registry.register::<NextCommand>();
+        registry.register::<UntilCommand>();
         registry.register::<SpeedCommand>();
         registry.register::<IndustryCommand>();
+        registry.register::<TaxCommand>();
+        registry.register::<DebtCommand>();
+        registry.register::<CarbonCommand>();
+        registry.register::<ResearchCommand>();
         registry.register::<QuitCommand>();
         registry.register::<ExitCommand>();
         registry
@@ -256,6 +274,87 @@ impl Command for TickCommand {
     }
 }
 
+pub struct NextCommand;
+
+impl Command for NextCommand {
+    fn name() -> &'static str {
+        "next"
+    }
+
+    fn execute(ctx: &mut Context<'_>, _args: Args<'_>) -> Result<()> {
+        let before = ctx.game().simulation_minutes();
+        let reports = ctx.game_mut().advance_to_next_task()?;
+        let elapsed = ctx.game().simulation_minutes() - before;
+        let mut stdout = io::stdout();
+        print_reports(&mut stdout, elapsed, &reports)?;
+        Ok(())
+    }
+}
+
+/// 条件式で進めるときの既定の上限日数。
+const DEFAULT_UNTIL_MAX_DAYS: f64 = 30.0;
+
+pub struct UntilCommand;
+
+impl Command for UntilCommand {
+    fn name() -> &'static str {
+        "until"
+    }
+
+    fn execute(ctx: &mut Context<'_>, mut args: Args<'_>) -> Result<()> {
+        let mut tokens = args.rest();
+        if tokens.is_empty() {
+            bail!("日付 (YYYY-MM-DD) または条件式を指定してください。");
+        }
+        let mut max_days = None;
+        if tokens.len() >= 2 && tokens[tokens.len() - 2].eq_ignore_ascii_case("max") {
+            let days: f64 = tokens[tokens.len() - 1]
+                .parse()
+                .map_err(|_| anyhow!("上限日数は数値で指定してください。"))?;
+            if !(days.is_finite() && days > 0.0) {
+                bail!("上限日数は正の値で指定してください。");
+            }
+            max_days = Some(days);
+            tokens.truncate(tokens.len() - 2);
+        }
+        let mut stdout = io::stdout();
+        // 条件式の識別子は数字で始まらないので、数字で始まれば日付として扱う。
+        if tokens.len() == 1 && tokens[0].starts_with(|ch: char| ch.is_ascii_digit()) {
+            if max_days.is_some() {
+                bail!("max は条件式と組み合わせて指定してください。");
+            }
+            let date: CalendarDate = tokens[0].parse()?;
+            let before = ctx.game().simulation_minutes();
+            let reports = ctx.game_mut().run_until_date(date)?;
+            let elapsed = ctx.game().simulation_minutes() - before;
+            print_reports(&mut stdout, elapsed, &reports)?;
+            return Ok(());
+        }
+
+        let condition = tokens.join(" ");
+        let max_days = max_days.unwrap_or(DEFAULT_UNTIL_MAX_DAYS);
+        let outcome = ctx
+            .game_mut()
+            .run_until(&condition, max_days * 60.0 * 24.0)?;
+        if outcome.elapsed_minutes > 0.0 {
+            print_reports(&mut stdout, outcome.elapsed_minutes, &outcome.reports)?;
+        }
+        if outcome.satisfied {
+            println!(
+                "条件 '{}' が成立しました ({})。",
+                condition,
+                ctx.game().calendar_date()
+            );
+        } else {
+            println!(
+                "{:.1} 日進めても条件 '{}' は成立しませんでした。",
+                max_days, condition
+            );
+        }
+        Ok(())
+    }
+}
+
 pub struct SpeedCommand;
 
 impl Command for SpeedCommand {
@@ -287,11 +386,98 @@ impl Command for IndustryCommand {
             .to_ascii_lowercase();
         match sub.as_str() {
             "subsidize" => IndustrySubsidizeCommand::run(ctx, args),
+            "list" => {
+                IndustryListCommand::run(ctx);
+                Ok(())
+            }
+            "graph" => IndustryGraphCommand::run(ctx, args),
+            "lint" => {
+                let dir = args.next_required("検査する産業定義ディレクトリを指定してください。")?;
+                lint_industry_catalog(Path::new(dir))
+            }
             other => bail!("未知の industry サブコマンドです: {}", other),
         }
     }
 }
 
+struct IndustryListCommand;
+
+impl IndustryListCommand {
+    fn run(ctx: &mut Context<'_>) {
+        println!(
+            "{:<24} {:>10} {:>10} {:>10} {:>8}",
+            "セクター", "生産量", "資本", "投資", "補助%"
+        );
+        for overview in ctx.game().industry_overview() {
+            println!(
+                "{:<24} {:>10.1} {:>10.1} {:>10.3} {:>8.1}",
+                format!("{}:{}", overview.id.category, overview.id.key),
+                overview.last_output,
+                overview.capital_stock,
+                overview.last_investment,
+                overview.subsidy_percent
+            );
+        }
+        let grid = ctx.game().power_grid_status();
+        println!("電力予備率: {:+.1}%", grid.reserve_margin * 100.0);
+        if let Some(active) = grid.active {
+            println!(
+                "計画停電中: 生産能力 -{:.0}% / 残り {:.1} 時間",
+                active.output_cut * 100.0,
+                grid.remaining_minutes / 60.0
+            );
+        }
+        if grid.history.is_empty() {
+            println!("停電履歴: なし");
+        } else {
+            println!("停電履歴 (直近 {} 件):", grid.history.len());
+            for record in grid.history.iter().rev() {
+                println!(
+                    "  {:.1} 日目: 供給不足 {:.1}% / {:.0} 時間 / 生産能力 -{:.0}%",
+                    record.started_at_minutes / (60.0 * 24.0),
+                    record.shortfall_ratio * 100.0,
+                    record.duration_minutes / 60.0,
+                    record.output_cut * 100.0
+                );
+            }
+        }
+    }
+}
+
+struct IndustryGraphCommand;
+
+impl IndustryGraphCommand {
+    fn run(ctx: &mut Context<'_>, mut args: Args<'_>) -> Result<()> {
+        let format: GraphFormat = args
+            .next_required("出力形式を指定してください (dot または mermaid)。")?
+            .parse()?;
+        let mut weighted = false;
+        let mut output = None;
+        while let Some(token) = args.next() {
+            if token.eq_ignore_ascii_case("flows") {
+                weighted = true;
+            } else if output.is_none() {
+                output = Some(token);
+            } else {
+                bail!("余分な引数があります: {}", token);
+            }
+        }
+        let rendered = ctx
+            .game()
+            .industry_dependency_graph(weighted)
+            .render(format);
+        match output {
+            Some(path) => {
+                fs::write(path, rendered)
+                    .with_context(|| format!("グラフの書き出しに失敗しました: {}", path))?;
+                println!("依存関係グラフを {} に書き出しました。", path);
+            }
+            None => print!("{rendered}"),
+        }
+        Ok(())
+    }
+}
+
 struct IndustrySubsidizeCommand;
 
 impl IndustrySubsidizeCommand {
@@ -318,6 +504,272 @@ impl IndustrySubsidizeCommand {
         Ok(())
     }
 }
+pub struct TaxCommand;
+
+impl Command for TaxCommand {
+    fn name() -> &'static str {
+        "tax"
+    }
+
+    fn execute(ctx: &mut Context<'_>, mut args: Args<'_>) -> Result<()> {
+        let sub = args
+            .next_required("tax サブコマンドを指定してください (例: set)。")?
+            .to_ascii_lowercase();
+        match sub.as_str() {
+            "set" => TaxSetCommand::run(ctx, args),
+            other => bail!("未知の tax サブコマンドです: {}", other),
+        }
+    }
+}
+
+struct TaxSetCommand;
+
+impl TaxSetCommand {
+    fn run(ctx: &mut Context<'_>, mut args: Args<'_>) -> Result<()> {
+        let token = args.next_required("対象を指定してください。")?;
+        let idx = resolve_country_index(ctx.game(), token)?;
+        let mut change = TaxPolicyChange {
+            phase_steps: 1,
+            ..TaxPolicyChange::default()
+        };
+        while let Some(item) = args.next() {
+            let (key, value) = item
+                .split_once('=')
+                .ok_or_else(|| anyhow!("税目は <項目>=<値> 形式で指定してください: {}", item))?;
+            let key = key.to_ascii_lowercase();
+            if key == "steps" {
+                change.phase_steps = value
+                    .parse()
+                    .map_err(|_| anyhow!("段階数は正の整数で指定してください。"))?;
+                continue;
+            }
+            let number: f64 = value
+                .parse()
+                .map_err(|_| anyhow!("{} の値は数値で指定してください。", key))?;
+            match key.as_str() {
+                "income" => change.income_rate = Some(number / 100.0),
+                "corporate" => change.corporate_rate = Some(number / 100.0),
+                "consumption" => change.consumption_rate = Some(number / 100.0),
+                "deductions" => change.deductions = Some(number),
+                other => bail!(
+                    "未知の税目です: {} (income/corporate/consumption/deductions/steps)",
+                    other
+                ),
+            }
+        }
+        ctx.game_mut().update_tax_policy(idx, change)?;
+        let country = &ctx.game().countries()[idx];
+        if let Some(transition) = country.tax_policy().transition() {
+            println!(
+                "{} の税制改定を予約しました: 所得 {:.1}% / 法人 {:.1}% / 消費 {:.1}% / 控除 {:.1} ({} 段階で移行)",
+                country.name,
+                transition.income_rate * 100.0,
+                transition.corporate_rate * 100.0,
+                transition.consumption_rate * 100.0,
+                transition.deductions,
+                transition.remaining_steps
+            );
+        }
+        Ok(())
+    }
+}
+
+pub struct DebtCommand;
+
+impl Command for DebtCommand {
+    fn name() -> &'static str {
+        "debt"
+    }
+
+    fn execute(ctx: &mut Context<'_>, mut args: Args<'_>) -> Result<()> {
+        let sub = args
+            .next_required("debt サブコマンドを指定してください (例: restructure, bailout)。")?
+            .to_ascii_lowercase();
+        let token = args.next_required("対象を指定してください。")?;
+        let idx = resolve_country_index(ctx.game(), token)?;
+        let choice = args
+            .next_required("方針を指定してください。")?
+            .to_ascii_lowercase();
+        match (sub.as_str(), choice.as_str()) {
+            ("restructure", "haircut" | "austerity") => {
+                let terms = if choice == "haircut" {
+                    RestructuringTerms::Haircut
+                } else {
+                    RestructuringTerms::Austerity
+                };
+                ctx.game_mut().restructure_debt(idx, terms)?;
+                println!(
+                    "{} は債務再編交渉を開始しました ({})。",
+                    ctx.game().countries()[idx].name,
+                    terms.label()
+                );
+            }
+            ("restructure", other) => {
+                bail!(
+                    "再編方針は haircut または austerity で指定してください: {}",
+                    other
+                )
+            }
+            ("bailout", "accept") => {
+                let tranche = ctx.game_mut().accept_bailout(idx)?;
+                println!(
+                    "{} は支援融資を受諾し、初回 {:.1} を受け取りました。",
+                    ctx.game().countries()[idx].name,
+                    tranche
+                );
+            }
+            ("bailout", "decline") => {
+                ctx.game_mut().decline_bailout(idx)?;
+                println!(
+                    "{} は支援融資の提案を辞退しました。",
+                    ctx.game().countries()[idx].name
+                );
+            }
+            ("bailout", other) => {
+                bail!(
+                    "支援融資は accept または decline で指定してください: {}",
+                    other
+                )
+            }
+            (other, _) => bail!("未知の debt サブコマンドです: {}", other),
+        }
+        Ok(())
+    }
+}
+
+pub struct CarbonCommand;
+
+impl Command for CarbonCommand {
+    fn name() -> &'static str {
+        "carbon"
+    }
+
+    fn execute(ctx: &mut Context<'_>, mut args: Args<'_>) -> Result<()> {
+        let sub = args
+            .next_required("carbon サブコマンドを指定してください (例: tax, cap, off)。")?
+            .to_ascii_lowercase();
+        let token = args.next_required("対象を指定してください。")?;
+        let idx = resolve_country_index(ctx.game(), token)?;
+        let policy = match sub.as_str() {
+            "tax" => {
+                let price = args
+                    .next_required("炭素価格を指定してください。")?
+                    .parse()
+                    .map_err(|_| anyhow!("炭素価格は数値で指定してください。"))?;
+                CarbonPolicy::Tax { price }
+            }
+            "cap" => {
+                let limit_per_day = args
+                    .next_required("1日あたりの排出上限を指定してください。")?
+                    .parse()
+                    .map_err(|_| anyhow!("排出上限は数値で指定してください。"))?;
+                CarbonPolicy::Cap { limit_per_day }
+            }
+            "off" => CarbonPolicy::None,
+            other => bail!("未知の carbon サブコマンドです: {}", other),
+        };
+        ctx.game_mut().set_carbon_policy(idx, policy)?;
+        println!(
+            "{} の炭素価格政策を {} に変更しました。",
+            ctx.game().countries()[idx].name,
+            policy.label()
+        );
+        Ok(())
+    }
+}
+
+pub struct ResearchCommand;
+
+impl Command for ResearchCommand {
+    fn name() -> &'static str {
+        "research"
+    }
+
+    fn execute(ctx: &mut Context<'_>, mut args: Args<'_>) -> Result<()> {
+        let sub = args
+            .next_required(
+                "research サブコマンドを指定してください (例: list, prioritize, clear)。",
+            )?
+            .to_ascii_lowercase();
+        let token = args.next_required("対象を指定してください。")?;
+        let idx = resolve_country_index(ctx.game(), token)?;
+        match sub.as_str() {
+            "list" => {
+                ResearchListCommand::run(ctx, idx);
+                Ok(())
+            }
+            "prioritize" => {
+                let mut keys = Vec::new();
+                while let Some(key) = args.next() {
+                    keys.push(key.to_string());
+                }
+                if keys.is_empty() {
+                    bail!("優先する技術キーを1つ以上指定してください。");
+                }
+                ctx.game_mut().set_research_priorities(idx, keys)?;
+                println!(
+                    "{} の研究優先順位を {} に設定しました。",
+                    ctx.game().countries()[idx].name,
+                    ctx.game().countries()[idx]
+                        .research()
+                        .priorities()
+                        .join(" > ")
+                );
+                Ok(())
+            }
+            "clear" => {
+                ctx.game_mut().set_research_priorities(idx, Vec::new())?;
+                println!(
+                    "{} の研究優先順位を解除しました。",
+                    ctx.game().countries()[idx].name
+                );
+                Ok(())
+            }
+            other => bail!("未知の research サブコマンドです: {}", other),
+        }
+    }
+}
+
+struct ResearchListCommand;
+
+impl ResearchListCommand {
+    fn run(ctx: &mut Context<'_>, idx: usize) {
+        let game = ctx.game();
+        let research = game.countries()[idx].research();
+        let target = research
+            .current_target(game.technology_tree())
+            .map(|tech| tech.key.as_str());
+        println!(
+            "{:<24} {:<20} {:>8} {:>16}",
+            "キー", "技術", "状態", "進捗/コスト"
+        );
+        for tech in game.technology_tree().technologies() {
+            let status = if research.is_completed(&tech.key) {
+                "完了"
+            } else if target == Some(tech.key.as_str()) {
+                "研究中"
+            } else if tech.prerequisites_met(research.completed()) {
+                "利用可"
+            } else {
+                "前提未達"
+            };
+            println!(
+                "{:<24} {:<20} {:>8} {:>16}",
+                tech.key,
+                tech.name,
+                status,
+                format!("{:.0}/{:.0}", research.progress(&tech.key), tech.cost)
+            );
+        }
+        println!("未配分の研究ポイント: {:.1}", research.points());
+        if research.priorities().is_empty() {
+            println!("優先順位: なし (研究ポイントは蓄積のみ)");
+        } else {
+            println!("優先順位: {}", research.priorities().join(" > "));
+        }
+    }
+}
+
 pub struct QuitCommand;
 
 impl Command for QuitCommand {
@@ -7,9 +7,37 @@ use std::path::{Path, PathBuf};
 use anyhow::{Context, Result};
 use rand::SeedableRng;
 use rand::rngs::StdRng;
-use realgeopolitics_core::{CountryDefinition, GameState};
+use realgeopolitics_core::{CalendarDate, CountryDefinition, GameBuilder, GraphFormat};
 
 fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let [command, sub, rest @ ..] = args.as_slice()
+        && command == "industry"
+        && sub == "lint"
+    {
+        let [dir] = rest else {
+            anyhow::bail!("使い方: realgeopolitics-cli industry lint <dir>");
+        };
+        return cli::lint_industry_catalog(Path::new(dir));
+    }
+    if let [command, sub, rest @ ..] = args.as_slice()
+        && command == "industry"
+        && sub == "graph"
+    {
+        let (dir, format) = match rest {
+            [dir] => (dir, GraphFormat::Dot),
+            [dir, format] => (dir, format.parse()?),
+            _ => anyhow::bail!("使い方: realgeopolitics-cli industry graph <dir> [dot|mermaid]"),
+        };
+        return cli::export_industry_graph(Path::new(dir), format);
+    }
+
+    let start_date = match args.as_slice() {
+        [] => CalendarDate::from_start(),
+        [flag, date] if flag == "--start" => date.parse()?,
+        _ => anyhow::bail!("使い方: realgeopolitics-cli [--start YYYY-MM-DD]"),
+    };
+
     let config_path = resolve_config_path()?;
 
     let file = File::open(&config_path)
@@ -24,12 +52,16 @@ fn main() -> Result<()> {
         })?;
 
     let rng = StdRng::from_entropy();
-    let mut game = GameState::from_definitions_with_rng(definitions, rng).with_context(|| {
-        format!(
-            "国設定ファイルの読み込みに失敗しました: {}",
-            config_path.display()
-        )
-    })?;
+    let mut game = GameBuilder::new(definitions)
+        .with_rng(rng)
+        .with_start_date(start_date)
+        .build()
+        .with_context(|| {
+            format!(
+                "国設定ファイルの読み込みに失敗しました: {}",
+                config_path.display()
+            )
+        })?;
 
     cli::run(&mut game)
 }
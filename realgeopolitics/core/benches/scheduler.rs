@@ -0,0 +1,80 @@
+use std::hint::black_box;
+
+use criterion::{BatchSize, BenchmarkId, Criterion, criterion_group, criterion_main};
+use realgeopolitics_core::{GameClock, ScheduleSpec, ScheduledTask, Scheduler, TaskKind};
+
+const DAY: u64 = 24 * 60;
+const YEAR: u64 = 365 * DAY;
+const TASKS: usize = 5_000;
+
+/// `years` 年経過した時点の時計と、そこまで空回ししたスケジューラ。
+fn scheduler_at(years: u64) -> (Scheduler, GameClock) {
+    let mut scheduler = Scheduler::new();
+    let mut clock = GameClock::new();
+    clock.advance_minutes((years * YEAR) as f64);
+    scheduler.next_ready_tasks(&clock);
+    (scheduler, clock)
+}
+
+fn offset(idx: usize) -> u64 {
+    (idx as u64 * 7_919) % (180 * DAY) + 1
+}
+
+fn insert_tasks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("scheduler_insert_5000");
+    for years in [0, 5, 30] {
+        group.bench_with_input(BenchmarkId::from_parameter(years), &years, |b, &years| {
+            b.iter_batched(
+                || scheduler_at(years),
+                |(mut scheduler, clock)| {
+                    let now = clock.total_minutes();
+                    for idx in 0..TASKS {
+                        scheduler.schedule(ScheduledTask::new(
+                            TaskKind::ScriptedEvent(idx),
+                            now + offset(idx),
+                        ));
+                    }
+                    black_box(scheduler)
+                },
+                BatchSize::SmallInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+fn drain_recurring(c: &mut Criterion) {
+    let mut group = c.benchmark_group("scheduler_recurring_90_days");
+    group.sample_size(20);
+    for years in [0, 5, 30] {
+        group.bench_with_input(BenchmarkId::from_parameter(years), &years, |b, &years| {
+            b.iter_batched(
+                || {
+                    let (mut scheduler, clock) = scheduler_at(years);
+                    let now = clock.total_minutes();
+                    for idx in 0..TASKS {
+                        let interval = (idx as u64 % 30 + 1) * 6 * 60;
+                        scheduler.schedule(
+                            ScheduledTask::new(TaskKind::ScriptedEvent(idx), now + offset(idx))
+                                .with_schedule(ScheduleSpec::EveryMinutes(interval)),
+                        );
+                    }
+                    (scheduler, clock)
+                },
+                |(mut scheduler, mut clock)| {
+                    let mut fired = 0;
+                    for _ in 0..90 {
+                        clock.advance_minutes(DAY as f64);
+                        fired += scheduler.next_ready_tasks(&clock).len();
+                    }
+                    black_box(fired)
+                },
+                BatchSize::LargeInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, insert_tasks, drain_recurring);
+criterion_main!(benches);
@@ -1,4 +1,4 @@
-use anyhow::{Result, ensure};
+use anyhow::{Result, anyhow, ensure};
 use rand::{SeedableRng, rngs::StdRng};
 
 use super::{
@@ -8,14 +8,16 @@ use super::{
     event_templates::ScriptedEventEngine,
     industry::IndustryEngine,
     market::CommodityMarket,
+    research::TechTree,
     state::GameState,
     systems::diplomacy,
 };
-use crate::{ScheduleSpec, ScheduledTask, Scheduler, TaskKind};
+use crate::{CalendarDate, ScheduleSpec, ScheduledTask, Scheduler, TaskKind};
 
 pub struct GameBuilder {
     definitions: Vec<CountryDefinition>,
     rng: StdRng,
+    start_date: CalendarDate,
 }
 
 impl GameBuilder {
@@ -23,6 +25,7 @@ impl GameBuilder {
         Self {
             definitions,
             rng: StdRng::from_entropy(),
+            start_date: CalendarDate::from_start(),
         }
     }
 
@@ -31,6 +34,12 @@ impl GameBuilder {
         self
     }
 
+    /// ゲーム初日の日付。暦ベースの予定や月末・年末の締めはこの日を起点に数える。
+    pub fn with_start_date(mut self, start_date: CalendarDate) -> Self {
+        self.start_date = start_date;
+        self
+    }
+
     pub fn build(self) -> Result<GameState> {
         let bootstrap = self.into_bootstrap()?;
         Ok(GameState::new(bootstrap))
@@ -45,7 +54,7 @@ impl GameBuilder {
     ///
     /// # Returns
     ///
-    /// A `GameBootstrap` containing `rng`, `scheduler`, `countries`, `commodity_market`,
+    /// A `GameBootstrap` containing `rng`, `start_date`, `scheduler`, `countries`, `commodity_market`,
     /// `scripted_events`, and `industry_engine` on success; an error if validation fails.
     ///
     /// # Examples
@@ -57,27 +66,36 @@ impl GameBuilder {
     /// ```
     pub(crate) fn into_bootstrap(self) -> Result<GameBootstrap> {
         self.validate_definitions()?;
-        let GameBuilder { definitions, rng } = self;
+        let GameBuilder {
+            definitions,
+            rng,
+            start_date,
+        } = self;
 
         let mut countries = initialise_countries(definitions);
         diplomacy::initialise_relations(&mut countries);
 
-        let mut scheduler = Scheduler::new();
+        let mut scheduler = Scheduler::new().with_epoch(start_date);
         register_core_tasks(&mut scheduler);
-        let scripted_events = register_scripted_events(&mut scheduler, countries.len())?;
+        let scripted_events = register_scripted_events(&mut scheduler)?;
 
         let commodity_market = CommodityMarket::new(120.0, 7.5, 0.04);
         let industry_catalog = IndustryCatalog::from_embedded().unwrap_or_default();
+        let technologies = TechTree::from_embedded()?;
+        technologies.validate_sectors(&industry_catalog)?;
         let industry_runtime = IndustryRuntime::from_catalog(industry_catalog);
+        scripted_events.validate_sectors(industry_runtime.registry())?;
         let industry_engine = IndustryEngine::new(industry_runtime);
 
         Ok(GameBootstrap {
             rng,
+            start_date,
             scheduler,
             countries,
             commodity_market,
             scripted_events,
             industry_engine,
+            technologies,
         })
     }
 
@@ -86,17 +104,20 @@ impl GameBuilder {
             !self.definitions.is_empty(),
             "国が1つも定義されていません。最低1件の国を用意してください。"
         );
+        self.start_date.validate()?;
         Ok(())
     }
 }
 
 pub(crate) struct GameBootstrap {
     pub(crate) rng: StdRng,
+    pub(crate) start_date: CalendarDate,
     pub(crate) scheduler: Scheduler,
     pub(crate) countries: Vec<CountryState>,
     pub(crate) commodity_market: CommodityMarket,
     pub(crate) scripted_events: ScriptedEventEngine,
     pub(crate) industry_engine: IndustryEngine,
+    pub(crate) technologies: TechTree,
 }
 
 fn initialise_countries(definitions: Vec<CountryDefinition>) -> Vec<CountryState> {
@@ -155,8 +176,9 @@ fn register_core_tasks(scheduler: &mut Scheduler) {
 
 /// Registers scripted-event tasks for each built-in scripted event and returns the configured engine.
 ///
-/// For each scripted event provided by the built-in engine (created for `country_count`), a `ScriptedEvent`
-/// task is scheduled on `scheduler` using the engine's initial delay and its recurring check interval.
+/// For each scripted event provided by the built-in engine, a `ScriptedEvent`
+/// task is scheduled on `scheduler` at the first slot of the template's schedule after its initial delay.
+/// Templates with `auto_trigger: false` are skipped; they only run as follow-ups of other events.
 /// Propagates any error encountered while constructing the `ScriptedEventEngine`.
 ///
 /// # Returns
@@ -169,37 +191,44 @@ fn register_core_tasks(scheduler: &mut Scheduler) {
 /// # use your_crate::{Scheduler, register_scripted_events};
 /// # fn make_scheduler() -> Scheduler { Scheduler::new() }
 /// let mut scheduler = make_scheduler();
-/// let engine = register_scripted_events(&mut scheduler, 3).expect("engine built");
+/// let engine = register_scripted_events(&mut scheduler).expect("engine built");
 /// assert!(engine.len() > 0);
 /// ```
-fn register_scripted_events(
-    scheduler: &mut Scheduler,
-    country_count: usize,
-) -> Result<ScriptedEventEngine> {
-    let engine = ScriptedEventEngine::from_builtin(country_count)?;
+fn register_scripted_events(scheduler: &mut Scheduler) -> Result<ScriptedEventEngine> {
+    let engine = ScriptedEventEngine::from_builtin()?;
     for idx in 0..engine.len() {
-        let mut task = ScheduledTask::new(
-            TaskKind::ScriptedEvent(idx),
-            engine.initial_delay_minutes(idx),
+        if !engine.auto_trigger(idx) {
+            continue;
+        }
+        let spec = engine.schedule(idx);
+        let execute_at = scheduler
+            .first_occurrence(spec, engine.initial_delay_minutes(idx))
+            .ok_or_else(|| {
+                anyhow!(
+                    "イベント '{}' のスケジュールに該当する日時がありません",
+                    engine.template_id(idx)
+                )
+            })?;
+        scheduler.schedule(
+            ScheduledTask::new(TaskKind::ScriptedEvent(idx), execute_at)
+                .with_schedule(spec)
+                .with_category(engine.category(idx)),
         );
-        task = task.with_schedule(ScheduleSpec::EveryMinutes(engine.check_minutes(idx)));
-        scheduler.schedule(task);
     }
     Ok(engine)
 }
 
-/// Clamp a metric to the allowed metric range.
+/// Convert an integer metric from a country definition and clamp it to the allowed range.
 ///
 /// Returns the input value clamped to the inclusive range [MIN_METRIC, MAX_METRIC].
 ///
 /// # Examples
 ///
 /// ```ignore
-/// let v = clamp_metric(7);
-/// assert_eq!(clamp_metric(v), v);
+/// assert_eq!(clamp_metric(120), 100.0);
 /// ```
-fn clamp_metric(value: i32) -> i32 {
-    value.clamp(MIN_METRIC, MAX_METRIC)
+fn clamp_metric(value: i32) -> f64 {
+    (value as f64).clamp(MIN_METRIC, MAX_METRIC)
 }
 
 fn clamp_resource(value: i32) -> i32 {
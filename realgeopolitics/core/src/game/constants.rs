@@ -1,8 +1,10 @@
 pub(crate) const BASE_TICK_MINUTES: f64 = 60.0;
+/// 1 回の進行で扱う実効分の上限。長い進行はこの長さ以下のステップに分けて処理する。
+pub(crate) const MAX_STEP_MINUTES: f64 = BASE_TICK_MINUTES;
 pub(crate) const MAX_RELATION: i32 = 100;
 pub(crate) const MIN_RELATION: i32 = -100;
-pub(crate) const MAX_METRIC: i32 = 100;
-pub(crate) const MIN_METRIC: i32 = 0;
+pub(crate) const MAX_METRIC: f64 = 100.0;
+pub(crate) const MIN_METRIC: f64 = 0.0;
 pub(crate) const MAX_RESOURCES: i32 = 200;
 pub(crate) const MIN_RESOURCES: i32 = 0;
 pub(crate) const MINUTES_PER_DAY: u64 = 24 * 60;
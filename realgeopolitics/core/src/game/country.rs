@@ -3,7 +3,14 @@ use std::collections::HashMap;
 use anyhow::{Result, ensure};
 use serde::{Deserialize, Serialize};
 
-use super::economy::{FiscalAccount, FiscalSnapshot, FiscalTrendPoint, TaxPolicy, TaxPolicyConfig};
+use super::economy::{
+    ConsumerPriceIndex, DebtCycleOutcome, EnvironmentState, FiscalAccount, FiscalSnapshot,
+    FiscalTrendPoint, RestructuringTerms, SovereignDebt, SovereignEvent, TaxPolicy,
+    TaxPolicyConfig,
+};
+use super::event_templates::StoryState;
+use super::research::ResearchState;
+use super::{MAX_RELATION, MAX_RESOURCES, MIN_RELATION, MIN_RESOURCES};
 
 const MAX_FISCAL_HISTORY: usize = 256;
 const HISTORY_DUPLICATE_EPS: f64 = 1e-6;
@@ -130,15 +137,24 @@ pub struct CountryState {
     pub government: String,
     pub population_millions: f64,
     pub gdp: f64,
-    pub stability: i32,
-    pub military: i32,
-    pub approval: i32,
+    /// 安定度・軍事力・支持率は端数を持ったまま蓄積し、表示するときに丸める。
+    pub stability: f64,
+    pub military: f64,
+    pub approval: f64,
     pub resources: i32,
     pub relations: HashMap<String, i32>,
     pub fiscal: FiscalAccount,
     pub tax_policy: TaxPolicy,
     fiscal_history: Vec<FiscalTrendPoint>,
     allocations: BudgetAllocation,
+    prices: ConsumerPriceIndex,
+    sovereign: SovereignDebt,
+    environment: EnvironmentState,
+    research: ResearchState,
+    story: StoryState,
+    resource_carry: FractionCarry,
+    /// 外交・軍事支出による関係値の変化の持ち越し。自国側と相手国側の 2 系統。
+    relation_carry: [FractionCarry; 2],
 }
 impl CountryState {
     pub(crate) fn new(
@@ -146,14 +162,15 @@ impl CountryState {
         government: String,
         population_millions: f64,
         gdp: f64,
-        stability: i32,
-        military: i32,
-        approval: i32,
+        stability: f64,
+        military: f64,
+        approval: f64,
         resources: i32,
         fiscal: FiscalAccount,
         tax_policy: TaxPolicy,
         allocations: BudgetAllocation,
     ) -> Self {
+        let prices = ConsumerPriceIndex::new(tax_policy.consumption_rate);
         Self {
             name,
             government,
@@ -168,9 +185,32 @@ impl CountryState {
             tax_policy,
             fiscal_history: Vec::new(),
             allocations,
+            prices,
+            sovereign: SovereignDebt::new(),
+            environment: EnvironmentState::new(),
+            research: ResearchState::new(),
+            story: StoryState::new(),
+            resource_carry: FractionCarry::default(),
+            relation_carry: [FractionCarry::default(); 2],
         }
     }
 
+    /// 資源を `delta` (端数可) だけ増減する。整数に満たない分は次回に持ち越す。
+    pub(crate) fn adjust_resources(&mut self, delta: f64) {
+        let whole = self.resource_carry.take(delta);
+        self.resources = (self.resources as f64 + whole)
+            .clamp(MIN_RESOURCES as f64, MAX_RESOURCES as f64) as i32;
+    }
+
+    /// 全ての相手国との関係値の変化 (自国側 `own`・相手国側 `partner`、端数可) を整数にして返す。
+    /// 整数に満たない分は次回に持ち越す。
+    pub(crate) fn take_relation_deltas(&mut self, own: f64, partner: f64) -> (i32, i32) {
+        let span = (MAX_RELATION - MIN_RELATION) as f64;
+        let own = self.relation_carry[0].take(own).clamp(-span, span);
+        let partner = self.relation_carry[1].take(partner).clamp(-span, span);
+        (own as i32, partner as i32)
+    }
+
     pub fn allocations(&self) -> BudgetAllocation {
         self.allocations
     }
@@ -199,14 +239,39 @@ impl CountryState {
             revenue: self.fiscal.total_revenue(),
             expense: self.fiscal.total_expense(),
             net_cash_flow: self.fiscal.net_cash_flow(),
-            debt: self.fiscal.debt,
+            debt: self.fiscal.debt(),
             gdp: self.gdp.max(0.0),
-            approval: self.approval as f64,
+            approval: self.approval,
             debt_ratio: self.current_debt_ratio(),
+            average_cost_of_debt: self.fiscal.average_cost_of_debt(),
+            maturity_profile: self.fiscal.maturity_profile(),
+            debt_status: self.sovereign.status(),
+            cpi: self.prices.cpi(),
+            inflation: self.prices.inflation(),
             history: self.fiscal_history.clone(),
         }
     }
 
+    pub fn consumer_prices(&self) -> &ConsumerPriceIndex {
+        &self.prices
+    }
+
+    pub fn sovereign_debt(&self) -> &SovereignDebt {
+        &self.sovereign
+    }
+
+    pub fn environment(&self) -> &EnvironmentState {
+        &self.environment
+    }
+
+    pub fn research(&self) -> &ResearchState {
+        &self.research
+    }
+
+    pub fn story(&self) -> &StoryState {
+        &self.story
+    }
+
     pub fn tax_policy(&self) -> &TaxPolicy {
         &self.tax_policy
     }
@@ -224,10 +289,10 @@ impl CountryState {
             simulation_minutes: minutes,
             revenue: self.fiscal.total_revenue(),
             expense: self.fiscal.total_expense(),
-            debt: self.fiscal.debt.max(0.0),
+            debt: self.fiscal.debt().max(0.0),
             cash_reserve: self.fiscal.cash_reserve(),
             gdp: self.gdp.max(0.0),
-            approval: self.approval as f64,
+            approval: self.approval,
             debt_ratio,
         };
         if let Some(last) = self.fiscal_history.last_mut() {
@@ -246,12 +311,50 @@ impl CountryState {
         &mut self.tax_policy
     }
 
+    pub(crate) fn consumer_prices_mut(&mut self) -> &mut ConsumerPriceIndex {
+        &mut self.prices
+    }
+
     pub(crate) fn fiscal_mut(&mut self) -> &mut FiscalAccount {
         &mut self.fiscal
     }
 
+    pub(crate) fn environment_mut(&mut self) -> &mut EnvironmentState {
+        &mut self.environment
+    }
+
+    pub(crate) fn research_mut(&mut self) -> &mut ResearchState {
+        &mut self.research
+    }
+
+    pub(crate) fn story_mut(&mut self) -> &mut StoryState {
+        &mut self.story
+    }
+
+    pub(crate) fn review_sovereign_debt(
+        &mut self,
+        outcome: &DebtCycleOutcome,
+        gdp: f64,
+    ) -> Vec<SovereignEvent> {
+        self.sovereign
+            .review(&mut self.fiscal, outcome, gdp, &self.allocations)
+    }
+
+    pub(crate) fn begin_debt_restructuring(&mut self, terms: RestructuringTerms) -> Result<()> {
+        self.sovereign.begin_restructuring(terms)
+    }
+
+    pub(crate) fn accept_bailout(&mut self) -> Result<f64> {
+        self.sovereign
+            .accept_bailout(&mut self.fiscal, &self.allocations)
+    }
+
+    pub(crate) fn decline_bailout(&mut self) -> Result<()> {
+        self.sovereign.decline_bailout()
+    }
+
     fn current_debt_ratio(&self) -> f64 {
-        let debt = self.fiscal.debt.max(0.0);
+        let debt = self.fiscal.debt().max(0.0);
         let gdp = self.gdp.max(0.0);
         if gdp > 0.0 {
             (debt / gdp) * 100.0
@@ -262,3 +365,18 @@ impl CountryState {
         }
     }
 }
+
+/// 整数で持つ指標に端数のある変化を加えるとき、整数に満たない分を次回へ持ち越す。
+#[derive(Debug, Clone, Copy, Default)]
+struct FractionCarry(f64);
+
+impl FractionCarry {
+    /// `amount` に持ち越し分を足した値の整数部分を返し、残りを持ち越す。
+    fn take(&mut self, amount: f64) -> f64 {
+        let raw = amount + self.0;
+        // 1/6 を 6 回足した 0.999… が 0 に切り捨てられないよう、わずかに寄せてから切り捨てる。
+        let whole = (raw + raw.signum() * 1e-9).trunc();
+        self.0 = raw - whole;
+        whole
+    }
+}
@@ -1,13 +1,41 @@
+mod debt;
+mod environment;
 pub mod industry;
+mod prices;
+mod sovereign;
+pub use debt::{BondIssue, MaturityBucket};
+pub use environment::{CarbonPolicy, EnvironmentState};
 pub use industry::{
-    DependencyKind, IndustryCatalog, IndustryCategory, IndustryRuntime, IndustryTickOutcome,
-    SectorDefinition, SectorDependency, SectorId, SectorOverview, SectorState,
+    BlackoutRecord, CatalogIssue, CatalogLint, DependencyGraph, DependencyKind, GraphEdge,
+    GraphFormat, GraphNode, IndustryCatalog, IndustryCategory, IndustryRuntime,
+    IndustryTickOutcome, LintSeverity, PowerGridStatus, SectorDefinition, SectorDependency,
+    SectorId, SectorModifier, SectorOverview, SectorShock, SectorState,
+};
+pub use prices::{ConsumerPriceIndex, CpiPoint, HouseholdPrices};
+pub(crate) use sovereign::SovereignEvent;
+pub use sovereign::{
+    BailoutConditions, BailoutOffer, BailoutProgram, DebtStatus, RestructuringTerms, SovereignDebt,
 };
 
+use anyhow::{Result, ensure};
 use serde::{Deserialize, Serialize};
 
+use crate::CalendarDate;
+use debt::DebtPortfolio;
+
 const HOURS_PER_YEAR: f64 = 24.0 * 365.0;
 const DEBT_CYCLE_PER_YEAR: f64 = 12.0;
+const LARGE_TRANCHE_GDP_SHARE: f64 = 0.03;
+const EMERGENCY_RATE_PREMIUM: f64 = 0.05;
+const EMERGENCY_MATURITY_YEARS: f64 = 0.25;
+const MAX_TAX_PHASE_STEPS: u32 = 30;
+const INCOME_BASE_ELASTICITY: f64 = 1.2;
+const CORPORATE_BASE_ELASTICITY: f64 = 1.6;
+const CONSUMPTION_BASE_ELASTICITY: f64 = 0.8;
+const LABOR_SUPPLY_ELASTICITY: f64 = 0.5;
+const INCOME_GDP_ELASTICITY: f64 = 0.15;
+const CORPORATE_GDP_ELASTICITY: f64 = 0.3;
+const CONSUMPTION_GDP_ELASTICITY: f64 = 0.1;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CreditRating {
@@ -38,6 +66,29 @@ impl CreditRating {
             CreditRating::D => 0.18,
         }
     }
+
+    /// 新規発行する国債の年限。格付けが低いほど長期の資金調達が難しくなる。
+    pub fn issuance_maturity_years(self) -> f64 {
+        match self {
+            CreditRating::AAA | CreditRating::AA | CreditRating::A => 10.0,
+            CreditRating::BBB => 7.0,
+            CreditRating::BB | CreditRating::B => 3.0,
+            _ => 1.0,
+        }
+    }
+
+    /// 大口の満期償還を市場で借り換えられる割合。
+    pub fn rollover_capacity(self) -> f64 {
+        match self {
+            CreditRating::AAA | CreditRating::AA | CreditRating::A | CreditRating::BBB => 1.0,
+            CreditRating::BB => 0.9,
+            CreditRating::B => 0.75,
+            CreditRating::CCC => 0.5,
+            CreditRating::CC => 0.35,
+            CreditRating::C => 0.2,
+            CreditRating::D => 0.0,
+        }
+    }
 }
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum RevenueKind {
@@ -45,6 +96,7 @@ pub enum RevenueKind {
     ResourceExport,
     Trade,
     Aid,
+    CarbonPricing,
     Other,
 }
 
@@ -58,6 +110,7 @@ pub enum ExpenseKind {
     Administration,
     Research,
     IndustrySupport,
+    Health,
     Other,
 }
 
@@ -78,6 +131,9 @@ pub struct DebtCycleOutcome {
     pub interest_due: f64,
     pub interest_paid: f64,
     pub principal_repaid: f64,
+    pub matured: f64,
+    pub rolled_over: f64,
+    pub rollover_shortfall: f64,
     pub new_issuance: f64,
     pub downgraded: Option<CreditRating>,
     pub crisis: Option<String>,
@@ -107,9 +163,30 @@ pub struct FiscalSnapshot {
     pub gdp: f64,
     pub approval: f64,
     pub debt_ratio: f64,
+    pub average_cost_of_debt: f64,
+    pub maturity_profile: Vec<MaturityBucket>,
+    pub debt_status: DebtStatus,
+    pub cpi: f64,
+    pub inflation: f64,
     pub history: Vec<FiscalTrendPoint>,
 }
 
+pub(crate) fn upgrade_rating(rating: CreditRating) -> CreditRating {
+    use CreditRating::*;
+    match rating {
+        AAA => AAA,
+        AA => AAA,
+        A => AA,
+        BBB => A,
+        BB => BBB,
+        B => BB,
+        CCC => B,
+        CC => CCC,
+        C => CC,
+        D => C,
+    }
+}
+
 pub(crate) fn downgrade_rating(rating: CreditRating) -> CreditRating {
     use CreditRating::*;
     match rating {
@@ -131,9 +208,10 @@ pub struct FiscalAccount {
     cash_reserve: f64,
     pub revenues: Vec<RevenueSource>,
     pub expenses: Vec<ExpenseItem>,
-    pub debt: f64,
+    debt: DebtPortfolio,
     pub interest_rate: f64,
     pub credit_rating: CreditRating,
+    market_access: bool,
 }
 
 impl FiscalAccount {
@@ -142,9 +220,10 @@ impl FiscalAccount {
             cash_reserve: initial_cash.max(0.0),
             revenues: Vec::new(),
             expenses: Vec::new(),
-            debt: 0.0,
+            debt: DebtPortfolio::new(),
             interest_rate: rating.base_interest_rate(),
             credit_rating: rating,
+            market_access: true,
         }
     }
 
@@ -156,6 +235,49 @@ impl FiscalAccount {
         self.cash_reserve = amount.max(0.0);
     }
 
+    pub fn debt(&self) -> f64 {
+        self.debt.total()
+    }
+
+    pub fn bond_issues(&self) -> &[BondIssue] {
+        self.debt.issues()
+    }
+
+    pub fn maturity_profile(&self) -> Vec<MaturityBucket> {
+        self.debt.maturity_profile()
+    }
+
+    /// 発行済み国債の加重平均クーポン。残高がなければ現在の新規発行金利を返す。
+    pub fn average_cost_of_debt(&self) -> f64 {
+        self.debt.average_coupon().unwrap_or(self.interest_rate)
+    }
+
+    pub fn sync_clock(&mut self, simulation_minutes: f64, date: CalendarDate) {
+        self.debt.sync_clock(simulation_minutes, date);
+    }
+
+    pub fn has_market_access(&self) -> bool {
+        self.market_access
+    }
+
+    pub(crate) fn set_market_access(&mut self, open: bool) {
+        self.market_access = open;
+    }
+
+    /// 国際機関などからの公的融資を市場を介さずに計上する。
+    pub(crate) fn issue_official_loan(&mut self, amount: f64, coupon_rate: f64, years: f64) {
+        self.debt.issue(amount, coupon_rate, years);
+    }
+
+    /// 債務元本を現金支出なしで削減し、実際に削減できた額を返す。
+    pub(crate) fn write_down_debt(&mut self, amount: f64) -> f64 {
+        self.debt.redeem(amount)
+    }
+
+    pub(crate) fn reprofile_debt(&mut self, extra_years: f64, coupon_cap: f64) {
+        self.debt.reprofile(extra_years, coupon_cap);
+    }
+
     pub fn set_credit_rating(&mut self, rating: CreditRating) {
         self.credit_rating = rating;
         self.interest_rate = rating.base_interest_rate();
@@ -194,10 +316,11 @@ impl FiscalAccount {
     }
 
     pub fn accrue_interest_hours(&mut self, hours: f64) -> f64 {
-        if self.debt <= 0.0 || hours <= 0.0 {
+        let debt = self.debt();
+        if debt <= 0.0 || hours <= 0.0 {
             return 0.0;
         }
-        let interest = self.debt * self.interest_rate * (hours / HOURS_PER_YEAR);
+        let interest = debt * self.average_cost_of_debt() * (hours / HOURS_PER_YEAR);
         if interest > 0.0 {
             self.record_expense(ExpenseKind::DebtService, interest);
         }
@@ -209,19 +332,13 @@ impl FiscalAccount {
             panic!("update_fiscal_cycle に不正な GDP が渡されました");
         }
 
-        let mut debt_ratio = if gdp > 0.0 {
-            (self.debt / gdp).max(0.0)
-        } else if self.debt > 0.0 {
-            5.0
-        } else {
-            0.0
-        };
+        let mut debt_ratio = self.debt_ratio(gdp);
 
         let base_rate = self.credit_rating.base_interest_rate();
         let risk_surcharge = (debt_ratio - 0.6).max(0.0) * 0.03;
         self.interest_rate = (base_rate + risk_surcharge).min(0.30);
 
-        let interest_due = self.debt * self.interest_rate / DEBT_CYCLE_PER_YEAR;
+        let interest_due = self.debt() * self.average_cost_of_debt() / DEBT_CYCLE_PER_YEAR;
         let mut interest_paid = 0.0;
         let mut unpaid_interest = 0.0;
         if interest_due > 0.0 {
@@ -232,23 +349,54 @@ impl FiscalAccount {
             }
             unpaid_interest = interest_due - payable;
             if unpaid_interest > 0.0 {
-                self.debt += unpaid_interest;
+                self.debt.issue(
+                    unpaid_interest,
+                    self.interest_rate,
+                    EMERGENCY_MATURITY_YEARS,
+                );
             }
         }
 
+        let matured = self.debt.take_matured();
         let mut principal_repaid = 0.0;
-        if self.debt > 0.0 {
-            let amort_target = (self.debt * 0.01).min(self.cash_reserve * 0.5);
-            if amort_target > 0.0 {
-                self.record_expense(ExpenseKind::DebtService, amort_target);
-                self.debt = (self.debt - amort_target).max(0.0);
-                principal_repaid = amort_target;
+        let mut rolled_over = 0.0;
+        let mut rollover_shortfall = 0.0;
+        if matured > 0.0 {
+            principal_repaid = matured.min(self.cash_reserve * 0.5);
+            self.record_expense(ExpenseKind::DebtService, principal_repaid);
+
+            let remaining = matured - principal_repaid;
+            let capacity = if !self.market_access {
+                0.0
+            } else if remaining > gdp * LARGE_TRANCHE_GDP_SHARE {
+                self.credit_rating.rollover_capacity()
+            } else {
+                1.0
+            };
+            rolled_over = remaining * capacity;
+            self.debt.issue(
+                rolled_over,
+                self.interest_rate,
+                self.credit_rating.issuance_maturity_years(),
+            );
+
+            let refused = remaining - rolled_over;
+            if refused > 0.0 {
+                let forced = refused.min(self.cash_reserve);
+                self.record_expense(ExpenseKind::DebtService, forced);
+                principal_repaid += forced;
+                rollover_shortfall = refused - forced;
+                self.debt.issue(
+                    rollover_shortfall,
+                    (self.interest_rate + EMERGENCY_RATE_PREMIUM).min(0.30),
+                    EMERGENCY_MATURITY_YEARS,
+                );
             }
         }
 
         let safety_reserve = (gdp * 0.04).max(25.0);
         let mut new_issuance = 0.0;
-        if self.cash_reserve < safety_reserve {
+        if self.market_access && self.cash_reserve < safety_reserve {
             let needed = safety_reserve - self.cash_reserve;
             if needed > 0.0 {
                 self.add_debt(needed);
@@ -257,18 +405,23 @@ impl FiscalAccount {
             }
         }
 
-        debt_ratio = if gdp > 0.0 {
-            (self.debt / gdp).max(0.0)
-        } else if self.debt > 0.0 {
-            5.0
-        } else {
-            0.0
-        };
+        debt_ratio = self.debt_ratio(gdp);
 
         let mut downgraded = None;
         let mut crisis = None;
 
-        if debt_ratio > 1.1 || unpaid_interest > interest_due * 0.25 {
+        if rollover_shortfall > 0.0 {
+            let previous = self.credit_rating;
+            let new_rating = downgrade_rating(previous);
+            if new_rating != previous {
+                self.set_credit_rating(new_rating);
+                downgraded = Some(new_rating);
+            }
+            crisis = Some(format!(
+                "満期を迎えた国債 {:.1} の借換えが難航し、{:.1} を高金利の短期債で穴埋めしました (格付け {:?})。",
+                matured, rollover_shortfall, self.credit_rating
+            ));
+        } else if debt_ratio > 1.1 || unpaid_interest > interest_due * 0.25 {
             let previous = self.credit_rating;
             let new_rating = downgrade_rating(previous);
             if new_rating != previous {
@@ -292,14 +445,46 @@ impl FiscalAccount {
             interest_due,
             interest_paid,
             principal_repaid,
+            matured,
+            rolled_over,
+            rollover_shortfall,
             new_issuance,
             downgraded,
             crisis,
         }
     }
 
+    /// 正の値は現在の金利と格付けに応じた年限で新規発行し、負の値は満期の近い銘柄から償還する。
     pub fn add_debt(&mut self, delta: f64) {
-        self.debt = (self.debt + delta).max(0.0);
+        if delta > 0.0 {
+            self.debt.issue(
+                delta,
+                self.interest_rate,
+                self.credit_rating.issuance_maturity_years(),
+            );
+        } else if delta < 0.0 {
+            self.debt.redeem(-delta);
+        }
+    }
+
+    /// 市場で新規に借り入れる。デフォルトなどで市場へのアクセスを失っている間は借りられず、`false` を返す。
+    pub(crate) fn borrow(&mut self, amount: f64) -> bool {
+        if !self.market_access {
+            return false;
+        }
+        self.add_debt(amount.max(0.0));
+        true
+    }
+
+    fn debt_ratio(&self, gdp: f64) -> f64 {
+        let debt = self.debt();
+        if gdp > 0.0 {
+            (debt / gdp).max(0.0)
+        } else if debt > 0.0 {
+            5.0
+        } else {
+            0.0
+        }
     }
 }
 #[derive(Debug, Clone, Copy)]
@@ -333,6 +518,9 @@ pub struct TaxPolicy {
     pub gdp_sensitivity: f64,
     pub employment_sensitivity: f64,
     lagged_revenue: f64,
+    transition: Option<TaxTransition>,
+    approval_carry: f64,
+    temporary_changes: Vec<TemporaryTaxChange>,
 }
 
 impl TaxPolicy {
@@ -369,6 +557,9 @@ impl TaxPolicy {
             gdp_sensitivity: config.gdp_sensitivity.clamp(-1.0, 1.0),
             employment_sensitivity: config.employment_sensitivity.clamp(-1.0, 1.0),
             lagged_revenue: 0.0,
+            transition: None,
+            approval_carry: 0.0,
+            temporary_changes: Vec::new(),
         }
     }
     pub fn default() -> Self {
@@ -384,15 +575,40 @@ impl TaxPolicy {
 
     pub fn collect(&mut self, gdp: f64, employment_ratio: f64, scale: f64) -> TaxOutcome {
         let gdp_scaled = gdp.max(0.0);
-        let income_base = gdp_scaled * 0.45 * self.income_rate;
-        let corporate_base = gdp_scaled * 0.35 * self.corporate_rate;
-        let consumption_base = gdp_scaled * 0.20 * self.consumption_rate;
+        let income_rate = self.effective_rate(TaxRateKind::Income);
+        let corporate_rate = self.effective_rate(TaxRateKind::Corporate);
+        let consumption_rate = self.effective_rate(TaxRateKind::Consumption);
+        let income_base = gdp_scaled
+            * 0.45
+            * income_rate
+            * laffer_factor(
+                income_rate,
+                Self::default_income_rate(),
+                INCOME_BASE_ELASTICITY,
+            );
+        let corporate_base = gdp_scaled
+            * 0.35
+            * corporate_rate
+            * laffer_factor(
+                corporate_rate,
+                Self::default_corporate_rate(),
+                CORPORATE_BASE_ELASTICITY,
+            );
+        let consumption_base = gdp_scaled
+            * 0.20
+            * consumption_rate
+            * laffer_factor(
+                consumption_rate,
+                Self::default_consumption_rate(),
+                CONSUMPTION_BASE_ELASTICITY,
+            );
         let gross = income_base + corporate_base + consumption_base;
         let deduction = self.deductions.min(gross * 0.4);
         let structural = (gross - deduction).max(0.0);
 
         let gdp_factor = 1.0 + self.gdp_sensitivity * ((gdp_scaled / 1500.0) - 1.0);
-        let employment_factor = 1.0 + self.employment_sensitivity * (employment_ratio - 0.9);
+        let employment_factor =
+            1.0 + self.employment_sensitivity * (employment_ratio * self.labor_response() - 0.9);
         let adjusted = (structural * gdp_factor * employment_factor).max(0.0) * scale;
 
         let immediate = (adjusted * 0.7) + self.lagged_revenue;
@@ -408,27 +624,395 @@ impl TaxPolicy {
     pub fn pending_revenue(&self) -> f64 {
         self.lagged_revenue
     }
+
+    /// 所得税率が基準より高いほど労働供給が細り、雇用率の実効値を押し下げる。
+    pub fn labor_response(&self) -> f64 {
+        laffer_factor(
+            self.effective_rate(TaxRateKind::Income),
+            Self::default_income_rate(),
+            LABOR_SUPPLY_ELASTICITY,
+        )
+    }
+
+    pub fn transition(&self) -> Option<&TaxTransition> {
+        self.transition.as_ref()
+    }
+
+    pub fn temporary_changes(&self) -> &[TemporaryTaxChange] {
+        &self.temporary_changes
+    }
+
+    /// 一時変更を含めた実効税率。基準税率に有効な一時変更の差分を足し、上下限で丸める。
+    pub fn effective_rate(&self, kind: TaxRateKind) -> f64 {
+        let base = match kind {
+            TaxRateKind::Income => self.income_rate,
+            TaxRateKind::Corporate => self.corporate_rate,
+            TaxRateKind::Consumption => self.consumption_rate,
+        };
+        let temporary: f64 = self
+            .temporary_changes
+            .iter()
+            .filter(|change| change.kind == kind)
+            .map(|change| change.delta)
+            .sum();
+        (base + temporary).clamp(Self::MIN_RATE, Self::MAX_RATE)
+    }
+
+    /// 税率を `minutes` 分だけ一時的に変更する。基準税率は変えず、`effective_rate` に上乗せする。
+    pub(crate) fn apply_temporary_change(&mut self, kind: TaxRateKind, delta: f64, minutes: f64) {
+        self.temporary_changes.push(TemporaryTaxChange {
+            kind,
+            delta,
+            remaining_minutes: minutes,
+        });
+    }
+
+    /// 一時的な税率変更の残り時間を進め、期限を迎えた税目を返す。
+    pub(crate) fn expire_temporary_changes(&mut self, elapsed_minutes: f64) -> Vec<TaxRateKind> {
+        let mut expired = Vec::new();
+        let mut active = Vec::with_capacity(self.temporary_changes.len());
+        for mut change in std::mem::take(&mut self.temporary_changes) {
+            change.remaining_minutes -= elapsed_minutes;
+            if change.remaining_minutes > 0.0 {
+                active.push(change);
+            } else {
+                expired.push(change.kind);
+            }
+        }
+        self.temporary_changes = active;
+        expired
+    }
+
+    /// 税制変更を予約する。既存の移行計画は破棄され、現在値から新しい目標へ段階的に移行する。
+    pub(crate) fn schedule_change(&mut self, change: TaxPolicyChange) -> Result<()> {
+        change.validate()?;
+        self.transition = Some(TaxTransition {
+            income_rate: change.income_rate.unwrap_or(self.income_rate),
+            corporate_rate: change.corporate_rate.unwrap_or(self.corporate_rate),
+            consumption_rate: change.consumption_rate.unwrap_or(self.consumption_rate),
+            deductions: change.deductions.unwrap_or(self.deductions),
+            remaining_steps: change.phase_steps,
+        });
+        Ok(())
+    }
+
+    /// 予約済みの税制変更を 1 段階進め、その段階で生じた経済・世論への影響を返す。
+    pub(crate) fn advance_transition(&mut self) -> Option<TaxStep> {
+        let transition = self.transition.as_mut()?;
+        let steps = transition.remaining_steps.max(1) as f64;
+        let income_delta = (transition.income_rate - self.income_rate) / steps;
+        let corporate_delta = (transition.corporate_rate - self.corporate_rate) / steps;
+        let consumption_delta = (transition.consumption_rate - self.consumption_rate) / steps;
+        let deductions_delta = (transition.deductions - self.deductions) / steps;
+        transition.remaining_steps = transition.remaining_steps.saturating_sub(1);
+        let remaining_steps = transition.remaining_steps;
+        if remaining_steps == 0 {
+            self.transition = None;
+        }
+
+        self.income_rate = (self.income_rate + income_delta).clamp(Self::MIN_RATE, Self::MAX_RATE);
+        self.corporate_rate =
+            (self.corporate_rate + corporate_delta).clamp(Self::MIN_RATE, Self::MAX_RATE);
+        self.consumption_rate =
+            (self.consumption_rate + consumption_delta).clamp(Self::MIN_RATE, Self::MAX_RATE);
+        self.deductions = (self.deductions + deductions_delta).max(0.0);
+
+        let segments = [
+            (
+                TaxSegment::Households,
+                -(income_delta * 100.0 * 0.5) + deductions_delta / 50.0,
+            ),
+            (TaxSegment::Businesses, -(corporate_delta * 100.0 * 0.2)),
+            (TaxSegment::Consumers, -(consumption_delta * 100.0 * 0.6)),
+        ];
+        let raw_approval: f64 =
+            segments.iter().map(|(_, impact)| impact).sum::<f64>() + self.approval_carry;
+        let approval_delta = raw_approval.trunc();
+        self.approval_carry = raw_approval - approval_delta;
+        let gdp_multiplier = (1.0
+            - income_delta * INCOME_GDP_ELASTICITY
+            - corporate_delta * CORPORATE_GDP_ELASTICITY
+            - consumption_delta * CONSUMPTION_GDP_ELASTICITY)
+            .clamp(0.9, 1.1);
+
+        Some(TaxStep {
+            segments,
+            approval_delta: approval_delta as i32,
+            gdp_multiplier,
+            remaining_steps,
+        })
+    }
+}
+
+/// 基準税率との差に応じて課税ベースを伸縮させる係数。税率が上がるほど (1 - 税率)^弾力性 で縮む。
+fn laffer_factor(rate: f64, reference: f64, elasticity: f64) -> f64 {
+    let reference_keep = (1.0 - reference).max(0.05);
+    let keep = (1.0 - rate).max(0.05);
+    (keep / reference_keep).powf(elasticity)
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TaxPolicyChange {
+    pub income_rate: Option<f64>,
+    pub corporate_rate: Option<f64>,
+    pub consumption_rate: Option<f64>,
+    pub deductions: Option<f64>,
+    pub phase_steps: u32,
+}
+
+impl TaxPolicyChange {
+    fn validate(&self) -> Result<()> {
+        ensure!(
+            self.income_rate.is_some()
+                || self.corporate_rate.is_some()
+                || self.consumption_rate.is_some()
+                || self.deductions.is_some(),
+            "変更する税目が指定されていません。"
+        );
+        ensure!(
+            (1..=MAX_TAX_PHASE_STEPS).contains(&self.phase_steps),
+            "移行段階数は 1〜{} の範囲で指定してください。",
+            MAX_TAX_PHASE_STEPS
+        );
+        for (label, rate) in [
+            ("所得税率", self.income_rate),
+            ("法人税率", self.corporate_rate),
+            ("消費税率", self.consumption_rate),
+        ] {
+            if let Some(rate) = rate {
+                ensure!(
+                    rate.is_finite() && (TaxPolicy::MIN_RATE..=TaxPolicy::MAX_RATE).contains(&rate),
+                    "{}は 0〜{:.0}% の範囲で指定してください。",
+                    label,
+                    TaxPolicy::MAX_RATE * 100.0
+                );
+            }
+        }
+        if let Some(deductions) = self.deductions {
+            ensure!(
+                deductions.is_finite() && deductions >= 0.0,
+                "控除額は0以上の有限な数値で指定してください。"
+            );
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TaxTransition {
+    pub income_rate: f64,
+    pub corporate_rate: f64,
+    pub consumption_rate: f64,
+    pub deductions: f64,
+    pub remaining_steps: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaxRateKind {
+    Income,
+    Corporate,
+    Consumption,
+}
+
+impl TaxRateKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            TaxRateKind::Income => "所得税",
+            TaxRateKind::Corporate => "法人税",
+            TaxRateKind::Consumption => "消費税",
+        }
+    }
+}
+
+/// 期限付きの税率変更。`delta` は基準税率に上乗せする量で、実効税率は上下限で丸める。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TemporaryTaxChange {
+    pub kind: TaxRateKind,
+    pub delta: f64,
+    pub remaining_minutes: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaxSegment {
+    Households,
+    Businesses,
+    Consumers,
+}
+
+impl TaxSegment {
+    pub fn label(self) -> &'static str {
+        match self {
+            TaxSegment::Households => "家計",
+            TaxSegment::Businesses => "企業",
+            TaxSegment::Consumers => "消費者",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TaxStep {
+    pub segments: [(TaxSegment, f64); 3],
+    pub approval_delta: i32,
+    pub gdp_multiplier: f64,
+    pub remaining_steps: u32,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn tax_transition_phases_in_over_steps() {
+        let mut policy = TaxPolicy::default();
+        policy
+            .schedule_change(TaxPolicyChange {
+                income_rate: Some(0.28),
+                deductions: Some(40.0),
+                phase_steps: 4,
+                ..TaxPolicyChange::default()
+            })
+            .expect("change should be accepted");
+
+        let first = policy.advance_transition().expect("first step");
+        assert_eq!(first.remaining_steps, 3);
+        assert!((policy.income_rate - 0.205).abs() < 1e-9);
+        assert!((policy.deductions - 10.0).abs() < 1e-9);
+        assert!(first.gdp_multiplier < 1.0);
+
+        for _ in 0..3 {
+            policy.advance_transition().expect("remaining steps");
+        }
+        assert!((policy.income_rate - 0.28).abs() < 1e-9);
+        assert!(policy.transition().is_none());
+        assert!(policy.advance_transition().is_none());
+    }
+
+    #[test]
+    fn tax_change_rejects_invalid_requests() {
+        let mut policy = TaxPolicy::default();
+        let out_of_range = TaxPolicyChange {
+            consumption_rate: Some(0.9),
+            phase_steps: 2,
+            ..TaxPolicyChange::default()
+        };
+        assert!(policy.schedule_change(out_of_range).is_err());
+        let no_steps = TaxPolicyChange {
+            corporate_rate: Some(0.2),
+            phase_steps: 0,
+            ..TaxPolicyChange::default()
+        };
+        assert!(policy.schedule_change(no_steps).is_err());
+        assert!(policy.schedule_change(TaxPolicyChange::default()).is_err());
+        assert!(policy.transition().is_none());
+    }
+
+    #[test]
+    fn temporary_tax_changes_leave_base_rates_untouched() {
+        let mut policy = TaxPolicy::default();
+        policy.apply_temporary_change(TaxRateKind::Consumption, 0.9, 120.0);
+        policy.apply_temporary_change(TaxRateKind::Income, -0.05, 60.0);
+        assert_eq!(policy.effective_rate(TaxRateKind::Consumption), 0.6);
+        assert!((policy.effective_rate(TaxRateKind::Income) - 0.13).abs() < 1e-9);
+        assert_eq!(policy.consumption_rate, 0.08);
+        assert_eq!(policy.income_rate, 0.18);
+
+        assert_eq!(
+            policy.expire_temporary_changes(60.0),
+            vec![TaxRateKind::Income]
+        );
+        assert!((policy.effective_rate(TaxRateKind::Income) - 0.18).abs() < 1e-9);
+        assert_eq!(policy.temporary_changes().len(), 1);
+
+        assert_eq!(
+            policy.expire_temporary_changes(60.0),
+            vec![TaxRateKind::Consumption]
+        );
+        assert!((policy.effective_rate(TaxRateKind::Consumption) - 0.08).abs() < 1e-9);
+        assert!(policy.temporary_changes().is_empty());
+    }
+
+    #[test]
+    fn temporary_tax_changes_do_not_leak_into_scheduled_transitions() {
+        let mut policy = TaxPolicy::default();
+        policy.apply_temporary_change(TaxRateKind::Income, 0.1, 120.0);
+        policy.apply_temporary_change(TaxRateKind::Corporate, 0.05, 120.0);
+        policy
+            .schedule_change(TaxPolicyChange {
+                corporate_rate: Some(0.3),
+                phase_steps: 2,
+                ..TaxPolicyChange::default()
+            })
+            .expect("schedule");
+        assert_eq!(policy.transition().unwrap().income_rate, 0.18);
+
+        policy.advance_transition().expect("step");
+        assert!((policy.effective_rate(TaxRateKind::Corporate) - 0.31).abs() < 1e-9);
+        policy.advance_transition().expect("step");
+        policy.expire_temporary_changes(120.0);
+        assert!(policy.transition().is_none());
+        assert!((policy.effective_rate(TaxRateKind::Income) - 0.18).abs() < 1e-9);
+        assert!((policy.effective_rate(TaxRateKind::Corporate) - 0.3).abs() < 1e-9);
+    }
+
+    #[test]
+    fn excessive_rates_shrink_revenue_past_laffer_peak() {
+        let mut moderate = TaxPolicy::default();
+        moderate.corporate_rate = 0.35;
+        let mut excessive = TaxPolicy::default();
+        excessive.corporate_rate = 0.6;
+        let moderate_revenue = moderate.collect(1_500.0, 0.9, 1.0).deferred;
+        let excessive_revenue = excessive.collect(1_500.0, 0.9, 1.0).deferred;
+        assert!(excessive_revenue < moderate_revenue);
+    }
+
     #[test]
     fn update_fiscal_cycle_pays_interest_and_reduces_debt() {
         let mut account = FiscalAccount::new(300.0, CreditRating::BBB);
-        account.debt = 1_200.0;
+        account.add_debt(1_200.0);
         let outcome = account.update_fiscal_cycle(1_800.0);
         assert!(outcome.interest_due > 0.0);
         assert!(outcome.interest_paid > 0.0);
         assert!(outcome.principal_repaid >= 0.0);
-        assert!(account.debt >= 0.0);
+        assert!(account.debt() >= 0.0);
+    }
+
+    #[test]
+    fn matured_tranche_rolls_over_under_investment_grade() {
+        let mut account = FiscalAccount::new(40.0, CreditRating::A);
+        account.add_debt(600.0);
+        account.sync_clock(11.0 * 365.0 * 24.0 * 60.0, CalendarDate::new(2036, 1, 1));
+        let outcome = account.update_fiscal_cycle(2_000.0);
+        assert!((outcome.matured - 600.0).abs() < 1e-6);
+        assert!(outcome.rolled_over > 500.0);
+        assert!(outcome.rollover_shortfall.abs() < 1e-9);
+        assert_eq!(account.credit_rating, CreditRating::A);
+    }
+
+    #[test]
+    fn matured_tranche_under_junk_rating_creates_rollover_risk() {
+        let mut account = FiscalAccount::new(40.0, CreditRating::CCC);
+        account.add_debt(600.0);
+        account.sync_clock(2.0 * 365.0 * 24.0 * 60.0, CalendarDate::new(2027, 1, 1));
+        let outcome = account.update_fiscal_cycle(2_000.0);
+        assert!(outcome.rollover_shortfall > 0.0);
+        assert_eq!(outcome.downgraded, Some(CreditRating::CC));
+        assert!(
+            outcome
+                .crisis
+                .as_deref()
+                .unwrap_or_default()
+                .contains("借換え")
+        );
+        let profile = account.maturity_profile();
+        assert!(profile[0].principal > 0.0);
+        assert!(account.average_cost_of_debt() > CreditRating::CCC.base_interest_rate());
     }
 
     #[test]
     fn update_fiscal_cycle_triggers_crisis_on_excess_debt() {
         let mut account = FiscalAccount::new(50.0, CreditRating::BBB);
-        account.debt = 2_500.0;
+        account.add_debt(2_500.0);
         let outcome = account.update_fiscal_cycle(1_500.0);
         assert!(outcome.crisis.is_some());
         assert!(matches!(outcome.downgraded, Some(_)));
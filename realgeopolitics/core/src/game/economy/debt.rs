@@ -0,0 +1,228 @@
+use serde::{Deserialize, Serialize};
+
+use crate::CalendarDate;
+use crate::game::constants::MINUTES_PER_DAY;
+
+const MINUTES_PER_YEAR: f64 = 365.0 * MINUTES_PER_DAY as f64;
+const MATURITY_BUCKETS: [(&str, f64, f64); 5] = [
+    ("1年以内", 0.0, 1.0),
+    ("1〜3年", 1.0, 3.0),
+    ("3〜5年", 3.0, 5.0),
+    ("5〜10年", 5.0, 10.0),
+    ("10年超", 10.0, f64::INFINITY),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BondIssue {
+    pub principal: f64,
+    pub coupon_rate: f64,
+    pub issued_on: CalendarDate,
+    pub issued_at_minutes: f64,
+    pub maturity_minutes: f64,
+}
+
+impl BondIssue {
+    pub fn years_to_maturity(&self, now_minutes: f64) -> f64 {
+        ((self.maturity_minutes - now_minutes) / MINUTES_PER_YEAR).max(0.0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MaturityBucket {
+    pub label: String,
+    pub principal: f64,
+}
+
+/// 発行済み国債の集合。残高は銘柄ごとの元本の合計として扱い、満期の近い順に償還する。
+#[derive(Debug, Clone)]
+pub(crate) struct DebtPortfolio {
+    issues: Vec<BondIssue>,
+    as_of_minutes: f64,
+    as_of_date: CalendarDate,
+}
+
+impl DebtPortfolio {
+    pub fn new() -> Self {
+        Self {
+            issues: Vec::new(),
+            as_of_minutes: 0.0,
+            as_of_date: CalendarDate::from_start(),
+        }
+    }
+
+    pub fn sync_clock(&mut self, minutes: f64, date: CalendarDate) {
+        self.as_of_minutes = minutes.max(0.0);
+        self.as_of_date = date;
+    }
+
+    pub fn issues(&self) -> &[BondIssue] {
+        &self.issues
+    }
+
+    pub fn total(&self) -> f64 {
+        self.issues.iter().map(|issue| issue.principal).sum()
+    }
+
+    pub fn average_coupon(&self) -> Option<f64> {
+        let total = self.total();
+        if total <= f64::EPSILON {
+            return None;
+        }
+        let weighted: f64 = self
+            .issues
+            .iter()
+            .map(|issue| issue.principal * issue.coupon_rate)
+            .sum();
+        Some(weighted / total)
+    }
+
+    /// 新規発行する。同じ月に同じ年限で発行済みの銘柄があれば、元本で加重平均して 1 銘柄にまとめる。
+    pub fn issue(&mut self, principal: f64, coupon_rate: f64, maturity_years: f64) {
+        if principal <= 0.0 || !principal.is_finite() {
+            return;
+        }
+        let maturity_years = maturity_years.max(0.0);
+        let mut issue = BondIssue {
+            principal,
+            coupon_rate: coupon_rate.max(0.0),
+            issued_on: self.as_of_date,
+            issued_at_minutes: self.as_of_minutes,
+            maturity_minutes: self.as_of_minutes + maturity_years * MINUTES_PER_YEAR,
+        };
+        let as_of_date = self.as_of_date;
+        if let Some(index) = self.issues.iter().position(|existing| {
+            existing.issued_on.year == as_of_date.year
+                && existing.issued_on.month == as_of_date.month
+                && ((existing.maturity_minutes - existing.issued_at_minutes) / MINUTES_PER_YEAR
+                    - maturity_years)
+                    .abs()
+                    < 1e-6
+        }) {
+            let existing = self.issues.remove(index);
+            let total = existing.principal + issue.principal;
+            let blend =
+                |old: f64, new: f64| (old * existing.principal + new * issue.principal) / total;
+            issue = BondIssue {
+                principal: total,
+                coupon_rate: blend(existing.coupon_rate, issue.coupon_rate),
+                issued_on: existing.issued_on,
+                issued_at_minutes: blend(existing.issued_at_minutes, issue.issued_at_minutes),
+                maturity_minutes: blend(existing.maturity_minutes, issue.maturity_minutes),
+            };
+        }
+        let position = self
+            .issues
+            .iter()
+            .position(|existing| existing.maturity_minutes > issue.maturity_minutes)
+            .unwrap_or(self.issues.len());
+        self.issues.insert(position, issue);
+    }
+
+    /// 満期の近い銘柄から元本を減らし、実際に減らせた額を返す。
+    pub fn redeem(&mut self, amount: f64) -> f64 {
+        let mut remaining = amount.max(0.0);
+        for issue in self.issues.iter_mut() {
+            if remaining <= 0.0 {
+                break;
+            }
+            let applied = issue.principal.min(remaining);
+            issue.principal -= applied;
+            remaining -= applied;
+        }
+        self.issues.retain(|issue| issue.principal > f64::EPSILON);
+        amount.max(0.0) - remaining
+    }
+
+    /// 全銘柄の満期を延長し、クーポンを上限で抑える (債務再編のリプロファイリング)。
+    pub fn reprofile(&mut self, extra_years: f64, coupon_cap: f64) {
+        for issue in self.issues.iter_mut() {
+            issue.maturity_minutes += extra_years.max(0.0) * MINUTES_PER_YEAR;
+            issue.coupon_rate = issue.coupon_rate.min(coupon_cap.max(0.0));
+        }
+    }
+
+    /// 現時点で満期を迎えた銘柄を取り出し、その元本合計を返す。
+    pub fn take_matured(&mut self) -> f64 {
+        let now = self.as_of_minutes;
+        let mut matured = 0.0;
+        self.issues.retain(|issue| {
+            if issue.maturity_minutes <= now {
+                matured += issue.principal;
+                false
+            } else {
+                true
+            }
+        });
+        matured
+    }
+
+    pub fn maturity_profile(&self) -> Vec<MaturityBucket> {
+        MATURITY_BUCKETS
+            .iter()
+            .map(|(label, from, to)| MaturityBucket {
+                label: label.to_string(),
+                principal: self
+                    .issues
+                    .iter()
+                    .filter(|issue| {
+                        let years = issue.years_to_maturity(self.as_of_minutes);
+                        years >= *from && years < *to
+                    })
+                    .map(|issue| issue.principal)
+                    .sum(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redeem_consumes_nearest_maturities_first() {
+        let mut portfolio = DebtPortfolio::new();
+        portfolio.issue(100.0, 0.05, 10.0);
+        portfolio.issue(50.0, 0.03, 1.0);
+        let redeemed = portfolio.redeem(70.0);
+        assert!((redeemed - 70.0).abs() < 1e-9);
+        assert_eq!(portfolio.issues().len(), 1);
+        assert!((portfolio.issues()[0].principal - 80.0).abs() < 1e-9);
+        assert!((portfolio.issues()[0].coupon_rate - 0.05).abs() < 1e-9);
+    }
+
+    #[test]
+    fn repeated_issues_within_a_month_share_one_tranche() {
+        let mut portfolio = DebtPortfolio::new();
+        for hour in 0..24 * 365 {
+            let minutes = hour as f64 * 60.0;
+            let month = (hour / (24 * 31)).min(11) as u8 + 1;
+            portfolio.sync_clock(minutes, CalendarDate::new(2025, month, 1));
+            let coupon = if hour % 2 == 0 { 0.03 } else { 0.05 };
+            portfolio.issue(10.0, coupon, 10.0);
+        }
+        assert!(portfolio.issues().len() <= 12);
+        assert!((portfolio.total() - 87_600.0).abs() < 1e-6);
+        assert!((portfolio.average_coupon().expect("average coupon") - 0.04).abs() < 1e-9);
+        let first = &portfolio.issues()[0];
+        let tenor = (first.maturity_minutes - first.issued_at_minutes) / MINUTES_PER_YEAR;
+        assert!((tenor - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn maturity_profile_and_average_coupon_follow_issues() {
+        let mut portfolio = DebtPortfolio::new();
+        portfolio.issue(100.0, 0.02, 0.5);
+        portfolio.issue(300.0, 0.06, 7.0);
+        let profile = portfolio.maturity_profile();
+        assert!((profile[0].principal - 100.0).abs() < 1e-9);
+        assert!((profile[3].principal - 300.0).abs() < 1e-9);
+        let average = portfolio.average_coupon().expect("average coupon");
+        assert!((average - 0.05).abs() < 1e-9);
+
+        portfolio.sync_clock(MINUTES_PER_YEAR, CalendarDate::new(2026, 1, 1));
+        let matured = portfolio.take_matured();
+        assert!((matured - 100.0).abs() < 1e-9);
+        assert!((portfolio.total() - 300.0).abs() < 1e-9);
+    }
+}
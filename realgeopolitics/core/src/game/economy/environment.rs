@@ -0,0 +1,221 @@
+use anyhow::{Result, ensure};
+
+use crate::game::constants::MINUTES_PER_DAY;
+
+const DAY_MINUTES: f64 = MINUTES_PER_DAY as f64;
+const EMISSION_SMOOTHING_MINUTES: f64 = 3.0 * DAY_MINUTES;
+const POLLUTION_SMOOTHING_MINUTES: f64 = 14.0 * DAY_MINUTES;
+/// 日次排出量 / GDP を汚染指数 (0〜100) に換算する係数。
+const POLLUTION_PER_INTENSITY: f64 = 12.0;
+const POLLUTION_APPROVAL_THRESHOLD: f64 = 40.0;
+const POLLUTION_APPROVAL_PER_DAY: f64 = 3.0;
+/// 汚染指数 100 のとき、GDP に対して 1 日あたりに発生する保健支出の比率。
+const HEALTH_COST_GDP_SHARE_PER_DAY: f64 = 0.01;
+const MAX_CARBON_PRICE: f64 = 500.0;
+const CAP_REFERENCE_PRICE: f64 = 25.0;
+const CAP_PRICE_EXPONENT: f64 = 3.0;
+
+/// 炭素価格付けの手段。税は価格を固定し、排出枠は上限に対する排出量から価格が決まる。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CarbonPolicy {
+    None,
+    Tax { price: f64 },
+    Cap { limit_per_day: f64 },
+}
+
+impl CarbonPolicy {
+    pub fn label(&self) -> String {
+        match self {
+            CarbonPolicy::None => "なし".to_string(),
+            CarbonPolicy::Tax { price } => format!("炭素税 {:.1}", price),
+            CarbonPolicy::Cap { limit_per_day } => format!("排出枠 {:.0}/日", limit_per_day),
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        match *self {
+            CarbonPolicy::None => {}
+            CarbonPolicy::Tax { price } => {
+                ensure!(
+                    price.is_finite() && (0.0..=MAX_CARBON_PRICE).contains(&price),
+                    "炭素税は 0〜{} の範囲で指定してください",
+                    MAX_CARBON_PRICE
+                );
+            }
+            CarbonPolicy::Cap { limit_per_day } => {
+                ensure!(
+                    limit_per_day.is_finite() && limit_per_day > 0.0,
+                    "排出枠は正の数値で指定してください"
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct EnvironmentOutcome {
+    pub carbon_revenue: f64,
+    pub health_cost: f64,
+    pub approval_penalty: f64,
+}
+
+/// 国別の排出量・汚染指数と炭素価格政策。
+#[derive(Debug, Clone)]
+pub struct EnvironmentState {
+    policy: CarbonPolicy,
+    emissions_per_day: f64,
+    pollution: f64,
+    carbon_price: f64,
+}
+
+impl EnvironmentState {
+    pub fn new() -> Self {
+        Self {
+            policy: CarbonPolicy::None,
+            emissions_per_day: 0.0,
+            pollution: 0.0,
+            carbon_price: 0.0,
+        }
+    }
+
+    pub fn policy(&self) -> CarbonPolicy {
+        self.policy
+    }
+
+    pub fn emissions_per_day(&self) -> f64 {
+        self.emissions_per_day
+    }
+
+    pub fn pollution(&self) -> f64 {
+        self.pollution
+    }
+
+    /// 直近の実効炭素価格 (排出 1 単位あたり)。
+    pub fn carbon_price(&self) -> f64 {
+        self.carbon_price
+    }
+
+    pub(crate) fn set_policy(&mut self, policy: CarbonPolicy) -> Result<()> {
+        policy.validate()?;
+        self.policy = policy;
+        self.carbon_price = self.price_for(self.emissions_per_day);
+        Ok(())
+    }
+
+    /// 今回の tick で割り当てられた排出量を取り込み、炭素収入と汚染による影響を返す。
+    pub(crate) fn absorb(&mut self, emissions: f64, minutes: f64, gdp: f64) -> EnvironmentOutcome {
+        let minutes = minutes.max(0.0);
+        if minutes <= 0.0 {
+            return EnvironmentOutcome::default();
+        }
+        let emissions = emissions.max(0.0);
+        let rate = emissions * DAY_MINUTES / minutes;
+        let weight = (minutes / EMISSION_SMOOTHING_MINUTES).clamp(0.0, 1.0);
+        self.emissions_per_day = self.emissions_per_day * (1.0 - weight) + rate * weight;
+        self.carbon_price = self.price_for(self.emissions_per_day);
+        let carbon_revenue = match self.policy {
+            CarbonPolicy::None => 0.0,
+            _ => emissions * self.carbon_price,
+        };
+
+        let target = if gdp > f64::EPSILON {
+            (self.emissions_per_day / gdp * POLLUTION_PER_INTENSITY).clamp(0.0, 100.0)
+        } else {
+            100.0
+        };
+        let weight = (minutes / POLLUTION_SMOOTHING_MINUTES).clamp(0.0, 1.0);
+        self.pollution = self.pollution * (1.0 - weight) + target * weight;
+
+        let days = minutes / DAY_MINUTES;
+        let health_cost =
+            gdp.max(0.0) * HEALTH_COST_GDP_SHARE_PER_DAY * (self.pollution / 100.0) * days;
+        let approval_penalty = if self.pollution > POLLUTION_APPROVAL_THRESHOLD {
+            let excess = (self.pollution - POLLUTION_APPROVAL_THRESHOLD)
+                / (100.0 - POLLUTION_APPROVAL_THRESHOLD);
+            excess * POLLUTION_APPROVAL_PER_DAY * days
+        } else {
+            0.0
+        };
+
+        EnvironmentOutcome {
+            carbon_revenue,
+            health_cost,
+            approval_penalty,
+        }
+    }
+
+    fn price_for(&self, emissions_per_day: f64) -> f64 {
+        match self.policy {
+            CarbonPolicy::None => 0.0,
+            CarbonPolicy::Tax { price } => price,
+            CarbonPolicy::Cap { limit_per_day } => {
+                let pressure = (emissions_per_day / limit_per_day).max(0.0);
+                (CAP_REFERENCE_PRICE * pressure.powf(CAP_PRICE_EXPONENT)).min(MAX_CARBON_PRICE)
+            }
+        }
+    }
+}
+
+impl Default for EnvironmentState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn carbon_tax_collects_revenue_on_emissions() {
+        let mut env = EnvironmentState::new();
+        assert!(env.set_policy(CarbonPolicy::Tax { price: -1.0 }).is_err());
+        env.set_policy(CarbonPolicy::Tax { price: 10.0 }).unwrap();
+        let outcome = env.absorb(50.0, 60.0, 2_000.0);
+        assert!((outcome.carbon_revenue - 500.0).abs() < 1e-9);
+        assert!(env.emissions_per_day() > 0.0);
+    }
+
+    #[test]
+    fn tight_cap_raises_carbon_price() {
+        let mut loose = EnvironmentState::new();
+        loose
+            .set_policy(CarbonPolicy::Cap {
+                limit_per_day: 10_000.0,
+            })
+            .unwrap();
+        let mut tight = EnvironmentState::new();
+        tight
+            .set_policy(CarbonPolicy::Cap {
+                limit_per_day: 1_000.0,
+            })
+            .unwrap();
+        for _ in 0..48 {
+            loose.absorb(100.0, 60.0, 2_000.0);
+            tight.absorb(100.0, 60.0, 2_000.0);
+        }
+        assert!(tight.carbon_price() > loose.carbon_price() * 100.0);
+    }
+
+    #[test]
+    fn heavy_pollution_costs_approval_and_health_spending() {
+        let mut env = EnvironmentState::new();
+        let mut penalty = 0.0;
+        let mut health = 0.0;
+        for _ in 0..(24 * 30) {
+            let outcome = env.absorb(1_000.0, 60.0, 1_000.0);
+            penalty += outcome.approval_penalty;
+            health += outcome.health_cost;
+        }
+        assert!(env.pollution() > 80.0);
+        assert!(penalty > 0.0);
+        assert!(health > 0.0);
+
+        // 支持率の低下は端数のまま返し、短い tick でも経過時間に比例する。
+        let hourly = env.absorb(1_000.0, 60.0, 1_000.0).approval_penalty;
+        assert!(hourly > 0.0 && hourly < 1.0);
+        let short = env.absorb(1_000.0, 6.0, 1_000.0).approval_penalty;
+        assert!((short * 10.0 - hourly).abs() < hourly * 0.01);
+    }
+}
@@ -0,0 +1,65 @@
+use crate::game::constants::MINUTES_PER_DAY;
+
+const MINUTES_PER_YEAR: f64 = 365.0 * MINUTES_PER_DAY as f64;
+/// 初期資本ストックは基準生産量に対して 1 割の余力を持たせる。
+const INITIAL_CAPITAL_HEADROOM: f64 = 1.1;
+const DEPRECIATION_PER_YEAR: f64 = 0.06;
+const PRIVATE_REINVESTMENT_SHARE: f64 = 0.15;
+/// 生産能力 1 単位を積み増すのに必要な投資額 (基準コストの何時間分か)。
+const CAPITAL_COST_HOURS: f64 = 2_000.0;
+const MIN_CAPITAL_SHARE: f64 = 0.1;
+
+pub(crate) fn initial_capital(base_output: f64) -> f64 {
+    base_output.max(0.1) * INITIAL_CAPITAL_HEADROOM
+}
+
+/// 経過時間に応じた減耗量。
+pub(crate) fn depreciation(capital: f64, minutes: f64) -> f64 {
+    capital.max(0.0) * DEPRECIATION_PER_YEAR * (minutes.max(0.0) / MINUTES_PER_YEAR)
+}
+
+/// 利益の一部が民間投資として再投資され、生産能力の単位に換算される。
+pub(crate) fn private_investment(revenue: f64, cost: f64, base_cost: f64) -> f64 {
+    let profit = revenue - cost;
+    if profit <= 0.0 || revenue <= f64::EPSILON {
+        return 0.0;
+    }
+    let margin = (profit / revenue).clamp(0.0, 1.0);
+    capital_units(
+        profit * PRIVATE_REINVESTMENT_SHARE * (0.5 + margin),
+        base_cost,
+    )
+}
+
+pub(crate) fn capital_units(amount: f64, base_cost: f64) -> f64 {
+    if amount <= 0.0 {
+        return 0.0;
+    }
+    amount / (base_cost.max(0.05) * CAPITAL_COST_HOURS)
+}
+
+/// 資本ストックは基準生産量の一定割合を下回らない (最低限の設備は残る)。
+pub(crate) fn capital_floor(base_output: f64) -> f64 {
+    base_output.max(0.1) * MIN_CAPITAL_SHARE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn depreciation_scales_with_elapsed_time() {
+        let yearly = depreciation(100.0, MINUTES_PER_YEAR);
+        assert!((yearly - 6.0).abs() < 1e-9);
+        assert!((depreciation(100.0, 60.0) * 365.0 * 24.0 - yearly).abs() < 1e-9);
+    }
+
+    #[test]
+    fn only_profitable_sectors_invest() {
+        assert_eq!(private_investment(100.0, 120.0, 50.0), 0.0);
+        let modest = private_investment(1_000.0, 900.0, 50.0);
+        let rich = private_investment(1_000.0, 400.0, 50.0);
+        assert!(modest > 0.0);
+        assert!(rich > modest * 6.0);
+    }
+}
@@ -130,6 +130,9 @@ sectors:
                     base_cost: 40.0,
                     price_sensitivity: 0.5,
                     employment: 100.0,
+                    household_share: 0.0,
+                    emission_factor: 0.0,
+                    efficiency: 1.0,
                     dependencies: Vec::new(),
                 },
             )
@@ -144,6 +147,9 @@ sectors:
                 base_cost: 45.0,
                 price_sensitivity: 0.6,
                 employment: 110.0,
+                household_share: 0.0,
+                emission_factor: 0.0,
+                efficiency: 1.0,
                 dependencies: Vec::new(),
             },
         );
@@ -169,6 +169,9 @@ mod tests {
             base_cost: 80.0,
             price_sensitivity: 0.4,
             employment: 50.0,
+            household_share: 0.0,
+            emission_factor: 0.0,
+            efficiency: 1.0,
             dependencies: Vec::new(),
         };
         catalog
@@ -183,6 +186,9 @@ mod tests {
             base_cost: 90.0,
             price_sensitivity: 0.5,
             employment: 80.0,
+            household_share: 0.0,
+            emission_factor: 0.0,
+            efficiency: 1.0,
             dependencies: vec![SectorDependency {
                 sector: "energy".into(),
                 category: Some(IndustryCategory::Energy),
@@ -221,6 +227,9 @@ mod tests {
             base_cost: 60.0,
             price_sensitivity: 0.45,
             employment: 95.0,
+            household_share: 0.0,
+            emission_factor: 0.0,
+            efficiency: 1.0,
             dependencies: Vec::new(),
         };
         catalog
@@ -235,6 +244,9 @@ mod tests {
             base_cost: 85.0,
             price_sensitivity: 0.5,
             employment: 90.0,
+            household_share: 0.0,
+            emission_factor: 0.0,
+            efficiency: 1.0,
             dependencies: vec![SectorDependency {
                 sector: "services".into(),
                 category: Some(IndustryCategory::Tertiary),
@@ -250,12 +262,14 @@ mod tests {
             service_id.clone(),
             SectorMetrics {
                 output: 120.0,
+                price: 85.0,
                 revenue: 0.0,
                 cost: 0.0,
                 sales: 100.0,
                 demand: 220.0,
                 inventory: 0.0,
                 unmet_demand: 40.0,
+                emissions: 0.0,
             },
         );
 
@@ -0,0 +1,398 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::str::FromStr;
+
+use anyhow::{Result, bail};
+
+use super::model::{DependencyKind, IndustryCatalog, IndustryCategory, SectorId, SectorMetrics};
+
+const MIN_PEN_WIDTH: f64 = 1.0;
+const MAX_PEN_WIDTH: f64 = 6.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    Dot,
+    Mermaid,
+}
+
+impl FromStr for GraphFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "dot" | "graphviz" => Ok(GraphFormat::Dot),
+            "mermaid" | "mmd" => Ok(GraphFormat::Mermaid),
+            other => bail!("未知のグラフ形式です: {} (dot / mermaid)", other),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GraphNode {
+    pub id: SectorId,
+    pub name: String,
+    /// カタログに存在しない参照先 (未解放のセクターなど)。
+    pub missing: bool,
+}
+
+/// 供給側 `from` から依存側 `to` への辺。
+#[derive(Debug, Clone)]
+pub struct GraphEdge {
+    pub from: SectorId,
+    pub to: SectorId,
+    pub kind: DependencyKind,
+    pub requirement: f64,
+    pub elasticity: f64,
+    /// 直近 tick の依存側生産量 × requirement。`with_flows` を呼んだ場合のみ設定される。
+    pub flow: Option<f64>,
+}
+
+/// 産業カタログの依存関係グラフ。
+#[derive(Debug, Clone, Default)]
+pub struct DependencyGraph {
+    nodes: Vec<GraphNode>,
+    edges: Vec<GraphEdge>,
+}
+
+impl DependencyGraph {
+    pub fn from_catalog(catalog: &IndustryCatalog) -> Self {
+        let mut nodes: Vec<GraphNode> = catalog
+            .sectors()
+            .map(|(id, def)| GraphNode {
+                id: id.clone(),
+                name: def.name.clone(),
+                missing: false,
+            })
+            .collect();
+        let mut edges = Vec::new();
+        for (id, def) in catalog.sectors() {
+            for dep in &def.dependencies {
+                let from = dep.resolve_sector(id.category);
+                if catalog.get(&from).is_none() && !nodes.iter().any(|node| node.id == from) {
+                    nodes.push(GraphNode {
+                        id: from.clone(),
+                        name: from.key.clone(),
+                        missing: true,
+                    });
+                }
+                edges.push(GraphEdge {
+                    from,
+                    to: id.clone(),
+                    kind: dep.dependency,
+                    requirement: dep.requirement,
+                    elasticity: dep.elasticity,
+                    flow: None,
+                });
+            }
+        }
+        nodes.sort_by(|a, b| sort_key(&a.id).cmp(&sort_key(&b.id)));
+        edges.sort_by(|a, b| {
+            sort_key(&a.to)
+                .cmp(&sort_key(&b.to))
+                .then_with(|| sort_key(&a.from).cmp(&sort_key(&b.from)))
+        });
+        Self { nodes, edges }
+    }
+
+    /// 直近 tick の生産量から各辺の流量を求め、描画時の線の太さに反映する。
+    pub fn with_flows(mut self, metrics: &HashMap<SectorId, SectorMetrics>) -> Self {
+        for edge in &mut self.edges {
+            let output = metrics.get(&edge.to).map(|m| m.output).unwrap_or(0.0);
+            edge.flow = Some((output * edge.requirement).max(0.0));
+        }
+        self
+    }
+
+    pub fn nodes(&self) -> &[GraphNode] {
+        &self.nodes
+    }
+
+    pub fn edges(&self) -> &[GraphEdge] {
+        &self.edges
+    }
+
+    pub fn render(&self, format: GraphFormat) -> String {
+        match format {
+            GraphFormat::Dot => self.to_dot(),
+            GraphFormat::Mermaid => self.to_mermaid(),
+        }
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut out = String::new();
+        out.push_str("digraph industry {\n");
+        out.push_str("  rankdir=LR;\n");
+        out.push_str("  node [shape=box, style=rounded];\n");
+        for category in IndustryCategory::iter() {
+            let members: Vec<&GraphNode> = self
+                .nodes
+                .iter()
+                .filter(|node| node.id.category == category)
+                .collect();
+            if members.is_empty() {
+                continue;
+            }
+            let _ = writeln!(out, "  subgraph cluster_{} {{", category);
+            let _ = writeln!(out, "    label=\"{}\";", category);
+            for node in members {
+                let style = if node.missing {
+                    ", style=\"rounded,dashed\""
+                } else {
+                    ""
+                };
+                let _ = writeln!(
+                    out,
+                    "    {} [label=\"{}\\n{}\"{}];",
+                    dot_id(&node.id),
+                    escape_dot(&node.name),
+                    escape_dot(&node.id.to_string()),
+                    style
+                );
+            }
+            out.push_str("  }\n");
+        }
+        let max_flow = self.max_flow();
+        for edge in &self.edges {
+            let style = match edge.kind {
+                DependencyKind::Input => "solid",
+                DependencyKind::Cost => "dashed",
+                DependencyKind::Demand => "dotted",
+            };
+            let _ = writeln!(
+                out,
+                "  {} -> {} [label=\"{}\", style={}, penwidth={:.2}];",
+                dot_id(&edge.from),
+                dot_id(&edge.to),
+                edge_label(edge),
+                style,
+                pen_width(edge, max_flow)
+            );
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    pub fn to_mermaid(&self) -> String {
+        let mut out = String::new();
+        out.push_str("flowchart LR\n");
+        for category in IndustryCategory::iter() {
+            let members: Vec<&GraphNode> = self
+                .nodes
+                .iter()
+                .filter(|node| node.id.category == category)
+                .collect();
+            if members.is_empty() {
+                continue;
+            }
+            let _ = writeln!(out, "  subgraph {}", category);
+            for node in members {
+                let _ = writeln!(
+                    out,
+                    "    {}[\"{}<br/>{}\"]",
+                    mermaid_id(&node.id),
+                    escape_mermaid(&node.name),
+                    escape_mermaid(&node.id.to_string())
+                );
+            }
+            out.push_str("  end\n");
+        }
+        let max_flow = self.max_flow();
+        let mut link_styles = Vec::new();
+        for (index, edge) in self.edges.iter().enumerate() {
+            let arrow = match edge.kind {
+                DependencyKind::Input => "-->",
+                DependencyKind::Cost | DependencyKind::Demand => "-.->",
+            };
+            let _ = writeln!(
+                out,
+                "  {} {}|\"{}\"| {}",
+                mermaid_id(&edge.from),
+                arrow,
+                edge_label(edge),
+                mermaid_id(&edge.to)
+            );
+            if edge.flow.is_some() {
+                link_styles.push(format!(
+                    "  linkStyle {} stroke-width:{:.1}px",
+                    index,
+                    pen_width(edge, max_flow)
+                ));
+            }
+        }
+        for node in self.nodes.iter().filter(|node| node.missing) {
+            let _ = writeln!(
+                out,
+                "  style {} stroke-dasharray: 4 4",
+                mermaid_id(&node.id)
+            );
+        }
+        for line in link_styles {
+            out.push_str(&line);
+            out.push('\n');
+        }
+        out
+    }
+
+    fn max_flow(&self) -> f64 {
+        self.edges
+            .iter()
+            .filter_map(|edge| edge.flow)
+            .fold(0.0, f64::max)
+    }
+}
+
+fn sort_key(id: &SectorId) -> (IndustryCategory, &str) {
+    (id.category, id.key.as_str())
+}
+
+fn edge_label(edge: &GraphEdge) -> String {
+    let kind = match edge.kind {
+        DependencyKind::Input => "input",
+        DependencyKind::Cost => "cost",
+        DependencyKind::Demand => "demand",
+    };
+    let mut label = format!("{} req={:.2}", kind, edge.requirement);
+    if edge.elasticity != 0.0 {
+        let _ = write!(label, " e={:.2}", edge.elasticity);
+    }
+    if let Some(flow) = edge.flow {
+        let _ = write!(label, " flow={:.1}", flow);
+    }
+    label
+}
+
+fn pen_width(edge: &GraphEdge, max_flow: f64) -> f64 {
+    match edge.flow {
+        Some(flow) if max_flow > f64::EPSILON => {
+            MIN_PEN_WIDTH + (MAX_PEN_WIDTH - MIN_PEN_WIDTH) * (flow / max_flow)
+        }
+        _ => MIN_PEN_WIDTH,
+    }
+}
+
+fn dot_id(id: &SectorId) -> String {
+    format!("\"{}\"", escape_dot(&id.to_string()))
+}
+
+/// Mermaid のノード ID は英数字と `_` に限る。それ以外の文字は `_{16 進}_` に置き換え、別のキーと衝突させない。
+fn mermaid_id(id: &SectorId) -> String {
+    let mut out = format!("{}_", id.category);
+    for c in id.key.chars() {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            out.push(c);
+        } else {
+            let _ = write!(out, "_{:x}_", c as u32);
+        }
+    }
+    out
+}
+
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn escape_mermaid(text: &str) -> String {
+    text.replace('#', "#35;")
+        .replace('"', "#quot;")
+        .replace('<', "#lt;")
+        .replace('>', "#gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embedded_graph_renders_both_formats() {
+        let catalog = IndustryCatalog::from_embedded().expect("catalog");
+        let graph = DependencyGraph::from_catalog(&catalog);
+        let ict = graph
+            .nodes()
+            .iter()
+            .find(|node| node.id.key == "ict_services")
+            .expect("未解放セクターもノードとして含む");
+        assert!(ict.missing);
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph industry {"));
+        assert!(dot.contains(
+            "\"energy:crude_oil\" -> \"energy:diesel\" [label=\"input req=0.60\", style=solid"
+        ));
+        let mermaid = graph.render(GraphFormat::Mermaid);
+        assert!(mermaid.starts_with("flowchart LR"));
+        assert!(mermaid.contains("energy_crude_oil -->|\"input req=0.60\"| energy_diesel"));
+        assert!(mermaid.contains("style tertiary_ict_services stroke-dasharray"));
+    }
+
+    #[test]
+    fn exports_escape_ids_and_names_with_special_characters() {
+        let odd = SectorId::new(IndustryCategory::Secondary, "odd key-\"x\"");
+        let plain = SectorId::new(IndustryCategory::Secondary, "odd_key");
+        let graph = DependencyGraph {
+            nodes: vec![
+                GraphNode {
+                    id: odd.clone(),
+                    name: "Say \"hi\" \\ <now>".to_string(),
+                    missing: false,
+                },
+                GraphNode {
+                    id: plain.clone(),
+                    name: "Plain".to_string(),
+                    missing: false,
+                },
+            ],
+            edges: vec![GraphEdge {
+                from: plain,
+                to: odd,
+                kind: DependencyKind::Input,
+                requirement: 0.5,
+                elasticity: 0.0,
+                flow: None,
+            }],
+        };
+
+        let dot = graph.to_dot();
+        assert!(dot.contains(
+            "    \"secondary:odd key-\\\"x\\\"\" [label=\"Say \\\"hi\\\" \\\\ <now>\\nsecondary:odd key-\\\"x\\\"\"];"
+        ));
+        assert!(dot.contains("  \"secondary:odd_key\" -> \"secondary:odd key-\\\"x\\\"\" [label="));
+
+        let mermaid = graph.to_mermaid();
+        let odd_id = "secondary_odd_20_key_2d__22_x_22_";
+        assert!(mermaid.contains(&format!(
+            "    {odd_id}[\"Say #quot;hi#quot; \\ #lt;now#gt;<br/>secondary:odd key-#quot;x#quot;\"]"
+        )));
+        assert!(mermaid.contains(&format!(
+            "  secondary_odd_key -->|\"input req=0.50\"| {odd_id}"
+        )));
+    }
+
+    #[test]
+    fn flows_scale_edge_width() {
+        let catalog = IndustryCatalog::from_embedded().expect("catalog");
+        let diesel = SectorId::new(IndustryCategory::Energy, "diesel");
+        let mut metrics = HashMap::new();
+        metrics.insert(
+            diesel.clone(),
+            SectorMetrics {
+                output: 100.0,
+                ..SectorMetrics::default()
+            },
+        );
+        let graph = DependencyGraph::from_catalog(&catalog).with_flows(&metrics);
+        let edge = graph
+            .edges()
+            .iter()
+            .find(|edge| edge.to == diesel)
+            .expect("diesel edge");
+        assert_eq!(edge.flow, Some(60.0));
+        assert!(
+            graph
+                .to_dot()
+                .contains("flow=60.0\", style=solid, penwidth=6.00")
+        );
+        assert!(graph.to_mermaid().contains("stroke-width:6.0px"));
+    }
+}
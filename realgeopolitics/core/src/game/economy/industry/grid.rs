@@ -0,0 +1,124 @@
+use super::model::{BlackoutRecord, PowerGridStatus};
+
+/// 需要に対する供給不足がこの割合を超えると計画停電を実施する。
+const SHORTFALL_THRESHOLD: f64 = 0.05;
+const MAX_OUTPUT_CUT: f64 = 0.6;
+const OUTPUT_CUT_PER_SHORTFALL: f64 = 1.5;
+const BASE_BLACKOUT_HOURS: f64 = 6.0;
+const BLACKOUT_HOURS_PER_SHORTFALL: f64 = 72.0;
+const MAX_BLACKOUT_HOURS: f64 = 72.0;
+const MAX_HISTORY: usize = 20;
+
+#[derive(Debug, Clone, Copy)]
+struct ActiveBlackout {
+    record: BlackoutRecord,
+    remaining_minutes: f64,
+}
+
+/// 電力の需給から予備率を追跡し、供給不足時の計画停電を管理する。
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PowerGrid {
+    clock_minutes: f64,
+    reserve_margin: f64,
+    active: Option<ActiveBlackout>,
+    history: Vec<BlackoutRecord>,
+}
+
+impl PowerGrid {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 時間を進め、停電が終了した場合はその記録を返す。
+    pub fn advance(&mut self, minutes: f64) -> Option<BlackoutRecord> {
+        let minutes = minutes.max(0.0);
+        self.clock_minutes += minutes;
+        let active = self.active.as_mut()?;
+        active.remaining_minutes -= minutes;
+        if active.remaining_minutes > 0.0 {
+            return None;
+        }
+        self.active.take().map(|active| active.record)
+    }
+
+    /// 電力セクターの供給能力・需要・未充足量を観測し、新たに停電を開始した場合はその記録を返す。
+    pub fn observe(&mut self, capacity: f64, demand: f64, unmet: f64) -> Option<BlackoutRecord> {
+        if demand <= f64::EPSILON {
+            self.reserve_margin = 1.0;
+            return None;
+        }
+        self.reserve_margin = ((capacity - demand) / demand).clamp(-1.0, 1.0);
+        let shortfall = (unmet.max(0.0) / demand).clamp(0.0, 1.0);
+        if self.active.is_some() || shortfall < SHORTFALL_THRESHOLD {
+            return None;
+        }
+        let hours = (BASE_BLACKOUT_HOURS + shortfall * BLACKOUT_HOURS_PER_SHORTFALL)
+            .min(MAX_BLACKOUT_HOURS);
+        let record = BlackoutRecord {
+            started_at_minutes: self.clock_minutes,
+            duration_minutes: hours * 60.0,
+            shortfall_ratio: shortfall,
+            output_cut: (shortfall * OUTPUT_CUT_PER_SHORTFALL).min(MAX_OUTPUT_CUT),
+        };
+        self.active = Some(ActiveBlackout {
+            record,
+            remaining_minutes: record.duration_minutes,
+        });
+        self.history.push(record);
+        if self.history.len() > MAX_HISTORY {
+            self.history.remove(0);
+        }
+        Some(record)
+    }
+
+    /// 停電中に電力依存セクターの供給能力から差し引く割合。
+    pub fn output_cut(&self) -> f64 {
+        self.active
+            .map(|active| active.record.output_cut)
+            .unwrap_or(0.0)
+    }
+
+    pub fn status(&self) -> PowerGridStatus {
+        PowerGridStatus {
+            reserve_margin: self.reserve_margin,
+            active: self.active.map(|active| active.record),
+            remaining_minutes: self
+                .active
+                .map(|active| active.remaining_minutes.max(0.0))
+                .unwrap_or(0.0),
+            history: self.history.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_shortfall_only_lowers_reserve_margin() {
+        let mut grid = PowerGrid::new();
+        assert!(grid.observe(100.0, 102.0, 2.0).is_none());
+        assert!(grid.status().reserve_margin < 0.0);
+        assert_eq!(grid.output_cut(), 0.0);
+    }
+
+    #[test]
+    fn blackout_runs_for_scheduled_duration() {
+        let mut grid = PowerGrid::new();
+        let record = grid.observe(80.0, 100.0, 20.0).expect("blackout");
+        assert!((record.output_cut - 0.3).abs() < 1e-9);
+        assert!(grid.observe(80.0, 100.0, 20.0).is_none());
+        assert!(grid.output_cut() > 0.0);
+
+        let mut elapsed = 0.0;
+        let mut ended = None;
+        while ended.is_none() {
+            ended = grid.advance(60.0);
+            elapsed += 60.0;
+        }
+        assert!(elapsed >= record.duration_minutes);
+        assert_eq!(grid.output_cut(), 0.0);
+        assert_eq!(grid.status().history.len(), 1);
+    }
+}
@@ -0,0 +1,571 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use super::model::{DependencyKind, IndustryCatalog, IndustryCategory, SectorDefinition, SectorId};
+
+/// ランタイムで `price_sensitivity` が切り詰められる範囲。
+const PRICE_SENSITIVITY_RANGE: (f64, f64) = (0.1, 2.5);
+const COST_ELASTICITY_LIMIT: f64 = 2.0;
+const DEMAND_ELASTICITY_LIMIT: f64 = 2.5;
+/// 基準生産量に対してこれを超える依存比率は入力ミスの可能性が高い。
+const SUSPICIOUS_REQUIREMENT: f64 = 2.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LintSeverity {
+    Warning,
+    Error,
+}
+
+impl LintSeverity {
+    pub fn label(self) -> &'static str {
+        match self {
+            LintSeverity::Warning => "警告",
+            LintSeverity::Error => "エラー",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CatalogIssue {
+    pub severity: LintSeverity,
+    pub sector: SectorId,
+    pub message: String,
+}
+
+impl fmt::Display for CatalogIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{}] {}: {}",
+            self.severity.label(),
+            self.sector,
+            self.message
+        )
+    }
+}
+
+/// 産業カタログの検査結果。エラーが先、同じ重要度ではセクター順に並ぶ。
+#[derive(Debug, Clone, Default)]
+pub struct CatalogLint {
+    issues: Vec<CatalogIssue>,
+}
+
+impl CatalogLint {
+    pub fn issues(&self) -> &[CatalogIssue] {
+        &self.issues
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &CatalogIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == LintSeverity::Error)
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    fn push(&mut self, severity: LintSeverity, sector: &SectorId, message: String) {
+        self.issues.push(CatalogIssue {
+            severity,
+            sector: sector.clone(),
+            message,
+        });
+    }
+}
+
+impl IndustryCatalog {
+    /// 依存先の参照・循環・生産量・感応度を検査する。
+    pub fn lint(&self) -> CatalogLint {
+        self.lint_with_pending(&[])
+    }
+
+    /// `pending` は技術開発などで後から追加されるセクターで、これらへの参照は警告に留める。
+    pub fn lint_with_pending(&self, pending: &[SectorId]) -> CatalogLint {
+        let mut lint = CatalogLint::default();
+        let mut sectors: Vec<(&SectorId, &SectorDefinition)> = self.sectors().collect();
+        sectors.sort_by(|a, b| sort_key(a.0).cmp(&sort_key(b.0)));
+
+        let mut input_demand: HashMap<SectorId, f64> = HashMap::new();
+        for (id, def) in &sectors {
+            check_parameters(&mut lint, id, def);
+            let mut seen = HashSet::new();
+            for dep in &def.dependencies {
+                let target = dep.resolve_sector(id.category);
+                if !seen.insert(target.clone()) {
+                    lint.push(
+                        LintSeverity::Warning,
+                        id,
+                        format!("依存先 {} が重複しています", target),
+                    );
+                }
+                if target == **id {
+                    lint.push(
+                        LintSeverity::Error,
+                        id,
+                        "自分自身に依存しています".to_string(),
+                    );
+                    continue;
+                }
+                if !dep.requirement.is_finite() || dep.requirement <= 0.0 {
+                    lint.push(
+                        LintSeverity::Error,
+                        id,
+                        format!(
+                            "依存先 {} の requirement は正の数値で指定してください ({})",
+                            target, dep.requirement
+                        ),
+                    );
+                } else if dep.requirement > SUSPICIOUS_REQUIREMENT {
+                    lint.push(
+                        LintSeverity::Warning,
+                        id,
+                        format!(
+                            "依存先 {} の requirement {:.2} は基準生産量の {:.0} 倍を超えています",
+                            target, dep.requirement, SUSPICIOUS_REQUIREMENT
+                        ),
+                    );
+                }
+                check_elasticity(&mut lint, id, &target, dep.dependency, dep.elasticity);
+
+                let Some(target_def) = self.get(&target) else {
+                    report_unknown_reference(&mut lint, self, id, &target, dep.dependency, pending);
+                    continue;
+                };
+                if dep.dependency == DependencyKind::Input && dep.requirement > 0.0 {
+                    let required = def.base_output * dep.requirement;
+                    *input_demand.entry(target.clone()).or_default() += required;
+                    if required > target_def.base_output {
+                        lint.push(
+                            LintSeverity::Error,
+                            id,
+                            format!(
+                                "入力 {} の必要量 {:.1} が供給側の基準生産量 {:.1} を上回り、基準生産量に到達できません",
+                                target, required, target_def.base_output
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+
+        for (id, def) in &sectors {
+            if let Some(total) = input_demand.get(*id)
+                && *total > def.base_output
+            {
+                lint.push(
+                    LintSeverity::Warning,
+                    id,
+                    format!(
+                        "入力として求められる量の合計 {:.1} が基準生産量 {:.1} を上回っています",
+                        total, def.base_output
+                    ),
+                );
+            }
+        }
+
+        check_cycles(&mut lint, self, &sectors);
+
+        lint.issues.sort_by(|a, b| {
+            b.severity
+                .cmp(&a.severity)
+                .then_with(|| sort_key(&a.sector).cmp(&sort_key(&b.sector)))
+        });
+        lint
+    }
+}
+
+fn sort_key(id: &SectorId) -> (IndustryCategory, &str) {
+    (id.category, id.key.as_str())
+}
+
+fn check_parameters(lint: &mut CatalogLint, id: &SectorId, def: &SectorDefinition) {
+    if !def.base_output.is_finite() || def.base_output <= 0.0 {
+        lint.push(
+            LintSeverity::Error,
+            id,
+            format!(
+                "base_output が {} のため生産量がゼロになります",
+                def.base_output
+            ),
+        );
+    }
+    if !def.base_cost.is_finite() || def.base_cost <= 0.0 {
+        lint.push(
+            LintSeverity::Error,
+            id,
+            format!("base_cost は正の数値で指定してください ({})", def.base_cost),
+        );
+    }
+    if !def.efficiency.is_finite() || def.efficiency <= 0.0 {
+        lint.push(
+            LintSeverity::Error,
+            id,
+            format!(
+                "efficiency は正の数値で指定してください ({})",
+                def.efficiency
+            ),
+        );
+    }
+    if !def.emission_factor.is_finite() || def.emission_factor < 0.0 {
+        lint.push(
+            LintSeverity::Error,
+            id,
+            format!(
+                "emission_factor は 0 以上で指定してください ({})",
+                def.emission_factor
+            ),
+        );
+    }
+    let (min, max) = PRICE_SENSITIVITY_RANGE;
+    if !(min..=max).contains(&def.price_sensitivity) {
+        lint.push(
+            LintSeverity::Warning,
+            id,
+            format!(
+                "price_sensitivity {} は {}〜{} に切り詰められます",
+                def.price_sensitivity, min, max
+            ),
+        );
+    }
+    if !(0.0..=1.0).contains(&def.household_share) {
+        lint.push(
+            LintSeverity::Warning,
+            id,
+            format!(
+                "household_share {} は 0〜1 の範囲外です",
+                def.household_share
+            ),
+        );
+    }
+    if def.employment < 0.0 {
+        lint.push(
+            LintSeverity::Warning,
+            id,
+            format!("employment が負の値です ({})", def.employment),
+        );
+    }
+}
+
+fn check_elasticity(
+    lint: &mut CatalogLint,
+    id: &SectorId,
+    target: &SectorId,
+    kind: DependencyKind,
+    elasticity: f64,
+) {
+    let limit = match kind {
+        DependencyKind::Input => {
+            if elasticity != 0.0 {
+                lint.push(
+                    LintSeverity::Warning,
+                    id,
+                    format!(
+                        "入力依存 {} の elasticity {} は使用されません",
+                        target, elasticity
+                    ),
+                );
+            }
+            return;
+        }
+        DependencyKind::Cost => COST_ELASTICITY_LIMIT,
+        DependencyKind::Demand => DEMAND_ELASTICITY_LIMIT,
+    };
+    if !elasticity.is_finite() || elasticity.abs() > limit {
+        lint.push(
+            LintSeverity::Warning,
+            id,
+            format!(
+                "依存先 {} の elasticity {} は ±{} に切り詰められます",
+                target, elasticity, limit
+            ),
+        );
+    }
+}
+
+fn report_unknown_reference(
+    lint: &mut CatalogLint,
+    catalog: &IndustryCatalog,
+    id: &SectorId,
+    target: &SectorId,
+    kind: DependencyKind,
+    pending: &[SectorId],
+) {
+    let fallback = match kind {
+        DependencyKind::Input => "存在しない間は生産量がゼロになります",
+        DependencyKind::Cost | DependencyKind::Demand => "存在しない間は影響なしとして扱われます",
+    };
+    if pending.contains(target) {
+        lint.push(
+            LintSeverity::Warning,
+            id,
+            format!(
+                "依存先 {} は後から解放されるセクターです ({})",
+                target, fallback
+            ),
+        );
+        return;
+    }
+    let elsewhere: Vec<String> = catalog
+        .sectors()
+        .filter(|(other, _)| other.key == target.key)
+        .map(|(other, _)| other.category.to_string())
+        .collect();
+    let message = if elsewhere.is_empty() {
+        format!("未知のセクター {} を参照しています ({})", target, fallback)
+    } else {
+        format!(
+            "依存先 {} のカテゴリが一致しません ({} に存在します)",
+            target,
+            elsewhere.join(", ")
+        )
+    };
+    lint.push(LintSeverity::Error, id, message);
+}
+
+/// 入力依存だけで閉じた循環は生産が立ち上がらないためエラー、それ以外は 1 ティック遅れで評価されるため警告とする。
+fn check_cycles(
+    lint: &mut CatalogLint,
+    catalog: &IndustryCatalog,
+    sectors: &[(&SectorId, &SectorDefinition)],
+) {
+    let input_cycles = strongly_connected(catalog, sectors, true);
+    for cycle in &input_cycles {
+        lint.push(
+            LintSeverity::Error,
+            &cycle[0],
+            format!("入力依存が循環しています: {}", join_ids(cycle)),
+        );
+    }
+    for cycle in strongly_connected(catalog, sectors, false) {
+        if input_cycles.contains(&cycle) {
+            continue;
+        }
+        lint.push(
+            LintSeverity::Warning,
+            &cycle[0],
+            format!(
+                "依存関係が循環しています (前ティックの値で評価されます): {}",
+                join_ids(&cycle)
+            ),
+        );
+    }
+}
+
+fn join_ids(ids: &[SectorId]) -> String {
+    ids.iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(" / ")
+}
+
+/// 2 セクター以上からなる強連結成分を、成分内をソートした状態で返す (Tarjan 法)。
+fn strongly_connected(
+    catalog: &IndustryCatalog,
+    sectors: &[(&SectorId, &SectorDefinition)],
+    input_only: bool,
+) -> Vec<Vec<SectorId>> {
+    let index_of: HashMap<&SectorId, usize> = sectors
+        .iter()
+        .enumerate()
+        .map(|(idx, (id, _))| (*id, idx))
+        .collect();
+    let edges: Vec<Vec<usize>> = sectors
+        .iter()
+        .map(|(id, def)| {
+            def.dependencies
+                .iter()
+                .filter(|dep| !input_only || dep.dependency == DependencyKind::Input)
+                .map(|dep| dep.resolve_sector(id.category))
+                .filter(|target| target != *id && catalog.get(target).is_some())
+                .filter_map(|target| index_of.get(&target).copied())
+                .collect()
+        })
+        .collect();
+
+    struct Tarjan<'a> {
+        edges: &'a [Vec<usize>],
+        counter: usize,
+        index: Vec<Option<usize>>,
+        low: Vec<usize>,
+        stack: Vec<usize>,
+        on_stack: Vec<bool>,
+        components: Vec<Vec<usize>>,
+    }
+
+    impl Tarjan<'_> {
+        fn visit(&mut self, node: usize) {
+            self.index[node] = Some(self.counter);
+            self.low[node] = self.counter;
+            self.counter += 1;
+            self.stack.push(node);
+            self.on_stack[node] = true;
+            for next in self.edges[node].clone() {
+                match self.index[next] {
+                    None => {
+                        self.visit(next);
+                        self.low[node] = self.low[node].min(self.low[next]);
+                    }
+                    Some(index) if self.on_stack[next] => {
+                        self.low[node] = self.low[node].min(index);
+                    }
+                    Some(_) => {}
+                }
+            }
+            if Some(self.low[node]) == self.index[node] {
+                let mut component = Vec::new();
+                while let Some(member) = self.stack.pop() {
+                    self.on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                self.components.push(component);
+            }
+        }
+    }
+
+    let mut tarjan = Tarjan {
+        edges: &edges,
+        counter: 0,
+        index: vec![None; sectors.len()],
+        low: vec![0; sectors.len()],
+        stack: Vec::new(),
+        on_stack: vec![false; sectors.len()],
+        components: Vec::new(),
+    };
+    for node in 0..sectors.len() {
+        if tarjan.index[node].is_none() {
+            tarjan.visit(node);
+        }
+    }
+
+    let mut cycles: Vec<Vec<SectorId>> = tarjan
+        .components
+        .into_iter()
+        .filter(|component| component.len() > 1)
+        .map(|mut component| {
+            component.sort_unstable();
+            component
+                .into_iter()
+                .map(|idx| sectors[idx].0.clone())
+                .collect()
+        })
+        .collect();
+    cycles.sort_by(|a, b| sort_key(&a[0]).cmp(&sort_key(&b[0])));
+    cycles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::economy::industry::model::SectorDependency;
+
+    fn sector(
+        key: &str,
+        base_output: f64,
+        dependencies: Vec<SectorDependency>,
+    ) -> SectorDefinition {
+        SectorDefinition {
+            key: key.into(),
+            name: key.into(),
+            description: None,
+            base_output,
+            base_cost: 40.0,
+            price_sensitivity: 0.5,
+            employment: 100.0,
+            household_share: 0.0,
+            emission_factor: 0.0,
+            efficiency: 1.0,
+            dependencies,
+        }
+    }
+
+    fn dependency(
+        sector: &str,
+        category: IndustryCategory,
+        requirement: f64,
+        dependency: DependencyKind,
+    ) -> SectorDependency {
+        SectorDependency {
+            sector: sector.into(),
+            category: Some(category),
+            requirement,
+            elasticity: 0.0,
+            dependency,
+        }
+    }
+
+    #[test]
+    fn embedded_catalog_has_no_errors() {
+        let catalog = IndustryCatalog::from_embedded().expect("catalog");
+        let pending = [SectorId::new(IndustryCategory::Tertiary, "ict_services")];
+        let lint = catalog.lint_with_pending(&pending);
+        assert!(
+            !lint.has_errors(),
+            "{:?}",
+            lint.errors().map(ToString::to_string).collect::<Vec<_>>()
+        );
+        assert!(catalog.lint().has_errors());
+    }
+
+    #[test]
+    fn lint_reports_broken_references_and_cycles() {
+        use DependencyKind::{Cost, Input};
+        use IndustryCategory::{Energy, Primary, Secondary};
+
+        let mut catalog = IndustryCatalog::default();
+        let definitions = [
+            (
+                Primary,
+                sector(
+                    "ore",
+                    100.0,
+                    vec![
+                        dependency("ore", Primary, 0.1, Cost),
+                        dependency("smelting", Secondary, 0.2, Input),
+                    ],
+                ),
+            ),
+            (
+                Secondary,
+                sector(
+                    "smelting",
+                    80.0,
+                    vec![
+                        dependency("ore", Primary, 2.0, Input),
+                        dependency("ore", Secondary, 0.1, Cost),
+                        dependency("fusion", Energy, 0.3, Cost),
+                    ],
+                ),
+            ),
+            (Energy, sector("idle", 0.0, Vec::new())),
+        ];
+        for (category, definition) in definitions {
+            catalog
+                .insert_definition(category, definition)
+                .expect("insert");
+        }
+
+        let lint = catalog.lint();
+        let errors: Vec<String> = lint.errors().map(ToString::to_string).collect();
+        let expect = |needle: &str| {
+            assert!(
+                errors.iter().any(|line| line.contains(needle)),
+                "{needle} が見つかりません: {errors:?}"
+            );
+        };
+        expect("自分自身に依存しています");
+        expect("カテゴリが一致しません");
+        expect("未知のセクター energy:fusion");
+        expect("基準生産量に到達できません");
+        expect("入力依存が循環しています: primary:ore / secondary:smelting");
+        expect("生産量がゼロになります");
+        assert_eq!(lint.issues()[0].severity, LintSeverity::Error);
+    }
+}
@@ -99,12 +99,14 @@ mod tests {
     fn sample_metrics(output: f64, revenue: f64, cost: f64) -> SectorMetrics {
         SectorMetrics {
             output,
+            price: 1.0,
             revenue,
             cost,
             sales: output,
             demand: output,
             inventory: 0.0,
             unmet_demand: 0.0,
+            emissions: 0.0,
         }
     }
 
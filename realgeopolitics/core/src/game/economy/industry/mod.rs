@@ -1,7 +1,11 @@
 #![allow(dead_code)]
 
+mod capital;
 pub mod catalog;
 mod effects;
+mod graph;
+mod grid;
+mod lint;
 mod metrics;
 pub mod model;
 mod registry;
@@ -12,6 +16,8 @@ pub mod runtime;
 pub use catalog::*;
 #[allow(unused_imports)]
 pub(crate) use effects::*;
+pub use graph::{DependencyGraph, GraphEdge, GraphFormat, GraphNode};
+pub use lint::{CatalogIssue, CatalogLint, LintSeverity};
 #[allow(unused_imports)]
 pub(crate) use metrics::{MetricsTotals, SectorMetricsStore};
 #[allow(unused_imports)]
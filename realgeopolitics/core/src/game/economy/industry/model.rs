@@ -8,6 +8,8 @@ use std::str::FromStr;
 use anyhow::{Result, anyhow, bail};
 use serde::{Deserialize, Serialize};
 
+use crate::game::economy::HouseholdPrices;
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[serde(rename_all = "snake_case")]
 pub enum IndustryCategory {
@@ -74,6 +76,12 @@ impl SectorId {
     }
 }
 
+impl fmt::Display for SectorId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.category, self.key)
+    }
+}
+
 impl Hash for SectorId {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.category.hash(state);
@@ -136,6 +144,15 @@ pub struct SectorDefinition {
     pub price_sensitivity: f64,
     #[serde(default = "SectorDefinition::default_employment")]
     pub employment: f64,
+    /// 家計消費に占める比率。0 より大きいセクターのみ CPI バスケットに含まれる。
+    #[serde(default)]
+    pub household_share: f64,
+    /// 生産 1 単位あたりの温室効果ガス排出量。
+    #[serde(default)]
+    pub emission_factor: f64,
+    /// 技術開発で恒久的に上昇する生産効率の倍率。
+    #[serde(default = "SectorDefinition::default_efficiency")]
+    pub efficiency: f64,
     #[serde(default)]
     pub dependencies: Vec<SectorDependency>,
 }
@@ -157,6 +174,10 @@ impl SectorDefinition {
         100.0
     }
 
+    const fn default_efficiency() -> f64 {
+        1.0
+    }
+
     pub fn id(&self, category: IndustryCategory) -> SectorId {
         SectorId::new(category, &self.key)
     }
@@ -172,6 +193,9 @@ pub struct SectorState {
     pub unmet_demand: f64,
     pub subsidy_rate: f64,
     pub efficiency: f64,
+    /// 生産能力の上限となる資本ストック (生産量と同じ単位)。
+    pub capital_stock: f64,
+    pub last_investment: f64,
 }
 
 impl SectorState {
@@ -186,6 +210,8 @@ impl SectorState {
             unmet_demand: 0.0,
             subsidy_rate: 0.0,
             efficiency: 1.0,
+            capital_stock: super::capital::initial_capital(base),
+            last_investment: 0.0,
         }
     }
 }
@@ -247,6 +273,8 @@ impl IndustryCatalog {
 pub struct SectorModifier {
     pub subsidy_bonus: f64,
     pub efficiency_bonus: f64,
+    /// 販売価格への上乗せ率 (0.2 で +20%、負の値で値下がり)。
+    pub price_shift: f64,
     pub remaining_minutes: f64,
 }
 
@@ -255,6 +283,7 @@ impl SectorModifier {
         if self.remaining_minutes <= 0.0 {
             self.subsidy_bonus = 0.0;
             self.efficiency_bonus = 0.0;
+            self.price_shift = 0.0;
             return;
         }
         let decay = minutes.max(0.0);
@@ -265,19 +294,35 @@ impl SectorModifier {
         if self.remaining_minutes == 0.0 {
             self.subsidy_bonus = 0.0;
             self.efficiency_bonus = 0.0;
+            self.price_shift = 0.0;
         }
     }
 }
 
+/// イベントなどの外部要因がセクターへ与える効果。
+#[derive(Debug, Clone, Copy)]
+pub enum SectorShock {
+    /// 期間付きの補助・効率・価格の修正。既存の修正とは独立に減衰する。
+    Modifier(SectorModifier),
+    /// 供給能力と資本ストックを倍率で変化させる。
+    ScaleCapacity(f64),
+    /// 潜在需要を倍率で変化させる。
+    ScaleDemand(f64),
+    /// 在庫の指定割合を失わせる。
+    DestroyInventory(f64),
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct SectorMetrics {
     pub output: f64,
+    pub price: f64,
     pub revenue: f64,
     pub cost: f64,
     pub sales: f64,
     pub demand: f64,
     pub inventory: f64,
     pub unmet_demand: f64,
+    pub emissions: f64,
 }
 
 #[derive(Debug, Clone)]
@@ -289,6 +334,26 @@ pub struct SectorOverview {
     pub last_output: f64,
     pub last_revenue: f64,
     pub last_cost: f64,
+    pub capital_stock: f64,
+    pub last_investment: f64,
+}
+
+/// 計画停電 1 回分の記録。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlackoutRecord {
+    pub started_at_minutes: f64,
+    pub duration_minutes: f64,
+    pub shortfall_ratio: f64,
+    pub output_cut: f64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PowerGridStatus {
+    /// (供給能力 - 需要) / 需要。負の値は供給不足を表す。
+    pub reserve_margin: f64,
+    pub active: Option<BlackoutRecord>,
+    pub remaining_minutes: f64,
+    pub history: Vec<BlackoutRecord>,
 }
 
 #[derive(Debug, Default)]
@@ -296,6 +361,9 @@ pub struct IndustryTickOutcome {
     pub total_revenue: f64,
     pub total_cost: f64,
     pub total_gdp: f64,
+    pub total_emissions: f64,
     pub sector_metrics: HashMap<SectorId, SectorMetrics>,
+    pub household_prices: HouseholdPrices,
+    pub blackout: Option<BlackoutRecord>,
     pub reports: Vec<String>,
 }
@@ -79,6 +79,9 @@ mod tests {
                     base_cost: 50.0,
                     price_sensitivity: 0.5,
                     employment: 80.0,
+                    household_share: 0.0,
+                    emission_factor: 0.0,
+                    efficiency: 1.0,
                     dependencies: Vec::new(),
                 },
             )
@@ -94,6 +97,9 @@ mod tests {
                     base_cost: 120.0,
                     price_sensitivity: 0.4,
                     employment: 110.0,
+                    household_share: 0.0,
+                    emission_factor: 0.0,
+                    efficiency: 1.0,
                     dependencies: Vec::new(),
                 },
             )
@@ -109,6 +115,9 @@ mod tests {
                     base_cost: 70.0,
                     price_sensitivity: 0.3,
                     employment: 60.0,
+                    household_share: 0.0,
+                    emission_factor: 0.0,
+                    efficiency: 1.0,
                     dependencies: Vec::new(),
                 },
             )
@@ -139,6 +148,9 @@ mod tests {
                     base_cost: 80.0,
                     price_sensitivity: 0.3,
                     employment: 90.0,
+                    household_share: 0.0,
+                    emission_factor: 0.0,
+                    efficiency: 1.0,
                     dependencies: Vec::new(),
                 },
             )
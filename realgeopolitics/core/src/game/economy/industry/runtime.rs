@@ -1,12 +1,15 @@
 use std::collections::HashMap;
 
-use anyhow::{Result, anyhow};
+use anyhow::{Result, anyhow, ensure};
 
+use super::graph::DependencyGraph;
+use super::grid::PowerGrid;
 use super::model::{
-    IndustryCatalog, IndustryCategory, IndustryTickOutcome, SectorId, SectorMetrics,
-    SectorModifier, SectorOverview, SectorState,
+    IndustryCatalog, IndustryCategory, IndustryTickOutcome, PowerGridStatus, SectorDefinition,
+    SectorId, SectorMetrics, SectorModifier, SectorOverview, SectorShock, SectorState,
 };
-use super::{Reporter, SectorMetricsStore, SectorRegistry, effects};
+use super::{Reporter, SectorMetricsStore, SectorRegistry, capital, effects};
+use crate::game::economy::HouseholdPrices;
 
 #[derive(Debug, Clone)]
 pub struct IndustryRuntime {
@@ -14,11 +17,24 @@ pub struct IndustryRuntime {
     registry: SectorRegistry,
     states: HashMap<SectorId, SectorState>,
     modifiers: HashMap<SectorId, SectorModifier>,
+    /// イベント由来の期間付き修正。政策による `modifiers` とは別に減衰させる。
+    event_modifiers: Vec<(SectorId, SectorModifier)>,
     metrics_store: SectorMetricsStore,
     energy_baseline_output: f64,
     energy_cost_index: f64,
+    pending_public_investment: f64,
+    carbon_price: f64,
+    grid: PowerGrid,
 }
 
+const ELECTRICITY_KEY: &str = "electricity";
+/// 電力に直接依存しない二次・三次産業も、停電時には削減率の半分の影響を受ける。
+const INDIRECT_BLACKOUT_SHARE: f64 = 0.5;
+
+/// 炭素価格に応じて排出削減が進む割合の上限と、その半分に達する価格。
+const MAX_ABATEMENT: f64 = 0.6;
+const ABATEMENT_HALF_PRICE: f64 = 40.0;
+
 impl IndustryRuntime {
     pub fn from_catalog(catalog: IndustryCatalog) -> Self {
         let mut states = HashMap::new();
@@ -35,12 +51,36 @@ impl IndustryRuntime {
             registry,
             states,
             modifiers: HashMap::new(),
+            event_modifiers: Vec::new(),
             metrics_store: SectorMetricsStore::new(),
             energy_baseline_output: energy_baseline.max(1.0),
             energy_cost_index: 1.0,
+            pending_public_investment: 0.0,
+            carbon_price: 0.0,
+            grid: PowerGrid::new(),
         }
     }
 
+    /// 公共インフラ投資を積み立て、次の tick で基準生産量に比例して各セクターの資本に配分する。
+    pub fn invest_public(&mut self, amount: f64) {
+        if amount.is_finite() && amount > 0.0 {
+            self.pending_public_investment += amount;
+        }
+    }
+
+    /// 排出 1 単位あたりの炭素価格を設定する。次の tick から生産コストと排出係数に反映される。
+    pub fn set_carbon_price(&mut self, price: f64) {
+        self.carbon_price = if price.is_finite() {
+            price.max(0.0)
+        } else {
+            0.0
+        };
+    }
+
+    pub fn carbon_price(&self) -> f64 {
+        self.carbon_price
+    }
+
     pub fn simulate_tick(&mut self, minutes: f64, scale: f64) -> IndustryTickOutcome {
         if scale <= 0.0 {
             return IndustryTickOutcome::default();
@@ -56,6 +96,21 @@ impl IndustryRuntime {
             IndustryCategory::Tertiary,
         ];
         let mut energy_output_total = 0.0;
+        let mut electricity_balance = None;
+        let mut blackout = None;
+        if let Some(ended) = self.grid.advance(minutes) {
+            reporter.push(format!(
+                "計画停電が解除されました (継続 {:.0} 時間)。",
+                ended.duration_minutes / 60.0
+            ));
+        }
+        let electricity_id = SectorId::new(IndustryCategory::Energy, ELECTRICITY_KEY);
+        let public_investment = std::mem::take(&mut self.pending_public_investment);
+        let total_base_output: f64 = self
+            .catalog
+            .sectors()
+            .map(|(_, def)| def.base_output.max(0.0))
+            .sum();
         for category in ORDER {
             let sector_ids: Vec<SectorId> = self
                 .catalog
@@ -73,6 +128,16 @@ impl IndustryRuntime {
                     self.metrics_store.metrics(),
                     &self.states,
                 );
+                let event = self
+                    .event_modifiers
+                    .iter()
+                    .filter(|(id, _)| *id == sector_id)
+                    .fold(SectorModifier::default(), |acc, (_, m)| SectorModifier {
+                        subsidy_bonus: acc.subsidy_bonus + m.subsidy_bonus,
+                        efficiency_bonus: acc.efficiency_bonus + m.efficiency_bonus,
+                        price_shift: acc.price_shift + m.price_shift,
+                        remaining_minutes: 0.0,
+                    });
                 let state_entry = self
                     .states
                     .entry(sector_id.clone())
@@ -81,8 +146,9 @@ impl IndustryRuntime {
                     .modifiers
                     .entry(sector_id.clone())
                     .or_insert_with(SectorModifier::default);
+                let efficiency_bonus = modifier.efficiency_bonus + event.efficiency_bonus;
 
-                let subsidy = modifier.subsidy_bonus.clamp(0.0, 0.9);
+                let subsidy = (modifier.subsidy_bonus + event.subsidy_bonus).clamp(0.0, 0.9);
                 state_entry.subsidy_rate = subsidy;
 
                 let base_demand = (def.base_output * scale).max(0.0);
@@ -92,9 +158,13 @@ impl IndustryRuntime {
                     + (adjusted_demand * adjustment_rate);
                 let demand_with_backlog = smoothed_demand + state_entry.unmet_demand;
 
-                let base_capacity = state_entry.supply_capacity.max(def.base_output * 0.1);
+                let base_capacity = state_entry
+                    .supply_capacity
+                    .max(def.base_output * 0.1)
+                    .min(state_entry.capital_stock);
                 let efficiency_factor =
-                    (state_entry.efficiency * (1.0 + modifier.efficiency_bonus)).clamp(0.1, 3.0);
+                    (state_entry.efficiency * def.efficiency * (1.0 + efficiency_bonus))
+                        .clamp(0.1, 3.0);
                 let subsidy_boost = 1.0 + subsidy * 0.6;
                 let input_limit = impact.input_availability.clamp(0.0, 1.5);
                 let mut cost_factor = impact.cost_multiplier;
@@ -102,9 +172,28 @@ impl IndustryRuntime {
                     cost_factor *= self.energy_cost_index;
                 }
 
-                let capacity_limit =
-                    (base_capacity * efficiency_factor * subsidy_boost * input_limit).max(0.0)
-                        * scale;
+                let blackout_factor = match category {
+                    IndustryCategory::Secondary | IndustryCategory::Tertiary => {
+                        let depends_on_grid = def
+                            .dependencies
+                            .iter()
+                            .any(|dep| dep.resolve_sector(category) == electricity_id);
+                        let share = if depends_on_grid {
+                            1.0
+                        } else {
+                            INDIRECT_BLACKOUT_SHARE
+                        };
+                        1.0 - self.grid.output_cut() * share
+                    }
+                    _ => 1.0,
+                };
+                let capacity_limit = (base_capacity
+                    * efficiency_factor
+                    * subsidy_boost
+                    * input_limit
+                    * blackout_factor)
+                    .max(0.0)
+                    * scale;
                 let target_output = (state_entry.last_output * (1.0 - adjustment_rate))
                     + (smoothed_demand * adjustment_rate);
                 let inertia_floor = if state_entry.last_output > 0.0 {
@@ -127,10 +216,18 @@ impl IndustryRuntime {
                     ((demand_with_backlog - sales) / demand_with_backlog).clamp(-1.5, 1.5)
                 };
                 let price_multiplier = effects::price_from_gap(gap_ratio, def.price_sensitivity);
-                let price = (def.base_cost * price_multiplier).max(0.05);
-                let unit_cost =
-                    (def.base_cost * cost_factor * (1.0 - subsidy).max(0.1)).clamp(0.05, 5_000.0);
+                let emission_factor =
+                    effective_emission_factor(def.emission_factor, self.carbon_price);
+                // 炭素コストは販売価格にも転嫁され、家計の物価上昇として現れる。
+                let carbon_cost = emission_factor * self.carbon_price;
+                let price_shift = (1.0 + modifier.price_shift + event.price_shift).max(0.1);
+                let price =
+                    ((def.base_cost + carbon_cost) * price_multiplier * price_shift).max(0.05);
+                let unit_cost = (def.base_cost * cost_factor * (1.0 - subsidy).max(0.1)
+                    + carbon_cost)
+                    .clamp(0.05, 5_000.0);
                 let cost = production * unit_cost;
+                let emissions = production * emission_factor;
                 let revenue = sales * price;
 
                 state_entry.inventory = new_inventory;
@@ -142,15 +239,28 @@ impl IndustryRuntime {
                 } else {
                     base_capacity
                 };
-                state_entry.supply_capacity =
-                    (state_entry.supply_capacity * 0.9) + (base_capacity_update * 0.1);
+                state_entry.supply_capacity = ((state_entry.supply_capacity * 0.9)
+                    + (base_capacity_update * 0.1))
+                    .min(state_entry.capital_stock);
+
+                let public_share = if total_base_output > f64::EPSILON {
+                    public_investment * def.base_output.max(0.0) / total_base_output
+                } else {
+                    0.0
+                };
+                let investment = capital::private_investment(revenue, cost, def.base_cost)
+                    + capital::capital_units(public_share, def.base_cost);
+                state_entry.last_investment = investment;
+                state_entry.capital_stock = (state_entry.capital_stock
+                    - capital::depreciation(state_entry.capital_stock, minutes)
+                    + investment)
+                    .max(capital::capital_floor(def.base_output));
                 let utilisation = if capacity_limit > f64::EPSILON {
                     (production / capacity_limit).clamp(0.0, 1.2)
                 } else {
                     0.0
                 };
-                let target_efficiency =
-                    (1.0 + modifier.efficiency_bonus * 0.5) * (0.9 + utilisation * 0.2);
+                let target_efficiency = (1.0 + efficiency_bonus * 0.5) * (0.9 + utilisation * 0.2);
                 state_entry.efficiency =
                     (state_entry.efficiency * 0.85 + target_efficiency * 0.15).clamp(0.2, 3.0);
                 modifier.decay(minutes);
@@ -158,15 +268,20 @@ impl IndustryRuntime {
                 if category == IndustryCategory::Energy {
                     energy_output_total += production;
                 }
+                if sector_id == electricity_id {
+                    electricity_balance = Some((capacity_limit, demand_with_backlog, new_unmet));
+                }
 
                 let metrics = SectorMetrics {
                     output: production,
+                    price,
                     revenue,
                     cost,
                     sales,
                     demand: demand_with_backlog,
                     inventory: new_inventory,
                     unmet_demand: new_unmet,
+                    emissions,
                 };
                 self.metrics_store.record(sector_id.clone(), metrics);
                 reporter.record_sector_activity(
@@ -184,19 +299,75 @@ impl IndustryRuntime {
                     self.energy_baseline_output,
                     energy_output_total,
                 );
+                if let Some((capacity, demand, unmet)) = electricity_balance
+                    && let Some(record) = self.grid.observe(capacity, demand, unmet)
+                {
+                    reporter.push(format!(
+                        "電力供給が需要を {:.1}% 下回り、計画停電を {:.0} 時間実施します (産業の生産能力 最大 {:.0}% 低下)。",
+                        record.shortfall_ratio * 100.0,
+                        record.duration_minutes / 60.0,
+                        record.output_cut * 100.0
+                    ));
+                    blackout = Some(record);
+                }
             }
         }
 
+        for (_, modifier) in &mut self.event_modifiers {
+            modifier.decay(minutes);
+        }
+        self.event_modifiers
+            .retain(|(_, modifier)| modifier.remaining_minutes > 0.0);
+
         let totals = self.metrics_store.totals();
+        let total_emissions = self
+            .metrics_store
+            .metrics()
+            .values()
+            .map(|m| m.emissions)
+            .sum();
         IndustryTickOutcome {
             total_revenue: totals.revenue(),
             total_cost: totals.cost(),
             total_gdp: totals.gdp(),
+            total_emissions,
             sector_metrics: self.metrics_store.snapshot(),
+            household_prices: self.household_prices(),
+            blackout,
             reports: reporter.into_reports(),
         }
     }
 
+    /// 家計消費比率で加重した相対価格 (価格 / 基準コスト) を、全体とエネルギーに分けて集計する。
+    pub fn household_prices(&self) -> HouseholdPrices {
+        let mut overall = (0.0, 0.0);
+        let mut energy = (0.0, 0.0);
+        for (id, def) in self.catalog.sectors() {
+            let weight = def.household_share.max(0.0);
+            if weight <= 0.0 || def.base_cost <= f64::EPSILON {
+                continue;
+            }
+            let relative = self
+                .metrics_store
+                .get(id)
+                .map(|m| m.price / def.base_cost)
+                .unwrap_or(1.0);
+            overall.0 += relative * weight;
+            overall.1 += weight;
+            if id.category == IndustryCategory::Energy {
+                energy.0 += relative * weight;
+                energy.1 += weight;
+            }
+        }
+        let weighted = |(sum, weight): (f64, f64)| {
+            if weight > 0.0 { sum / weight } else { 1.0 }
+        };
+        HouseholdPrices {
+            overall: weighted(overall),
+            energy: weighted(energy),
+        }
+    }
+
     pub fn registry(&self) -> &SectorRegistry {
         &self.registry
     }
@@ -216,6 +387,87 @@ impl IndustryRuntime {
         self.overview_for(id)
     }
 
+    /// イベントによるショックをセクターへ与える。期間付きの修正は次の tick から反映される。
+    pub fn apply_shock(&mut self, id: &SectorId, shock: SectorShock) -> Result<()> {
+        let state = self
+            .states
+            .get_mut(id)
+            .ok_or_else(|| anyhow!("セクターが存在しません: {}", id))?;
+        match shock {
+            SectorShock::Modifier(modifier) => {
+                ensure!(
+                    modifier.remaining_minutes.is_finite() && modifier.remaining_minutes > 0.0,
+                    "効果の期間は正の分数で指定してください"
+                );
+                self.event_modifiers.push((id.clone(), modifier));
+            }
+            SectorShock::ScaleCapacity(factor) => {
+                ensure!(
+                    factor.is_finite() && factor > 0.0,
+                    "供給能力の倍率は正の数値で指定してください"
+                );
+                state.capital_stock *= factor;
+                state.supply_capacity = (state.supply_capacity * factor).min(state.capital_stock);
+            }
+            SectorShock::ScaleDemand(factor) => {
+                ensure!(
+                    factor.is_finite() && factor > 0.0,
+                    "需要の倍率は正の数値で指定してください"
+                );
+                state.potential_demand *= factor;
+            }
+            SectorShock::DestroyInventory(share) => {
+                ensure!(
+                    (0.0..=1.0).contains(&share),
+                    "在庫の損失割合は 0〜1 で指定してください"
+                );
+                state.inventory *= 1.0 - share;
+            }
+        }
+        Ok(())
+    }
+
+    /// 技術開発によるセクター定義の恒久的な変更 (効率・基準コスト・排出係数の倍率) を適用する。
+    pub fn improve_sector(
+        &mut self,
+        id: &SectorId,
+        efficiency: f64,
+        cost: f64,
+        emissions: f64,
+    ) -> Result<()> {
+        for (label, value) in [("効率", efficiency), ("コスト", cost), ("排出", emissions)] {
+            if !value.is_finite() || value <= 0.0 {
+                return Err(anyhow!("{}の倍率は正の数値で指定してください", label));
+            }
+        }
+        let def = self
+            .catalog
+            .get_mut(id)
+            .ok_or_else(|| anyhow!("セクターが存在しません: {}: {}", id.category, id.key))?;
+        def.efficiency *= efficiency;
+        def.base_cost *= cost;
+        def.emission_factor *= emissions;
+        Ok(())
+    }
+
+    /// 新しいセクターをカタログへ追加し、次の tick から生産に参加させる。
+    pub fn unlock_sector(
+        &mut self,
+        category: IndustryCategory,
+        definition: SectorDefinition,
+    ) -> Result<SectorId> {
+        let id = definition.id(category);
+        let state = SectorState::from_definition(&definition, category);
+        let base_output = definition.base_output.max(0.0);
+        self.catalog.insert_definition(category, definition)?;
+        if category == IndustryCategory::Energy {
+            self.energy_baseline_output += base_output;
+        }
+        self.states.insert(id.clone(), state);
+        self.registry = SectorRegistry::from_catalog(&self.catalog);
+        Ok(id)
+    }
+
     pub fn overview(&self) -> Vec<SectorOverview> {
         let mut entries = Vec::new();
         for (id, def) in self.catalog.sectors() {
@@ -229,6 +481,8 @@ impl IndustryRuntime {
                 last_output: metrics.map(|m| m.output).unwrap_or(0.0),
                 last_revenue: metrics.map(|m| m.revenue).unwrap_or(0.0),
                 last_cost: metrics.map(|m| m.cost).unwrap_or(0.0),
+                capital_stock: state.map(|s| s.capital_stock).unwrap_or(0.0),
+                last_investment: state.map(|s| s.last_investment).unwrap_or(0.0),
             });
         }
         entries.sort_by(|a, b| {
@@ -254,6 +508,8 @@ impl IndustryRuntime {
             last_output: metrics.map(|m| m.output).unwrap_or(0.0),
             last_revenue: metrics.map(|m| m.revenue).unwrap_or(0.0),
             last_cost: metrics.map(|m| m.cost).unwrap_or(0.0),
+            capital_stock: state.map(|s| s.capital_stock).unwrap_or(0.0),
+            last_investment: state.map(|s| s.last_investment).unwrap_or(0.0),
         })
     }
 
@@ -261,10 +517,43 @@ impl IndustryRuntime {
         self.metrics_store.metrics()
     }
 
+    /// 依存関係グラフ。`weighted` の場合は直近 tick の流量で辺を重み付けする。
+    pub fn dependency_graph(&self, weighted: bool) -> DependencyGraph {
+        let graph = DependencyGraph::from_catalog(&self.catalog);
+        if weighted {
+            graph.with_flows(self.metrics_store.metrics())
+        } else {
+            graph
+        }
+    }
+
+    pub fn grid_status(&self) -> PowerGridStatus {
+        self.grid.status()
+    }
+
     pub fn energy_cost_index(&self) -> f64 {
         self.energy_cost_index
     }
 
+    #[cfg(test)]
+    pub fn state_for_test(&self, id: &SectorId) -> Option<&SectorState> {
+        self.states.get(id)
+    }
+
+    #[cfg(test)]
+    pub fn state_mut_for_test(&mut self, id: &SectorId) -> Option<&mut SectorState> {
+        self.states.get_mut(id)
+    }
+
+    #[cfg(test)]
+    pub fn event_modifiers_for_test(&self, id: &SectorId) -> Vec<&SectorModifier> {
+        self.event_modifiers
+            .iter()
+            .filter(|(sector, _)| sector == id)
+            .map(|(_, modifier)| modifier)
+            .collect()
+    }
+
     #[cfg(test)]
     pub fn set_modifier_for_test(
         &mut self,
@@ -280,6 +569,13 @@ impl IndustryRuntime {
     }
 }
 
+/// 炭素価格が高いほど排出削減が進み、実効的な排出係数が下がる。
+fn effective_emission_factor(factor: f64, carbon_price: f64) -> f64 {
+    let price = carbon_price.max(0.0);
+    let abatement = MAX_ABATEMENT * price / (price + ABATEMENT_HALF_PRICE);
+    factor.max(0.0) * (1.0 - abatement)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -297,6 +593,24 @@ mod tests {
         assert!(runtime.energy_cost_index() >= 0.5);
     }
 
+    #[test]
+    fn energy_shortage_raises_household_energy_prices() {
+        let catalog = IndustryCatalog::from_embedded().expect("catalog");
+        let mut baseline = IndustryRuntime::from_catalog(catalog.clone());
+        let baseline_prices = baseline.simulate_tick(60.0, 1.0).household_prices;
+
+        let mut shortage = IndustryRuntime::from_catalog(catalog);
+        let electricity = SectorId::new(IndustryCategory::Energy, "electricity");
+        shortage.set_modifier_for_test(&electricity, 0.0, -0.9, 600.0);
+        let mut shortage_prices = shortage.simulate_tick(60.0, 1.0).household_prices;
+        for _ in 0..4 {
+            shortage_prices = shortage.simulate_tick(60.0, 1.0).household_prices;
+        }
+
+        assert!(baseline_prices.overall > 0.0);
+        assert!(shortage_prices.energy > baseline_prices.energy);
+    }
+
     #[test]
     fn dependency_shortage_reduces_output() {
         let mut catalog = IndustryCatalog::default();
@@ -311,6 +625,9 @@ mod tests {
                     base_cost: 80.0,
                     price_sensitivity: 0.3,
                     employment: 90.0,
+                    household_share: 0.0,
+                    emission_factor: 0.0,
+                    efficiency: 1.0,
                     dependencies: Vec::new(),
                 },
             )
@@ -326,6 +643,9 @@ mod tests {
                     base_cost: 120.0,
                     price_sensitivity: 0.4,
                     employment: 110.0,
+                    household_share: 0.0,
+                    emission_factor: 0.0,
+                    efficiency: 1.0,
                     dependencies: vec![SectorDependency {
                         sector: "electricity".into(),
                         category: Some(IndustryCategory::Energy),
@@ -378,6 +698,9 @@ mod tests {
                     base_cost: 50.0,
                     price_sensitivity: 0.6,
                     employment: 80.0,
+                    household_share: 0.0,
+                    emission_factor: 0.0,
+                    efficiency: 1.0,
                     dependencies: Vec::new(),
                 },
             )
@@ -424,6 +747,9 @@ mod tests {
                     base_cost: 130.0,
                     price_sensitivity: 0.4,
                     employment: 120.0,
+                    household_share: 0.0,
+                    emission_factor: 0.0,
+                    efficiency: 1.0,
                     dependencies: Vec::new(),
                 },
             )
@@ -439,6 +765,9 @@ mod tests {
                     base_cost: 90.0,
                     price_sensitivity: 0.5,
                     employment: 90.0,
+                    household_share: 0.0,
+                    emission_factor: 0.0,
+                    efficiency: 1.0,
                     dependencies: vec![SectorDependency {
                         sector: "automotive".into(),
                         category: Some(IndustryCategory::Secondary),
@@ -457,6 +786,7 @@ mod tests {
             state.last_output = 400.0;
             state.potential_demand = 400.0;
             state.supply_capacity = 400.0;
+            state.capital_stock = 400.0;
             state.inventory = 0.0;
             state.unmet_demand = 0.0;
         }
@@ -464,6 +794,7 @@ mod tests {
             state.last_output = 800.0;
             state.potential_demand = 800.0;
             state.supply_capacity = 800.0;
+            state.capital_stock = 800.0;
         }
         runtime.set_modifier_for_test(&auto_id, 0.0, 1.2, 180.0);
         let mut outcome = runtime.simulate_tick(60.0, 1.0);
@@ -500,6 +831,9 @@ mod tests {
                     base_cost: 130.0,
                     price_sensitivity: 0.5,
                     employment: 120.0,
+                    household_share: 0.0,
+                    emission_factor: 0.0,
+                    efficiency: 1.0,
                     dependencies: Vec::new(),
                 },
             )
@@ -523,6 +857,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn capital_stock_bounds_supply_capacity() {
+        let catalog = IndustryCatalog::from_embedded().expect("catalog");
+        let mut runtime = IndustryRuntime::from_catalog(catalog);
+        let id = SectorId::new(IndustryCategory::Secondary, "automotive");
+        runtime.simulate_tick(60.0, 1.0);
+        if let Some(state) = runtime.states.get_mut(&id) {
+            state.capital_stock = 60.0;
+        }
+        runtime.simulate_tick(60.0, 1.0);
+        let state = runtime.states.get(&id).expect("automotive state");
+        assert!(state.supply_capacity <= 60.0 + 1e-9);
+        assert!(state.capital_stock < 60.0 + state.last_investment);
+    }
+
     #[test]
     fn long_run_simulation_remains_stable() {
         let catalog = IndustryCatalog::from_embedded().expect("catalog");
@@ -552,4 +901,87 @@ mod tests {
             assert!(step < 1 || outcome.total_revenue.is_finite());
         }
     }
+
+    #[test]
+    fn carbon_price_cuts_emissions_and_raises_costs() {
+        let catalog = IndustryCatalog::from_embedded().expect("catalog");
+        let mut baseline = IndustryRuntime::from_catalog(catalog.clone());
+        let mut priced = IndustryRuntime::from_catalog(catalog);
+        priced.set_carbon_price(60.0);
+        let base = baseline.simulate_tick(60.0, 1.0);
+        let taxed = priced.simulate_tick(60.0, 1.0);
+        assert!(base.total_emissions > 0.0);
+        assert!(taxed.total_emissions < base.total_emissions);
+        let id = SectorId::new(IndustryCategory::Energy, "electricity");
+        let base_cost = base.sector_metrics[&id].cost;
+        let taxed_cost = taxed.sector_metrics[&id].cost;
+        assert!(taxed_cost > base_cost);
+    }
+
+    #[test]
+    fn electricity_shortfall_triggers_blackout_in_dependent_sectors() {
+        let catalog = IndustryCatalog::from_embedded().expect("catalog");
+        let mut baseline = IndustryRuntime::from_catalog(catalog.clone());
+        let mut runtime = IndustryRuntime::from_catalog(catalog);
+        baseline.simulate_tick(60.0, 1.0);
+        runtime.simulate_tick(60.0, 1.0);
+        assert!(runtime.grid_status().reserve_margin >= 0.0);
+
+        let electricity = SectorId::new(IndustryCategory::Energy, ELECTRICITY_KEY);
+        if let Some(state) = runtime.states.get_mut(&electricity) {
+            state.capital_stock = 150.0;
+        }
+        let outcome = runtime.simulate_tick(60.0, 1.0);
+        let base_outcome = baseline.simulate_tick(60.0, 1.0);
+        let record = outcome.blackout.expect("blackout should start");
+        assert!(record.output_cut > 0.0);
+        assert!(outcome.reports.iter().any(|r| r.contains("計画停電")));
+        let status = runtime.grid_status();
+        assert!(status.reserve_margin < 0.0);
+        assert_eq!(status.active, Some(record));
+        assert_eq!(status.history.len(), 1);
+
+        let steel = SectorId::new(IndustryCategory::Secondary, "steel");
+        assert!(outcome.sector_metrics[&steel].output < base_outcome.sector_metrics[&steel].output);
+    }
+
+    #[test]
+    fn event_shocks_shift_prices_until_expiry_and_destroy_inventory() {
+        let catalog = IndustryCatalog::from_embedded().expect("catalog");
+        let mut baseline = IndustryRuntime::from_catalog(catalog.clone());
+        let mut runtime = IndustryRuntime::from_catalog(catalog);
+        let diesel = SectorId::new(IndustryCategory::Energy, "diesel");
+        runtime
+            .apply_shock(
+                &diesel,
+                SectorShock::Modifier(SectorModifier {
+                    price_shift: 0.5,
+                    remaining_minutes: 90.0,
+                    ..SectorModifier::default()
+                }),
+            )
+            .expect("price shock");
+        assert!(
+            runtime
+                .apply_shock(&diesel, SectorShock::DestroyInventory(1.5))
+                .is_err()
+        );
+
+        let base = baseline.simulate_tick(60.0, 1.0);
+        let shocked = runtime.simulate_tick(60.0, 1.0);
+        assert!(shocked.sector_metrics[&diesel].price > base.sector_metrics[&diesel].price * 1.4);
+        runtime.simulate_tick(60.0, 1.0);
+        assert!(runtime.event_modifiers.is_empty());
+
+        runtime.states.get_mut(&diesel).unwrap().inventory = 40.0;
+        runtime
+            .apply_shock(&diesel, SectorShock::DestroyInventory(0.25))
+            .expect("inventory");
+        runtime
+            .apply_shock(&diesel, SectorShock::ScaleCapacity(0.5))
+            .expect("capacity");
+        let state = &runtime.states[&diesel];
+        assert!((state.inventory - 30.0).abs() < 1e-9);
+        assert!(state.supply_capacity <= state.capital_stock);
+    }
 }
@@ -0,0 +1,185 @@
+use crate::game::constants::MINUTES_PER_DAY;
+
+const BASE_CPI: f64 = 100.0;
+const MAX_CPI_HISTORY: usize = 400;
+const INFLATION_WINDOW_MINUTES: f64 = 365.0 * MINUTES_PER_DAY as f64;
+const ENERGY_TREND_MINUTES: f64 = 7.0 * MINUTES_PER_DAY as f64;
+const ENERGY_SPIKE_THRESHOLD: f64 = 0.08;
+const ENERGY_APPROVAL_SENSITIVITY: f64 = 40.0;
+
+/// 家計バスケットの価格水準 (基準価格 = 1.0)。産業 tick ごとに算出される全体共通の値。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HouseholdPrices {
+    pub overall: f64,
+    pub energy: f64,
+}
+
+impl Default for HouseholdPrices {
+    fn default() -> Self {
+        Self {
+            overall: 1.0,
+            energy: 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CpiPoint {
+    pub simulation_minutes: f64,
+    pub cpi: f64,
+    pub inflation: f64,
+}
+
+/// 国別の消費者物価指数。家計バスケットの価格に消費税の上乗せ分を加味し、
+/// 日次サンプルの履歴から直近 1 年 (履歴が短い場合は開始時点) 比のインフレ率を求める。
+#[derive(Debug, Clone)]
+pub struct ConsumerPriceIndex {
+    base_tax_wedge: f64,
+    cpi: f64,
+    energy_index: f64,
+    energy_trend: f64,
+    inflation: f64,
+    history: Vec<CpiPoint>,
+}
+
+impl ConsumerPriceIndex {
+    pub fn new(consumption_rate: f64) -> Self {
+        Self {
+            base_tax_wedge: tax_wedge(consumption_rate),
+            cpi: BASE_CPI,
+            energy_index: BASE_CPI,
+            energy_trend: BASE_CPI,
+            inflation: 0.0,
+            history: Vec::new(),
+        }
+    }
+
+    pub fn cpi(&self) -> f64 {
+        self.cpi
+    }
+
+    pub fn energy_index(&self) -> f64 {
+        self.energy_index
+    }
+
+    pub fn inflation(&self) -> f64 {
+        self.inflation
+    }
+
+    pub fn history(&self) -> &[CpiPoint] {
+        &self.history
+    }
+
+    /// 最新の家計価格を取り込み、エネルギー価格急騰による支持率の低下幅 (端数あり) を返す。
+    pub(crate) fn observe(
+        &mut self,
+        prices: HouseholdPrices,
+        consumption_rate: f64,
+        elapsed_minutes: f64,
+        simulation_minutes: f64,
+        scale: f64,
+    ) -> f64 {
+        let wedge = tax_wedge(consumption_rate) / self.base_tax_wedge;
+        self.cpi = (BASE_CPI * prices.overall.max(0.0) * wedge).max(0.0);
+        self.energy_index = (BASE_CPI * prices.energy.max(0.0)).max(0.0);
+
+        let spike = if self.energy_trend > f64::EPSILON {
+            self.energy_index / self.energy_trend - 1.0
+        } else {
+            0.0
+        };
+        let penalty = if spike > ENERGY_SPIKE_THRESHOLD {
+            (spike - ENERGY_SPIKE_THRESHOLD) * ENERGY_APPROVAL_SENSITIVITY * scale.max(0.0)
+        } else {
+            0.0
+        };
+        let weight = (elapsed_minutes.max(0.0) / ENERGY_TREND_MINUTES).clamp(0.0, 1.0);
+        self.energy_trend = self.energy_trend * (1.0 - weight) + self.energy_index * weight;
+
+        self.inflation = self.compute_inflation(simulation_minutes);
+        self.record_sample(simulation_minutes);
+
+        penalty
+    }
+
+    fn compute_inflation(&self, simulation_minutes: f64) -> f64 {
+        let reference = self
+            .history
+            .iter()
+            .find(|point| simulation_minutes - point.simulation_minutes <= INFLATION_WINDOW_MINUTES)
+            .map(|point| point.cpi);
+        match reference {
+            Some(base) if base > f64::EPSILON => (self.cpi / base - 1.0) * 100.0,
+            _ => 0.0,
+        }
+    }
+
+    fn record_sample(&mut self, simulation_minutes: f64) {
+        let point = CpiPoint {
+            simulation_minutes,
+            cpi: self.cpi,
+            inflation: self.inflation,
+        };
+        if let Some(last) = self.history.last()
+            && simulation_minutes - last.simulation_minutes < MINUTES_PER_DAY as f64
+        {
+            return;
+        }
+        self.history.push(point);
+        if self.history.len() > MAX_CPI_HISTORY {
+            self.history.remove(0);
+        }
+    }
+}
+
+fn tax_wedge(consumption_rate: f64) -> f64 {
+    1.0 + consumption_rate.clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prices(overall: f64, energy: f64) -> HouseholdPrices {
+        HouseholdPrices { overall, energy }
+    }
+
+    #[test]
+    fn cpi_tracks_prices_and_consumption_tax() {
+        let mut index = ConsumerPriceIndex::new(0.08);
+        index.observe(prices(1.0, 1.0), 0.08, 60.0, 60.0, 1.0);
+        assert!((index.cpi() - 100.0).abs() < 1e-6);
+
+        index.observe(prices(1.1, 1.0), 0.18, 60.0, 120.0, 1.0);
+        let expected = 100.0 * 1.1 * (1.18 / 1.08);
+        assert!((index.cpi() - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    fn inflation_compares_against_oldest_sample_within_a_year() {
+        let mut index = ConsumerPriceIndex::new(0.0);
+        let day = MINUTES_PER_DAY as f64;
+        index.observe(prices(1.0, 1.0), 0.0, day, 0.0, 1.0);
+        index.observe(prices(1.05, 1.0), 0.0, day, 100.0 * day, 1.0);
+        assert!((index.inflation() - 5.0).abs() < 1e-6);
+
+        index.observe(prices(1.2, 1.0), 0.0, day, 400.0 * day, 1.0);
+        let expected = (1.2 / 1.05 - 1.0) * 100.0;
+        assert!((index.inflation() - expected).abs() < 1e-6);
+        assert_eq!(index.history().len(), 3);
+    }
+
+    #[test]
+    fn energy_spike_accumulates_approval_penalty() {
+        let mut index = ConsumerPriceIndex::new(0.08);
+        let mut penalty = 0.0;
+        for step in 0..6 {
+            penalty += index.observe(prices(1.1, 1.6), 0.08, 60.0, 60.0 * step as f64, 1.0);
+        }
+        assert!(penalty > 0.0);
+
+        let mut calm = ConsumerPriceIndex::new(0.08);
+        let calm_penalty = calm.observe(prices(1.0, 1.02), 0.08, 60.0, 60.0, 1.0);
+        assert_eq!(calm_penalty, 0.0);
+    }
+}
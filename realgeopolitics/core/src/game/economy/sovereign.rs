@@ -0,0 +1,556 @@
+use anyhow::{Result, bail, ensure};
+use serde::{Deserialize, Serialize};
+
+use super::{CreditRating, DebtCycleOutcome, FiscalAccount, RevenueKind, upgrade_rating};
+use crate::game::country::BudgetAllocation;
+
+const MISSED_PAYMENTS_BEFORE_DEFAULT: u32 = 3;
+const HAIRCUT_SHARE: f64 = 0.35;
+const REPROFILE_EXTRA_YEARS: f64 = 5.0;
+const REPROFILE_COUPON_CAP: f64 = 0.03;
+const RECOVERY_DAYS_PER_NOTCH: u32 = 90;
+const RECOVERY_MAX_DEBT_RATIO: f64 = 1.0;
+const BAILOUT_GDP_SHARE: f64 = 0.08;
+const BAILOUT_TRANCHES: u32 = 4;
+const BAILOUT_TRANCHE_INTERVAL_DAYS: u32 = 30;
+const BAILOUT_COUPON: f64 = 0.015;
+const BAILOUT_MATURITY_YEARS: f64 = 10.0;
+const BAILOUT_OFFER_DAYS: u32 = 45;
+const BAILOUT_LENDER: &str = "国際通貨基金";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DebtStatus {
+    Performing,
+    MissedPayments,
+    Default,
+    Restructuring,
+    Recovery,
+}
+
+impl DebtStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            DebtStatus::Performing => "正常",
+            DebtStatus::MissedPayments => "支払遅延",
+            DebtStatus::Default => "デフォルト",
+            DebtStatus::Restructuring => "債務再編交渉中",
+            DebtStatus::Recovery => "回復過程",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RestructuringTerms {
+    /// 元本の削減。交渉は長引き、債権国との関係が悪化する。
+    Haircut,
+    /// 満期延長と金利減免の代わりに緊縮財政を受け入れる。
+    Austerity,
+}
+
+impl RestructuringTerms {
+    pub fn label(self) -> &'static str {
+        match self {
+            RestructuringTerms::Haircut => "元本削減",
+            RestructuringTerms::Austerity => "緊縮条件付き返済猶予",
+        }
+    }
+
+    fn negotiation_days(self) -> u32 {
+        match self {
+            RestructuringTerms::Haircut => 45,
+            RestructuringTerms::Austerity => 20,
+        }
+    }
+
+    fn settlement_rating(self) -> CreditRating {
+        match self {
+            RestructuringTerms::Haircut => CreditRating::CC,
+            RestructuringTerms::Austerity => CreditRating::CCC,
+        }
+    }
+}
+
+/// 支援融資の条件。予算配分 (GDP 比 %) が条件を満たすときだけ融資が実行される。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BailoutConditions {
+    pub max_military: f64,
+    pub max_welfare: f64,
+    pub min_debt_service: f64,
+}
+
+impl BailoutConditions {
+    fn for_allocation(allocation: &BudgetAllocation) -> Self {
+        Self {
+            max_military: (allocation.military * 0.7).max(2.0),
+            max_welfare: (allocation.welfare * 0.85).max(3.0),
+            min_debt_service: allocation.debt_service.max(8.0),
+        }
+    }
+
+    pub fn is_met(&self, allocation: &BudgetAllocation) -> bool {
+        allocation.military <= self.max_military + f64::EPSILON
+            && allocation.welfare <= self.max_welfare + f64::EPSILON
+            && allocation.debt_service + f64::EPSILON >= self.min_debt_service
+    }
+
+    pub fn describe(&self) -> String {
+        format!(
+            "軍事 {:.1}% 以下 / 福祉 {:.1}% 以下 / 債務返済 {:.1}% 以上",
+            self.max_military, self.max_welfare, self.min_debt_service
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BailoutOffer {
+    pub lender: String,
+    pub total: f64,
+    pub conditions: BailoutConditions,
+    pub expires_in_days: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BailoutProgram {
+    pub lender: String,
+    pub tranche: f64,
+    pub remaining_tranches: u32,
+    pub days_to_next_tranche: u32,
+    pub conditions: BailoutConditions,
+    pub breaches: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum SovereignEvent {
+    PaymentMissed {
+        count: u32,
+        arrears: f64,
+    },
+    PaymentsCured,
+    Defaulted,
+    BailoutOffered {
+        total: f64,
+    },
+    BailoutExpired,
+    RestructuringSettled {
+        terms: RestructuringTerms,
+        written_off: f64,
+    },
+    Upgraded(CreditRating),
+    InvestmentGradeRestored,
+    TrancheDisbursed {
+        amount: f64,
+        remaining: u32,
+    },
+    TrancheWithheld {
+        remaining: u32,
+    },
+    ProgramCompleted,
+}
+
+/// 国家債務の返済状況。日次の債務処理の結果から支払遅延 → デフォルト → 再編 → 回復へと遷移する。
+#[derive(Debug, Clone)]
+pub struct SovereignDebt {
+    status: DebtStatus,
+    missed_payments: u32,
+    arrears: f64,
+    negotiation: Option<(RestructuringTerms, u32)>,
+    clean_days: u32,
+    offer: Option<BailoutOffer>,
+    program: Option<BailoutProgram>,
+}
+
+impl SovereignDebt {
+    pub fn new() -> Self {
+        Self {
+            status: DebtStatus::Performing,
+            missed_payments: 0,
+            arrears: 0.0,
+            negotiation: None,
+            clean_days: 0,
+            offer: None,
+            program: None,
+        }
+    }
+
+    pub fn status(&self) -> DebtStatus {
+        self.status
+    }
+
+    pub fn missed_payments(&self) -> u32 {
+        self.missed_payments
+    }
+
+    pub fn arrears(&self) -> f64 {
+        self.arrears
+    }
+
+    pub fn negotiation(&self) -> Option<(RestructuringTerms, u32)> {
+        self.negotiation
+    }
+
+    pub fn offer(&self) -> Option<&BailoutOffer> {
+        self.offer.as_ref()
+    }
+
+    pub fn program(&self) -> Option<&BailoutProgram> {
+        self.program.as_ref()
+    }
+
+    pub(crate) fn begin_restructuring(&mut self, terms: RestructuringTerms) -> Result<()> {
+        ensure!(
+            self.status == DebtStatus::Default,
+            "債務再編はデフォルト状態でのみ開始できます (現在: {})",
+            self.status.label()
+        );
+        self.status = DebtStatus::Restructuring;
+        self.negotiation = Some((terms, terms.negotiation_days()));
+        Ok(())
+    }
+
+    pub(crate) fn accept_bailout(
+        &mut self,
+        fiscal: &mut FiscalAccount,
+        allocation: &BudgetAllocation,
+    ) -> Result<f64> {
+        let Some(offer) = self.offer.as_ref() else {
+            bail!("受諾できる支援融資の提案がありません");
+        };
+        ensure!(
+            offer.conditions.is_met(allocation),
+            "予算配分が支援条件を満たしていません ({})",
+            offer.conditions.describe()
+        );
+        let offer = self.offer.take().expect("offer checked above");
+        let tranche = offer.total / BAILOUT_TRANCHES as f64;
+        disburse(fiscal, tranche);
+        self.program = Some(BailoutProgram {
+            lender: offer.lender,
+            tranche,
+            remaining_tranches: BAILOUT_TRANCHES - 1,
+            days_to_next_tranche: BAILOUT_TRANCHE_INTERVAL_DAYS,
+            conditions: offer.conditions,
+            breaches: 0,
+        });
+        Ok(tranche)
+    }
+
+    pub(crate) fn decline_bailout(&mut self) -> Result<()> {
+        ensure!(self.offer.is_some(), "辞退できる支援融資の提案がありません");
+        self.offer = None;
+        Ok(())
+    }
+
+    /// 日次の債務処理結果を取り込み、状態遷移と支援融資の進捗を返す。
+    pub(crate) fn review(
+        &mut self,
+        fiscal: &mut FiscalAccount,
+        outcome: &DebtCycleOutcome,
+        gdp: f64,
+        allocation: &BudgetAllocation,
+    ) -> Vec<SovereignEvent> {
+        let mut events = Vec::new();
+        let shortfall =
+            outcome.rollover_shortfall + (outcome.interest_due - outcome.interest_paid).max(0.0);
+        let missed = shortfall > 1e-6;
+
+        match self.status {
+            DebtStatus::Performing | DebtStatus::MissedPayments | DebtStatus::Recovery => {
+                if missed {
+                    self.missed_payments += 1;
+                    self.arrears += shortfall;
+                    self.clean_days = 0;
+                    events.push(SovereignEvent::PaymentMissed {
+                        count: self.missed_payments,
+                        arrears: self.arrears,
+                    });
+                    if self.status != DebtStatus::Recovery {
+                        self.status = DebtStatus::MissedPayments;
+                    }
+                    if self.missed_payments >= MISSED_PAYMENTS_BEFORE_DEFAULT
+                        || fiscal.credit_rating == CreditRating::D
+                    {
+                        self.declare_default(fiscal, allocation, gdp, &mut events);
+                    }
+                } else if self.status == DebtStatus::MissedPayments {
+                    self.status = DebtStatus::Performing;
+                    self.missed_payments = 0;
+                    self.arrears = 0.0;
+                    events.push(SovereignEvent::PaymentsCured);
+                } else if self.status == DebtStatus::Recovery {
+                    self.advance_recovery(fiscal, gdp, &mut events);
+                } else if fiscal.credit_rating == CreditRating::D {
+                    self.declare_default(fiscal, allocation, gdp, &mut events);
+                }
+            }
+            DebtStatus::Default => {}
+            DebtStatus::Restructuring => {
+                if let Some((terms, days)) = self.negotiation.as_mut() {
+                    *days = days.saturating_sub(1);
+                    if *days == 0 {
+                        let terms = *terms;
+                        let written_off = self.settle(fiscal, terms);
+                        events.push(SovereignEvent::RestructuringSettled { terms, written_off });
+                    }
+                }
+            }
+        }
+
+        if let Some(offer) = self.offer.as_mut() {
+            offer.expires_in_days = offer.expires_in_days.saturating_sub(1);
+            if offer.expires_in_days == 0 {
+                self.offer = None;
+                events.push(SovereignEvent::BailoutExpired);
+            }
+        }
+        self.advance_program(fiscal, allocation, &mut events);
+        events
+    }
+
+    fn declare_default(
+        &mut self,
+        fiscal: &mut FiscalAccount,
+        allocation: &BudgetAllocation,
+        gdp: f64,
+        events: &mut Vec<SovereignEvent>,
+    ) {
+        self.status = DebtStatus::Default;
+        self.clean_days = 0;
+        fiscal.set_credit_rating(CreditRating::D);
+        fiscal.set_market_access(false);
+        events.push(SovereignEvent::Defaulted);
+        if self.offer.is_none() && self.program.is_none() {
+            let total = (gdp * BAILOUT_GDP_SHARE).max(50.0);
+            self.offer = Some(BailoutOffer {
+                lender: BAILOUT_LENDER.to_string(),
+                total,
+                conditions: BailoutConditions::for_allocation(allocation),
+                expires_in_days: BAILOUT_OFFER_DAYS,
+            });
+            events.push(SovereignEvent::BailoutOffered { total });
+        }
+    }
+
+    fn settle(&mut self, fiscal: &mut FiscalAccount, terms: RestructuringTerms) -> f64 {
+        let written_off = match terms {
+            RestructuringTerms::Haircut => fiscal.write_down_debt(fiscal.debt() * HAIRCUT_SHARE),
+            RestructuringTerms::Austerity => {
+                fiscal.reprofile_debt(REPROFILE_EXTRA_YEARS, REPROFILE_COUPON_CAP);
+                0.0
+            }
+        };
+        fiscal.set_credit_rating(terms.settlement_rating());
+        fiscal.set_market_access(true);
+        self.status = DebtStatus::Recovery;
+        self.negotiation = None;
+        self.missed_payments = 0;
+        self.arrears = 0.0;
+        self.clean_days = 0;
+        written_off
+    }
+
+    fn advance_recovery(
+        &mut self,
+        fiscal: &mut FiscalAccount,
+        gdp: f64,
+        events: &mut Vec<SovereignEvent>,
+    ) {
+        self.clean_days += 1;
+        self.missed_payments = 0;
+        self.arrears = 0.0;
+        let debt_ratio = if gdp > 0.0 {
+            fiscal.debt() / gdp
+        } else {
+            f64::INFINITY
+        };
+        if self.clean_days < RECOVERY_DAYS_PER_NOTCH || debt_ratio > RECOVERY_MAX_DEBT_RATIO {
+            return;
+        }
+        self.clean_days = 0;
+        let upgraded = upgrade_rating(fiscal.credit_rating);
+        fiscal.set_credit_rating(upgraded);
+        events.push(SovereignEvent::Upgraded(upgraded));
+        if upgraded == CreditRating::BBB {
+            self.status = DebtStatus::Performing;
+            events.push(SovereignEvent::InvestmentGradeRestored);
+        }
+    }
+
+    fn advance_program(
+        &mut self,
+        fiscal: &mut FiscalAccount,
+        allocation: &BudgetAllocation,
+        events: &mut Vec<SovereignEvent>,
+    ) {
+        let Some(program) = self.program.as_mut() else {
+            return;
+        };
+        program.days_to_next_tranche = program.days_to_next_tranche.saturating_sub(1);
+        if program.days_to_next_tranche > 0 {
+            return;
+        }
+        program.remaining_tranches = program.remaining_tranches.saturating_sub(1);
+        program.days_to_next_tranche = BAILOUT_TRANCHE_INTERVAL_DAYS;
+        if program.conditions.is_met(allocation) {
+            disburse(fiscal, program.tranche);
+            events.push(SovereignEvent::TrancheDisbursed {
+                amount: program.tranche,
+                remaining: program.remaining_tranches,
+            });
+        } else {
+            program.breaches += 1;
+            events.push(SovereignEvent::TrancheWithheld {
+                remaining: program.remaining_tranches,
+            });
+        }
+        if program.remaining_tranches == 0 {
+            self.program = None;
+            events.push(SovereignEvent::ProgramCompleted);
+        }
+    }
+}
+
+impl Default for SovereignDebt {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn disburse(fiscal: &mut FiscalAccount, amount: f64) {
+    fiscal.record_revenue(RevenueKind::Aid, amount);
+    fiscal.issue_official_loan(amount, BAILOUT_COUPON, BAILOUT_MATURITY_YEARS);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn missed_outcome() -> DebtCycleOutcome {
+        DebtCycleOutcome {
+            interest_due: 10.0,
+            interest_paid: 2.0,
+            principal_repaid: 0.0,
+            matured: 0.0,
+            rolled_over: 0.0,
+            rollover_shortfall: 0.0,
+            new_issuance: 0.0,
+            downgraded: None,
+            crisis: None,
+        }
+    }
+
+    fn clean_outcome() -> DebtCycleOutcome {
+        DebtCycleOutcome {
+            interest_paid: 10.0,
+            ..missed_outcome()
+        }
+    }
+
+    #[test]
+    fn repeated_missed_payments_lead_to_default_and_bailout_offer() {
+        let mut sovereign = SovereignDebt::new();
+        let mut fiscal = FiscalAccount::new(10.0, CreditRating::B);
+        let allocation = BudgetAllocation::default();
+
+        let first = sovereign.review(&mut fiscal, &missed_outcome(), 1_000.0, &allocation);
+        assert_eq!(sovereign.status(), DebtStatus::MissedPayments);
+        assert!(matches!(
+            first[0],
+            SovereignEvent::PaymentMissed { count: 1, .. }
+        ));
+
+        sovereign.review(&mut fiscal, &missed_outcome(), 1_000.0, &allocation);
+        let third = sovereign.review(&mut fiscal, &missed_outcome(), 1_000.0, &allocation);
+        assert_eq!(sovereign.status(), DebtStatus::Default);
+        assert!(third.contains(&SovereignEvent::Defaulted));
+        assert_eq!(fiscal.credit_rating, CreditRating::D);
+        assert!(!fiscal.has_market_access());
+        assert!(sovereign.offer().is_some());
+    }
+
+    #[test]
+    fn cured_payments_return_to_performing() {
+        let mut sovereign = SovereignDebt::new();
+        let mut fiscal = FiscalAccount::new(10.0, CreditRating::BB);
+        let allocation = BudgetAllocation::default();
+        sovereign.review(&mut fiscal, &missed_outcome(), 1_000.0, &allocation);
+        let events = sovereign.review(&mut fiscal, &clean_outcome(), 1_000.0, &allocation);
+        assert_eq!(events, vec![SovereignEvent::PaymentsCured]);
+        assert_eq!(sovereign.status(), DebtStatus::Performing);
+        assert_eq!(sovereign.missed_payments(), 0);
+    }
+
+    #[test]
+    fn haircut_restructuring_then_recovery_reaches_investment_grade() {
+        let mut sovereign = SovereignDebt::new();
+        let mut fiscal = FiscalAccount::new(10.0, CreditRating::D);
+        fiscal.add_debt(400.0);
+        let allocation = BudgetAllocation::default();
+        sovereign.review(&mut fiscal, &missed_outcome(), 1_000.0, &allocation);
+        assert_eq!(sovereign.status(), DebtStatus::Default);
+        assert!(
+            sovereign
+                .begin_restructuring(RestructuringTerms::Haircut)
+                .is_ok()
+        );
+        assert!(
+            sovereign
+                .begin_restructuring(RestructuringTerms::Austerity)
+                .is_err()
+        );
+
+        let mut settled = None;
+        for _ in 0..60 {
+            for event in sovereign.review(&mut fiscal, &clean_outcome(), 1_000.0, &allocation) {
+                if let SovereignEvent::RestructuringSettled { written_off, .. } = event {
+                    settled = Some(written_off);
+                }
+            }
+        }
+        let written_off = settled.expect("restructuring should settle");
+        assert!((written_off - 140.0).abs() < 1e-6);
+        assert!(fiscal.has_market_access());
+
+        for _ in 0..(RECOVERY_DAYS_PER_NOTCH * 4) {
+            sovereign.review(&mut fiscal, &clean_outcome(), 1_000.0, &allocation);
+        }
+        assert_eq!(fiscal.credit_rating, CreditRating::BBB);
+        assert_eq!(sovereign.status(), DebtStatus::Performing);
+    }
+
+    #[test]
+    fn bailout_tranches_require_conditionality() {
+        let mut sovereign = SovereignDebt::new();
+        let mut fiscal = FiscalAccount::new(10.0, CreditRating::D);
+        let allocation = BudgetAllocation::default();
+        sovereign.review(&mut fiscal, &missed_outcome(), 1_000.0, &allocation);
+        assert!(sovereign.accept_bailout(&mut fiscal, &allocation).is_err());
+
+        let conditions = sovereign.offer().expect("offer").conditions;
+        let compliant = BudgetAllocation::new(
+            allocation.infrastructure,
+            conditions.max_military,
+            conditions.max_welfare,
+            allocation.diplomacy,
+            conditions.min_debt_service,
+            allocation.administration,
+            allocation.research,
+            true,
+        )
+        .unwrap();
+        let cash_before = fiscal.cash_reserve();
+        let tranche = sovereign.accept_bailout(&mut fiscal, &compliant).unwrap();
+        assert!(fiscal.cash_reserve() > cash_before);
+        assert!((fiscal.debt() - tranche).abs() < 1e-6);
+
+        let mut withheld = 0;
+        for _ in 0..BAILOUT_TRANCHE_INTERVAL_DAYS {
+            for event in sovereign.review(&mut fiscal, &clean_outcome(), 1_000.0, &allocation) {
+                if matches!(event, SovereignEvent::TrancheWithheld { .. }) {
+                    withheld += 1;
+                }
+            }
+        }
+        assert_eq!(withheld, 1);
+        assert_eq!(sovereign.program().expect("program").breaches, 1);
+    }
+}
@@ -1,12 +1,22 @@
 use std::fmt;
 
-use anyhow::{Result, anyhow};
+use anyhow::{Result, anyhow, ensure};
+use rand::Rng;
+use rand::rngs::StdRng;
 use serde::Deserialize;
 
+use crate::ScheduleSpec;
+use crate::game::country::BudgetAllocation;
 use crate::game::country::CountryState;
-use crate::game::{MAX_METRIC, MAX_RESOURCES, MIN_METRIC, MIN_RESOURCES};
+use crate::game::economy::industry::SectorRegistry;
+use crate::game::economy::{
+    SectorModifier, SectorShock, TaxRateKind, downgrade_rating, upgrade_rating,
+};
+use crate::game::{
+    MAX_METRIC, MAX_RELATION, MAX_RESOURCES, MIN_METRIC, MIN_RELATION, MIN_RESOURCES,
+};
 
-use super::condition::{ConditionEvaluator, parse_condition};
+use super::condition::{ConditionEvaluator, ValueExpression, parse_condition, parse_value};
 use super::formatter::ScriptedEventReport;
 
 /// Compiles a raw event template into a ready-to-run CompiledEventTemplate.
@@ -57,21 +67,237 @@ pub(super) struct EventTemplateRaw {
     condition: String,
     #[serde(default = "EventTemplateRaw::default_check_minutes")]
     check_minutes: u64,
+    /// 暦に沿ったチェック時刻 (`"0 0 L 3 *"` や `"@monthly"`)。指定すると `check_minutes` より優先する。
+    #[serde(default)]
+    schedule: Option<String>,
     #[serde(default)]
     initial_delay_minutes: u64,
     #[serde(default = "EventTemplateRaw::default_cooldown_minutes")]
     cooldown_minutes: u64,
+    /// false のテンプレートは定期チェックされず、`schedule_event` からのみ発生する。
+    #[serde(default = "EventTemplateRaw::default_auto_trigger")]
+    auto_trigger: bool,
+    /// 条件を満たしたチェックごとの発生確率 (0〜1)。
+    #[serde(default = "EventTemplateRaw::default_probability")]
+    probability: f64,
+    #[serde(default)]
+    scope: EventScope,
+    #[serde(default)]
+    targeting: TargetingRaw,
+    /// `top_n` / `bottom_n` の順位付けに使う数式。
+    #[serde(default)]
+    rank_by: Option<String>,
+    #[serde(default = "EventTemplateRaw::default_target_count")]
+    target_count: usize,
     #[serde(default)]
     effects: Vec<EventEffectRaw>,
+    /// 二国間テンプレートで target 側に適用する効果。
+    #[serde(default)]
+    target_effects: Vec<EventEffectRaw>,
+    /// 発生時に重みに従って 1 つだけ選ばれる効果の組。`effects` の後に適用される。
+    #[serde(default)]
+    pool: Vec<PoolEntryRaw>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct PoolEntryRaw {
+    weight: f64,
+    #[serde(default)]
+    effects: Vec<EventEffectRaw>,
+    #[serde(default)]
+    target_effects: Vec<EventEffectRaw>,
+}
+
+/// 条件を満たした国 (二国間テンプレートでは組) のうち、どれに発生させるか。
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum TargetingRaw {
+    /// 条件を満たしたすべてに発生させる。
+    #[default]
+    Each,
+    /// 1 つでも条件を満たせば、世界全体に対して 1 回だけ発生させる。
+    Global,
+    RandomOne,
+    /// `rank_by` の値が大きい順に `target_count` 件。
+    TopN,
+    /// `rank_by` の値が小さい順に `target_count` 件。
+    BottomN,
+}
+
+/// コンパイル済みの `targeting`。`Each` 以外はクールダウンをテンプレート全体で共有する。
+#[derive(Debug)]
+pub(super) enum Targeting {
+    Each,
+    Global,
+    RandomOne,
+    Ranked {
+        metric: ValueExpression,
+        count: usize,
+        highest_first: bool,
+    },
+}
+
+impl Targeting {
+    fn compile(
+        raw: TargetingRaw,
+        rank_by: Option<String>,
+        count: usize,
+        bilateral: bool,
+    ) -> Result<Self> {
+        let highest_first = match raw {
+            TargetingRaw::TopN => true,
+            TargetingRaw::BottomN => false,
+            other => {
+                ensure!(
+                    rank_by.is_none(),
+                    "rank_by は targeting: top_n / bottom_n でのみ指定できます"
+                );
+                return match other {
+                    TargetingRaw::Global if bilateral => Err(anyhow!(
+                        "targeting: global は scope: bilateral と併用できません"
+                    )),
+                    TargetingRaw::Global => Ok(Self::Global),
+                    TargetingRaw::RandomOne => Ok(Self::RandomOne),
+                    _ => Ok(Self::Each),
+                };
+            }
+        };
+        let rank_by = rank_by
+            .ok_or_else(|| anyhow!("targeting: top_n / bottom_n には rank_by が必要です"))?;
+        ensure!(count >= 1, "target_count は 1 以上で指定してください");
+        let metric = parse_value(&rank_by)
+            .map_err(|err| anyhow!("rank_by '{}' を解釈できません: {}", rank_by, err))?;
+        ensure!(
+            bilateral || !metric.references_target(),
+            "target を参照する rank_by は scope: bilateral のテンプレートでのみ指定できます"
+        );
+        Ok(Self::Ranked {
+            metric,
+            count,
+            highest_first,
+        })
+    }
+}
+
+/// `bilateral` のテンプレートは国の組 (actor, target) ごとに条件を評価する。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum EventScope {
+    #[default]
+    Country,
+    Bilateral,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(tag = "type")]
 pub(super) enum EventEffectRaw {
     #[serde(rename = "adjust_metric")]
-    AdjustMetric { metric: String, delta: f64 },
+    AdjustMetric { metric: String, delta: AmountRaw },
     #[serde(rename = "report")]
     Report { message: String },
+    #[serde(rename = "sector_modifier")]
+    SectorModifier {
+        sector: String,
+        #[serde(default)]
+        subsidy_bonus: f64,
+        #[serde(default)]
+        efficiency_bonus: f64,
+        duration_minutes: f64,
+    },
+    #[serde(rename = "scale_sector")]
+    ScaleSector {
+        sector: String,
+        target: SectorScaleTarget,
+        factor: f64,
+    },
+    #[serde(rename = "destroy_inventory")]
+    DestroyInventory { sector: String, share: f64 },
+    #[serde(rename = "shift_sector_price")]
+    ShiftSectorPrice {
+        sector: String,
+        shift: f64,
+        duration_minutes: f64,
+    },
+    #[serde(rename = "set_flag")]
+    SetFlag { flag: String },
+    #[serde(rename = "clear_flag")]
+    ClearFlag { flag: String },
+    #[serde(rename = "set_variable")]
+    SetVariable { variable: String, value: f64 },
+    #[serde(rename = "adjust_variable")]
+    AdjustVariable { variable: String, delta: AmountRaw },
+    /// 同じ国を対象に、別のテンプレートを `delay_minutes` 後に発生させる。
+    #[serde(rename = "schedule_event")]
+    ScheduleEvent { event: String, delay_minutes: u64 },
+    /// 効果を受けた国から相手国への関係値を変える。二国間テンプレート専用。
+    #[serde(rename = "adjust_relation")]
+    AdjustRelation { delta: i32 },
+    #[serde(rename = "change_credit_rating")]
+    ChangeCreditRating {
+        direction: RatingDirection,
+        #[serde(default = "default_rating_steps")]
+        steps: u32,
+    },
+    /// `duration_minutes` 経過後に元へ戻る税率変更。
+    #[serde(rename = "temporary_tax")]
+    TemporaryTax {
+        tax: TaxRateKind,
+        delta: f64,
+        duration_minutes: f64,
+    },
+    #[serde(rename = "set_allocation")]
+    SetAllocation { category: String, percent: f64 },
+    #[serde(rename = "issue_debt")]
+    IssueDebt { amount: f64 },
+    #[serde(rename = "forgive_debt")]
+    ForgiveDebt { share: f64 },
+    /// 世界共通の資源市場の価格に倍率をかける。
+    #[serde(rename = "commodity_shock")]
+    CommodityShock { multiplier: f64 },
+}
+
+/// 効果の量。数値のほか、`"-0.05 * gdp"` や `"uniform(-8, -4)"` のように条件式と同じ
+/// メトリクスを使った数式を文字列で書ける。数式は発生時に国ごとに評価する。
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub(super) enum AmountRaw {
+    Fixed(f64),
+    Formula(String),
+}
+
+impl AmountRaw {
+    fn compile(self, field: &str) -> Result<ValueExpression> {
+        match self {
+            AmountRaw::Fixed(value) => {
+                ensure!(
+                    value.is_finite(),
+                    "{} は有限の数値で指定してください",
+                    field
+                );
+                Ok(ValueExpression::constant(value))
+            }
+            AmountRaw::Formula(text) => parse_value(&text)
+                .map_err(|err| anyhow!("{} の数式 '{}' を解釈できません: {}", field, text, err)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum RatingDirection {
+    Upgrade,
+    Downgrade,
+}
+
+const fn default_rating_steps() -> u32 {
+    1
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum SectorScaleTarget {
+    SupplyCapacity,
+    PotentialDemand,
 }
 
 impl EventTemplateRaw {
@@ -101,16 +327,42 @@ impl EventTemplateRaw {
     const fn default_cooldown_minutes() -> u64 {
         720
     }
+
+    const fn default_auto_trigger() -> bool {
+        true
+    }
+
+    const fn default_probability() -> f64 {
+        1.0
+    }
+
+    const fn default_target_count() -> usize {
+        1
+    }
 }
 pub(super) struct CompiledEventTemplate {
     id: String,
     name: String,
     description: String,
     check_minutes: u64,
+    schedule: Option<ScheduleSpec>,
     initial_delay_minutes: u64,
     cooldown_minutes: f64,
+    auto_trigger: bool,
+    probability: f64,
+    scope: EventScope,
+    targeting: Targeting,
     condition: Box<dyn ConditionEvaluator>,
     effects: Vec<CompiledEffect>,
+    target_effects: Vec<CompiledEffect>,
+    pool: Vec<PoolEntry>,
+}
+
+#[derive(Debug)]
+struct PoolEntry {
+    weight: f64,
+    effects: Vec<CompiledEffect>,
+    target_effects: Vec<CompiledEffect>,
 }
 
 impl fmt::Debug for CompiledEventTemplate {
@@ -120,9 +372,16 @@ impl fmt::Debug for CompiledEventTemplate {
             .field("name", &self.name)
             .field("description", &self.description)
             .field("check_minutes", &self.check_minutes)
+            .field("schedule", &self.schedule)
             .field("initial_delay_minutes", &self.initial_delay_minutes)
             .field("cooldown_minutes", &self.cooldown_minutes)
+            .field("auto_trigger", &self.auto_trigger)
+            .field("probability", &self.probability)
+            .field("scope", &self.scope)
+            .field("targeting", &self.targeting)
             .field("effects", &self.effects)
+            .field("target_effects", &self.target_effects)
+            .field("pool", &self.pool)
             .finish()
     }
 }
@@ -156,26 +415,87 @@ impl CompiledEventTemplate {
     /// };
     ///
     /// let compiled = CompiledEventTemplate::new(raw).unwrap();
-    /// assert_eq!(compiled.check_minutes(), 1);
+    /// assert_eq!(compiled.schedule(), ScheduleSpec::EveryMinutes(1));
     /// ```
     fn new(raw: EventTemplateRaw) -> Result<Self> {
         if raw.check_minutes == 0 {
             return Err(anyhow!("check_minutes は 1 以上である必要があります"));
         }
+        ensure!(
+            (0.0..=1.0).contains(&raw.probability),
+            "probability は 0〜1 で指定してください"
+        );
+        let schedule = raw
+            .schedule
+            .as_deref()
+            .map(str::parse::<ScheduleSpec>)
+            .transpose()
+            .map_err(|err| anyhow!("schedule を解釈できません: {}", err))?;
         let condition = parse_condition(&raw.condition)?;
-        let mut effects = Vec::with_capacity(raw.effects.len());
-        for effect in raw.effects {
-            effects.push(CompiledEffect::from_raw(effect)?);
+        let bilateral = raw.scope == EventScope::Bilateral;
+        ensure!(
+            bilateral || !condition.references_target(),
+            "target や二国間の指標を参照する条件には scope: bilateral が必要です"
+        );
+        let effects = compile_effects(raw.effects)?;
+        let target_effects = compile_effects(raw.target_effects)?;
+        let mut pool = Vec::with_capacity(raw.pool.len());
+        for entry in raw.pool {
+            ensure!(
+                entry.weight.is_finite() && entry.weight > 0.0,
+                "pool の weight は正の数値で指定してください"
+            );
+            pool.push(PoolEntry {
+                weight: entry.weight,
+                effects: compile_effects(entry.effects)?,
+                target_effects: compile_effects(entry.target_effects)?,
+            });
+        }
+        ensure!(
+            bilateral
+                || (target_effects.is_empty()
+                    && pool.iter().all(|entry| entry.target_effects.is_empty())),
+            "target_effects は scope: bilateral のテンプレートでのみ指定できます"
+        );
+        let targeting =
+            Targeting::compile(raw.targeting, raw.rank_by, raw.target_count, bilateral)?;
+        if matches!(targeting, Targeting::Global) {
+            ensure!(
+                effects
+                    .iter()
+                    .chain(pool.iter().flat_map(|entry| entry.effects.iter()))
+                    .all(CompiledEffect::is_world_level),
+                "targeting: global のテンプレートには report・セクター効果・commodity_shock のみ指定できます"
+            );
+        }
+        if !bilateral {
+            let mut actor_effects = effects
+                .iter()
+                .chain(pool.iter().flat_map(|entry| entry.effects.iter()));
+            ensure!(
+                !actor_effects.any(|effect| matches!(
+                    effect,
+                    CompiledEffect::AdjustRelation { .. }
+                ) || effect.references_target()),
+                "adjust_relation や target を参照する数式は scope: bilateral のテンプレートでのみ指定できます"
+            );
         }
         Ok(Self {
             id: raw.id,
             name: raw.name,
             description: raw.description,
             check_minutes: raw.check_minutes,
+            schedule,
             initial_delay_minutes: raw.initial_delay_minutes,
             cooldown_minutes: raw.cooldown_minutes as f64,
+            auto_trigger: raw.auto_trigger,
+            probability: raw.probability,
+            scope: raw.scope,
+            targeting,
             condition,
             effects,
+            target_effects,
+            pool,
         })
     }
 
@@ -218,17 +538,10 @@ impl CompiledEventTemplate {
         &self.description
     }
 
-    /// The configured interval between condition evaluations for this template, in minutes.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// // `tmpl` is a compiled event template
-    /// let minutes = tmpl.check_minutes();
-    /// assert!(minutes >= 1);
-    /// ```
-    pub(super) fn check_minutes(&self) -> u64 {
-        self.check_minutes
+    /// 定期チェックの規則。`schedule` がなければ `check_minutes` ごと。
+    pub(super) fn schedule(&self) -> ScheduleSpec {
+        self.schedule
+            .unwrap_or(ScheduleSpec::EveryMinutes(self.check_minutes))
     }
 
     /// The initial delay configured for the template, in minutes.
@@ -243,6 +556,18 @@ impl CompiledEventTemplate {
         self.initial_delay_minutes
     }
 
+    pub(super) fn auto_trigger(&self) -> bool {
+        self.auto_trigger
+    }
+
+    pub(super) fn targeting(&self) -> &Targeting {
+        &self.targeting
+    }
+
+    pub(super) fn is_bilateral(&self) -> bool {
+        self.scope == EventScope::Bilateral
+    }
+
     /// Determine whether this compiled event template may trigger for a given country at a specific time.
     ///
     /// The template may trigger only if its condition evaluates to true for `country` and the cooldown period
@@ -285,14 +610,119 @@ impl CompiledEventTemplate {
     /// # Returns
     ///
     /// `true` if the template's condition evaluates to `true` for `country`, `false` otherwise.
-    fn condition_matches(&self, country: &CountryState) -> bool {
+    pub(super) fn condition_matches(&self, country: &CountryState) -> bool {
         self.condition.evaluate(country)
     }
+
+    pub(super) fn pair_condition_matches(
+        &self,
+        actor: &CountryState,
+        target: &CountryState,
+    ) -> bool {
+        self.condition.evaluate_pair(actor, target)
+    }
+
+    /// 二国間テンプレート用の `can_trigger`。クールダウンは呼び出し側が組ごとに管理する。
+    pub(super) fn can_trigger_pair(
+        &self,
+        actor: &CountryState,
+        target: &CountryState,
+        last_triggered_at: Option<f64>,
+        current_minutes: f64,
+    ) -> bool {
+        if !self.pair_condition_matches(actor, target) {
+            return false;
+        }
+        match last_triggered_at {
+            Some(last) => current_minutes - last >= self.cooldown_minutes,
+            None => true,
+        }
+    }
+
+    /// 発生確率の判定。確率 1 のテンプレートは乱数を消費しない。
+    pub(super) fn roll_probability(&self, rng: &mut StdRng) -> bool {
+        self.probability >= 1.0 || rng.gen_bool(self.probability)
+    }
+
+    /// `pool` から重みに従って 1 件選ぶ。`pool` が空なら `None`。
+    pub(super) fn choose_pool_entry(&self, rng: &mut StdRng) -> Option<usize> {
+        let total: f64 = self.pool.iter().map(|entry| entry.weight).sum();
+        if self.pool.is_empty() {
+            return None;
+        }
+        let mut target = rng.gen_range(0.0..total);
+        for (idx, entry) in self.pool.iter().enumerate() {
+            if target < entry.weight {
+                return Some(idx);
+            }
+            target -= entry.weight;
+        }
+        Some(self.pool.len() - 1)
+    }
+}
+
+fn compile_effects(raw: Vec<EventEffectRaw>) -> Result<Vec<CompiledEffect>> {
+    raw.into_iter().map(CompiledEffect::from_raw).collect()
 }
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 enum CompiledEffect {
-    AdjustMetric { metric: MetricField, delta: f64 },
-    Report { message: String },
+    AdjustMetric {
+        metric: MetricField,
+        delta: ValueExpression,
+    },
+    Report {
+        message: String,
+    },
+    /// セクターは実行時に `SectorRegistry` で解決する。
+    Sector {
+        sector: String,
+        shock: SectorShock,
+    },
+    SetFlag {
+        flag: String,
+    },
+    ClearFlag {
+        flag: String,
+    },
+    SetVariable {
+        variable: String,
+        value: f64,
+    },
+    AdjustVariable {
+        variable: String,
+        delta: ValueExpression,
+    },
+    /// 後続テンプレートはエンジン構築時に解決を確認する。
+    ScheduleEvent {
+        event: String,
+        delay_minutes: u64,
+    },
+    AdjustRelation {
+        delta: i32,
+    },
+    CreditRating {
+        direction: RatingDirection,
+        steps: u32,
+    },
+    TemporaryTax {
+        tax: TaxRateKind,
+        delta: f64,
+        duration_minutes: f64,
+    },
+    SetAllocation {
+        category: AllocationCategory,
+        percent: f64,
+    },
+    IssueDebt {
+        amount: f64,
+    },
+    ForgiveDebt {
+        share: f64,
+    },
+    /// 資源市場は全世界共通のため、セクター効果と同様に発生後にまとめて適用する。
+    CommodityShock {
+        multiplier: f64,
+    },
 }
 
 impl CompiledEffect {
@@ -302,12 +732,216 @@ impl CompiledEffect {
                 let field = MetricField::from_str(&metric)?;
                 Ok(Self::AdjustMetric {
                     metric: field,
-                    delta,
+                    delta: delta.compile("delta")?,
                 })
             }
             EventEffectRaw::Report { message } => Ok(Self::Report { message }),
+            EventEffectRaw::SectorModifier {
+                sector,
+                subsidy_bonus,
+                efficiency_bonus,
+                duration_minutes,
+            } => {
+                ensure!(
+                    (0.0..=0.9).contains(&subsidy_bonus),
+                    "subsidy_bonus は 0〜0.9 で指定してください"
+                );
+                ensure!(
+                    efficiency_bonus.is_finite() && efficiency_bonus > -1.0,
+                    "efficiency_bonus は -1 より大きい値で指定してください"
+                );
+                Self::sector(
+                    sector,
+                    SectorShock::Modifier(SectorModifier {
+                        subsidy_bonus,
+                        efficiency_bonus,
+                        price_shift: 0.0,
+                        remaining_minutes: positive_duration(duration_minutes)?,
+                    }),
+                )
+            }
+            EventEffectRaw::ScaleSector {
+                sector,
+                target,
+                factor,
+            } => {
+                ensure!(
+                    factor.is_finite() && factor > 0.0,
+                    "factor は正の数値で指定してください"
+                );
+                let shock = match target {
+                    SectorScaleTarget::SupplyCapacity => SectorShock::ScaleCapacity(factor),
+                    SectorScaleTarget::PotentialDemand => SectorShock::ScaleDemand(factor),
+                };
+                Self::sector(sector, shock)
+            }
+            EventEffectRaw::DestroyInventory { sector, share } => {
+                ensure!(
+                    (0.0..=1.0).contains(&share),
+                    "share は 0〜1 で指定してください"
+                );
+                Self::sector(sector, SectorShock::DestroyInventory(share))
+            }
+            EventEffectRaw::ShiftSectorPrice {
+                sector,
+                shift,
+                duration_minutes,
+            } => {
+                ensure!(
+                    shift.is_finite() && shift > -0.9,
+                    "shift は -0.9 より大きい値で指定してください"
+                );
+                Self::sector(
+                    sector,
+                    SectorShock::Modifier(SectorModifier {
+                        price_shift: shift,
+                        remaining_minutes: positive_duration(duration_minutes)?,
+                        ..SectorModifier::default()
+                    }),
+                )
+            }
+            EventEffectRaw::SetFlag { flag } => Ok(Self::SetFlag {
+                flag: story_name("flag", flag)?,
+            }),
+            EventEffectRaw::ClearFlag { flag } => Ok(Self::ClearFlag {
+                flag: story_name("flag", flag)?,
+            }),
+            EventEffectRaw::SetVariable { variable, value } => {
+                ensure!(value.is_finite(), "value は有限の数値で指定してください");
+                Ok(Self::SetVariable {
+                    variable: story_name("variable", variable)?,
+                    value,
+                })
+            }
+            EventEffectRaw::AdjustVariable { variable, delta } => Ok(Self::AdjustVariable {
+                variable: story_name("variable", variable)?,
+                delta: delta.compile("delta")?,
+            }),
+            EventEffectRaw::ScheduleEvent {
+                event,
+                delay_minutes,
+            } => {
+                ensure!(!event.trim().is_empty(), "event を指定してください");
+                ensure!(
+                    delay_minutes >= 1,
+                    "delay_minutes は 1 以上である必要があります"
+                );
+                Ok(Self::ScheduleEvent {
+                    event,
+                    delay_minutes,
+                })
+            }
+            EventEffectRaw::AdjustRelation { delta } => {
+                let span = MAX_RELATION - MIN_RELATION;
+                ensure!(
+                    delta != 0 && delta.abs() <= span,
+                    "adjust_relation の delta は 0 以外の ±{} 以内で指定してください",
+                    span
+                );
+                Ok(Self::AdjustRelation { delta })
+            }
+            EventEffectRaw::ChangeCreditRating { direction, steps } => {
+                ensure!(
+                    (1..=MAX_RATING_STEPS).contains(&steps),
+                    "steps は 1〜{} で指定してください",
+                    MAX_RATING_STEPS
+                );
+                Ok(Self::CreditRating { direction, steps })
+            }
+            EventEffectRaw::TemporaryTax {
+                tax,
+                delta,
+                duration_minutes,
+            } => {
+                ensure!(
+                    delta.is_finite() && delta != 0.0 && delta.abs() <= 0.6,
+                    "temporary_tax の delta は 0 以外の ±0.6 以内で指定してください"
+                );
+                Ok(Self::TemporaryTax {
+                    tax,
+                    delta,
+                    duration_minutes: positive_duration(duration_minutes)?,
+                })
+            }
+            EventEffectRaw::SetAllocation { category, percent } => {
+                ensure!(
+                    percent.is_finite() && (0.0..=100.0).contains(&percent),
+                    "percent は 0〜100 で指定してください"
+                );
+                Ok(Self::SetAllocation {
+                    category: AllocationCategory::from_str(&category)?,
+                    percent,
+                })
+            }
+            EventEffectRaw::IssueDebt { amount } => {
+                ensure!(
+                    amount.is_finite() && amount > 0.0,
+                    "issue_debt の amount は正の数値で指定してください"
+                );
+                Ok(Self::IssueDebt { amount })
+            }
+            EventEffectRaw::ForgiveDebt { share } => {
+                ensure!(
+                    share > 0.0 && share <= 1.0,
+                    "forgive_debt の share は 0 より大きく 1 以下で指定してください"
+                );
+                Ok(Self::ForgiveDebt { share })
+            }
+            EventEffectRaw::CommodityShock { multiplier } => {
+                ensure!(
+                    multiplier.is_finite() && multiplier > 0.0 && multiplier <= 5.0,
+                    "commodity_shock の multiplier は 0 より大きく 5 以下で指定してください"
+                );
+                Ok(Self::CommodityShock { multiplier })
+            }
         }
     }
+
+    /// 特定の国に依存せず、`targeting: global` でも意味を持つ効果。
+    fn is_world_level(&self) -> bool {
+        matches!(
+            self,
+            Self::Report { .. } | Self::Sector { .. } | Self::CommodityShock { .. }
+        )
+    }
+
+    fn references_target(&self) -> bool {
+        match self {
+            Self::AdjustMetric { delta, .. } | Self::AdjustVariable { delta, .. } => {
+                delta.references_target()
+            }
+            _ => false,
+        }
+    }
+
+    fn sector(sector: String, shock: SectorShock) -> Result<Self> {
+        ensure!(!sector.trim().is_empty(), "sector を指定してください");
+        Ok(Self::Sector { sector, shock })
+    }
+}
+
+/// 条件式の `flag.名前` / `var.名前` から参照できる名前に限る。
+fn story_name(field: &str, name: String) -> Result<String> {
+    ensure!(
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || ch == '_'),
+        "{} '{}' は英数字と '_' のみで指定してください",
+        field,
+        name
+    );
+    Ok(name)
+}
+
+const MAX_RATING_STEPS: u32 = 9;
+
+fn positive_duration(minutes: f64) -> Result<f64> {
+    ensure!(
+        minutes.is_finite() && minutes > 0.0,
+        "duration_minutes は正の数値で指定してください"
+    );
+    Ok(minutes)
 }
 #[derive(Debug, Clone, Copy)]
 enum MetricField {
@@ -361,9 +995,47 @@ impl MetricField {
         }
     }
 }
-fn clamp_metric_delta(base: i32, delta: f64) -> i32 {
-    let candidate = (base as f64 + delta).round() as i32;
-    candidate.clamp(MIN_METRIC, MAX_METRIC)
+/// `set_allocation` で変更できる予算項目。
+#[derive(Debug, Clone, Copy)]
+enum AllocationCategory {
+    Infrastructure,
+    Military,
+    Welfare,
+    Diplomacy,
+    DebtService,
+    Administration,
+    Research,
+}
+
+impl AllocationCategory {
+    fn from_str(value: &str) -> Result<Self> {
+        match value {
+            "infrastructure" => Ok(Self::Infrastructure),
+            "military" => Ok(Self::Military),
+            "welfare" => Ok(Self::Welfare),
+            "diplomacy" => Ok(Self::Diplomacy),
+            "debt_service" => Ok(Self::DebtService),
+            "administration" => Ok(Self::Administration),
+            "research" => Ok(Self::Research),
+            other => Err(anyhow!("未知の予算項目 '{}' が指定されました", other)),
+        }
+    }
+
+    fn field_mut(self, allocations: &mut BudgetAllocation) -> &mut f64 {
+        match self {
+            Self::Infrastructure => &mut allocations.infrastructure,
+            Self::Military => &mut allocations.military,
+            Self::Welfare => &mut allocations.welfare,
+            Self::Diplomacy => &mut allocations.diplomacy,
+            Self::DebtService => &mut allocations.debt_service,
+            Self::Administration => &mut allocations.administration,
+            Self::Research => &mut allocations.research,
+        }
+    }
+}
+
+fn clamp_metric_delta(base: f64, delta: f64) -> f64 {
+    (base + delta).clamp(MIN_METRIC, MAX_METRIC)
 }
 
 /// Clamp a resource value after applying a fractional delta.
@@ -404,25 +1076,290 @@ impl CompiledEventTemplate {
     /// # Returns
     ///
     /// A vector of report messages produced by applying the effects; empty if no report effects were present.
-    pub(super) fn apply_effects(&self, country: &mut CountryState) -> Vec<ScriptedEventReport> {
-        let mut reports = Vec::new();
-        for effect in &self.effects {
-            match effect {
-                CompiledEffect::AdjustMetric { metric, delta } => {
-                    metric.apply(country, *delta);
-                }
+    pub(super) fn apply_effects(
+        &self,
+        country: &mut CountryState,
+        pool_entry: Option<usize>,
+        rng: &mut StdRng,
+    ) -> Vec<ScriptedEventReport> {
+        let placeholders = [("{country}", country.name.clone())];
+        apply_effect_list(
+            self.selected_effects(pool_entry),
+            Affected {
+                country,
+                counterpart: None,
+                is_target: false,
+            },
+            &placeholders,
+            rng,
+        )
+    }
+
+    /// 二国間テンプレートの効果を actor と target の双方に適用する。報告文では `{actor}` / `{target}`
+    /// が両国名に、`{country}` は効果を受けた側の国名に置き換わる。
+    pub(super) fn apply_pair_effects(
+        &self,
+        actor: &mut CountryState,
+        target: &mut CountryState,
+        pool_entry: Option<usize>,
+        rng: &mut StdRng,
+    ) -> Vec<ScriptedEventReport> {
+        let actor_name = actor.name.clone();
+        let target_name = target.name.clone();
+        let mut reports = apply_effect_list(
+            self.selected_effects(pool_entry),
+            Affected {
+                country: actor,
+                counterpart: Some(target),
+                is_target: false,
+            },
+            &[
+                ("{country}", actor_name.clone()),
+                ("{actor}", actor_name.clone()),
+                ("{target}", target_name.clone()),
+            ],
+            rng,
+        );
+        reports.extend(apply_effect_list(
+            self.selected_target_effects(pool_entry),
+            Affected {
+                country: target,
+                counterpart: Some(actor),
+                is_target: true,
+            },
+            &[
+                ("{country}", target_name.clone()),
+                ("{actor}", actor_name),
+                ("{target}", target_name),
+            ],
+            rng,
+        ));
+        reports
+    }
+
+    /// `targeting: global` の報告文を作る。`{count}` は条件を満たした国の数、`{countries}` はその国名の一覧。
+    pub(super) fn global_reports(
+        &self,
+        pool_entry: Option<usize>,
+        matched: &[&str],
+    ) -> Vec<ScriptedEventReport> {
+        self.selected_effects(pool_entry)
+            .filter_map(|effect| match effect {
                 CompiledEffect::Report { message } => {
                     let mut report = ScriptedEventReport::new(message.clone());
-                    report.add_placeholder("{country}", country.name.clone());
-                    reports.push(report);
+                    report.add_placeholder("{count}", matched.len().to_string());
+                    report.add_placeholder("{countries}", matched.join("、"));
+                    Some(report)
                 }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// `schedule_event` で予約する後続テンプレート (テンプレート ID, 遅延分)。
+    pub(super) fn follow_ups(
+        &self,
+        pool_entry: Option<usize>,
+    ) -> impl Iterator<Item = (&str, u64)> {
+        self.selected_effects(pool_entry).filter_map(follow_up_of)
+    }
+
+    /// target 側の効果が予約する後続テンプレート。target を起点に予約される。
+    pub(super) fn target_follow_ups(
+        &self,
+        pool_entry: Option<usize>,
+    ) -> impl Iterator<Item = (&str, u64)> {
+        self.selected_target_effects(pool_entry)
+            .filter_map(follow_up_of)
+    }
+
+    /// `pool` のすべての候補を含む後続テンプレート。参照先の検証に使う。
+    pub(super) fn all_follow_ups(&self) -> impl Iterator<Item = (&str, u64)> {
+        self.all_effects().filter_map(follow_up_of)
+    }
+
+    /// 共通の効果と、選ばれた `pool` 候補の効果。
+    fn selected_effects(&self, pool_entry: Option<usize>) -> impl Iterator<Item = &CompiledEffect> {
+        let selected = pool_entry
+            .and_then(|idx| self.pool.get(idx))
+            .map(|entry| entry.effects.as_slice())
+            .unwrap_or_default();
+        self.effects.iter().chain(selected)
+    }
+
+    fn selected_target_effects(
+        &self,
+        pool_entry: Option<usize>,
+    ) -> impl Iterator<Item = &CompiledEffect> {
+        let selected = pool_entry
+            .and_then(|idx| self.pool.get(idx))
+            .map(|entry| entry.target_effects.as_slice())
+            .unwrap_or_default();
+        self.target_effects.iter().chain(selected)
+    }
+
+    fn all_effects(&self) -> impl Iterator<Item = &CompiledEffect> {
+        self.effects.iter().chain(self.target_effects.iter()).chain(
+            self.pool
+                .iter()
+                .flat_map(|entry| entry.effects.iter().chain(entry.target_effects.iter())),
+        )
+    }
+
+    /// 産業セクターに対する効果。産業は全世界共通のため、国ごとの効果とは別に適用する。
+    pub(super) fn sector_shocks(
+        &self,
+        pool_entry: Option<usize>,
+    ) -> impl Iterator<Item = (&str, SectorShock)> {
+        self.selected_effects(pool_entry)
+            .chain(self.selected_target_effects(pool_entry))
+            .filter_map(sector_shock_of)
+    }
+
+    /// 資源市場に対する価格ショックの倍率。
+    pub(super) fn commodity_shocks(&self, pool_entry: Option<usize>) -> impl Iterator<Item = f64> {
+        self.selected_effects(pool_entry)
+            .chain(self.selected_target_effects(pool_entry))
+            .filter_map(|effect| match effect {
+                CompiledEffect::CommodityShock { multiplier } => Some(*multiplier),
+                _ => None,
+            })
+    }
+
+    pub(super) fn validate_sectors(&self, registry: &SectorRegistry) -> Result<()> {
+        for (sector, _) in self.all_effects().filter_map(sector_shock_of) {
+            registry
+                .resolve(sector)
+                .map_err(|err| anyhow!("イベント {} の対象セクター: {}", self.id, err))?;
+        }
+        Ok(())
+    }
+}
+
+/// 効果を受ける国。二国間テンプレートでは相手国と、効果を受けるのが target 側かどうかを持つ。
+struct Affected<'a> {
+    country: &'a mut CountryState,
+    counterpart: Option<&'a CountryState>,
+    is_target: bool,
+}
+
+impl Affected<'_> {
+    /// 数式の `actor.` / `target.` は効果を受ける側によらず条件式と同じ国を指す。
+    fn evaluate(&self, amount: &ValueExpression, rng: &mut StdRng) -> f64 {
+        match self.counterpart {
+            Some(actor) if self.is_target => amount.evaluate(actor, Some(self.country), rng),
+            counterpart => amount.evaluate(self.country, counterpart, rng),
+        }
+    }
+}
+
+fn apply_effect_list<'a>(
+    effects: impl Iterator<Item = &'a CompiledEffect>,
+    affected: Affected<'_>,
+    placeholders: &[(&str, String)],
+    rng: &mut StdRng,
+) -> Vec<ScriptedEventReport> {
+    let mut reports = Vec::new();
+    for effect in effects {
+        match effect {
+            CompiledEffect::AdjustMetric { metric, delta } => {
+                let delta = affected.evaluate(delta, rng);
+                metric.apply(affected.country, delta);
+            }
+            CompiledEffect::Report { message } => {
+                let mut report = ScriptedEventReport::new(message.clone());
+                for (token, value) in placeholders {
+                    report.add_placeholder(*token, value.clone());
+                }
+                reports.push(report);
+            }
+            CompiledEffect::SetFlag { flag } => affected.country.story_mut().set_flag(flag),
+            CompiledEffect::ClearFlag { flag } => affected.country.story_mut().clear_flag(flag),
+            CompiledEffect::SetVariable { variable, value } => {
+                affected.country.story_mut().set_variable(variable, *value);
+            }
+            CompiledEffect::AdjustVariable { variable, delta } => {
+                let delta = affected.evaluate(delta, rng);
+                affected
+                    .country
+                    .story_mut()
+                    .adjust_variable(variable, delta);
+            }
+            CompiledEffect::AdjustRelation { delta } => {
+                if let Some(other) = affected.counterpart {
+                    let relation = affected
+                        .country
+                        .relations
+                        .entry(other.name.clone())
+                        .or_insert(0);
+                    *relation = (*relation + delta).clamp(MIN_RELATION, MAX_RELATION);
+                }
+            }
+            CompiledEffect::CreditRating { direction, steps } => {
+                let mut rating = affected.country.fiscal.credit_rating;
+                for _ in 0..*steps {
+                    rating = match direction {
+                        RatingDirection::Upgrade => upgrade_rating(rating),
+                        RatingDirection::Downgrade => downgrade_rating(rating),
+                    };
+                }
+                affected.country.fiscal_mut().set_credit_rating(rating);
+            }
+            CompiledEffect::TemporaryTax {
+                tax,
+                delta,
+                duration_minutes,
+            } => {
+                affected.country.tax_policy_mut().apply_temporary_change(
+                    *tax,
+                    *delta,
+                    *duration_minutes,
+                );
+            }
+            CompiledEffect::SetAllocation { category, percent } => {
+                let mut allocations = affected.country.allocations();
+                *category.field_mut(&mut allocations) = *percent;
+                affected.country.set_allocations(allocations);
+            }
+            CompiledEffect::IssueDebt { amount } => {
+                let cash = affected.country.fiscal.cash_reserve() + amount;
+                let fiscal = affected.country.fiscal_mut();
+                if fiscal.borrow(*amount) {
+                    fiscal.set_cash_reserve(cash);
+                }
+            }
+            CompiledEffect::ForgiveDebt { share } => {
+                let amount = affected.country.fiscal.debt() * share;
+                affected.country.fiscal_mut().write_down_debt(amount);
             }
+            CompiledEffect::Sector { .. }
+            | CompiledEffect::ScheduleEvent { .. }
+            | CompiledEffect::CommodityShock { .. } => {}
         }
-        reports
+    }
+    reports
+}
+
+fn follow_up_of(effect: &CompiledEffect) -> Option<(&str, u64)> {
+    match effect {
+        CompiledEffect::ScheduleEvent {
+            event,
+            delay_minutes,
+        } => Some((event.as_str(), *delay_minutes)),
+        _ => None,
+    }
+}
+
+fn sector_shock_of(effect: &CompiledEffect) -> Option<(&str, SectorShock)> {
+    match effect {
+        CompiledEffect::Sector { sector, shock } => Some((sector.as_str(), *shock)),
+        _ => None,
     }
 }
 #[cfg(test)]
 mod tests {
+    use rand::SeedableRng;
+
     use super::super::formatter::format_reports;
     use super::*;
     use crate::game::country::{BudgetAllocation, CountryState};
@@ -435,9 +1372,9 @@ mod tests {
             "Republic".to_string(),
             10.0,
             500.0,
-            50,
-            40,
-            45,
+            50.0,
+            40.0,
+            45.0,
             60,
             FiscalAccount::new(200.0, CreditRating::A),
             TaxPolicy::default(),
@@ -453,9 +1390,18 @@ mod tests {
             description: "desc".to_string(),
             condition: "approval > 0".to_string(),
             check_minutes: 0,
+            schedule: None,
             initial_delay_minutes: 0,
             cooldown_minutes: 60,
+            auto_trigger: true,
+            probability: 1.0,
+            scope: EventScope::Country,
+            targeting: TargetingRaw::Each,
+            rank_by: None,
+            target_count: 1,
             effects: Vec::new(),
+            target_effects: Vec::new(),
+            pool: Vec::new(),
         };
         let err = compile_template(3, raw).expect_err("check_minutes == 0 should be rejected");
         assert!(err.to_string().contains("check_minutes"));
@@ -469,12 +1415,21 @@ mod tests {
             description: "desc".to_string(),
             condition: "approval >= 40".to_string(),
             check_minutes: 60,
+            schedule: None,
             initial_delay_minutes: 5,
             cooldown_minutes: 120,
+            auto_trigger: true,
+            probability: 1.0,
+            scope: EventScope::Country,
+            targeting: TargetingRaw::Each,
+            rank_by: None,
+            target_count: 1,
+            target_effects: Vec::new(),
+            pool: Vec::new(),
             effects: vec![
                 EventEffectRaw::AdjustMetric {
                     metric: "approval".to_string(),
-                    delta: 10.0,
+                    delta: AmountRaw::Fixed(10.0),
                 },
                 EventEffectRaw::Report {
                     message: "{country} improved approval".to_string(),
@@ -482,21 +1437,315 @@ mod tests {
             ],
         };
         let template = compile_template(0, raw).expect("valid template should compile");
-        assert_eq!(template.check_minutes(), 60);
+        assert_eq!(template.schedule(), ScheduleSpec::EveryMinutes(60));
         assert_eq!(template.initial_delay_minutes(), 5);
         assert_eq!(template.id(), "approval_push");
 
         let mut country = sample_country();
         assert!(template.can_trigger(&country, None, 300.0));
-        let reports = template.apply_effects(&mut country);
+        let mut rng = StdRng::seed_from_u64(0);
+        let reports = template.apply_effects(&mut country, None, &mut rng);
         assert_eq!(reports.len(), 1);
         let formatted = format_reports(&reports);
         assert_eq!(formatted[0], "Testland improved approval");
-        assert_eq!(country.approval, 55);
+        assert_eq!(country.approval, 55.0);
 
         assert!(
             !template.can_trigger(&country, Some(300.0), 360.0),
             "cooldown should prevent immediate re-trigger"
         );
     }
+
+    #[test]
+    fn sector_effects_compile_and_resolve_through_registry() {
+        let raw: EventTemplateRaw = serde_yaml::from_str(
+            r#"
+id: port_strike
+name: 港湾スト
+description: desc
+condition: "stability < 40"
+effects:
+  - type: shift_sector_price
+    sector: tertiary:logistics
+    shift: 0.25
+    duration_minutes: 600
+  - type: scale_sector
+    sector: automotive
+    target: potential_demand
+    factor: 0.8
+  - type: destroy_inventory
+    sector: primary:horticulture
+    share: 0.5
+"#,
+        )
+        .expect("yaml");
+        let template = compile_template(0, raw).expect("compile");
+        let shocks: Vec<_> = template.sector_shocks(None).collect();
+        assert_eq!(shocks.len(), 3);
+        assert!(matches!(
+            shocks[0],
+            ("tertiary:logistics", SectorShock::Modifier(modifier))
+                if modifier.price_shift == 0.25 && modifier.remaining_minutes == 600.0
+        ));
+
+        let catalog = crate::game::economy::IndustryCatalog::from_embedded().expect("catalog");
+        let registry = SectorRegistry::from_catalog(&catalog);
+        template
+            .validate_sectors(&registry)
+            .expect("sectors resolve");
+
+        let unknown: EventTemplateRaw = serde_json::from_str(
+            r#"{
+                "id": "x", "name": "x", "description": "d", "condition": "approval > 0",
+                "effects": [{ "type": "destroy_inventory", "sector": "energy:fusion", "share": 0.1 }]
+            }"#,
+        )
+        .expect("json");
+        let compiled = compile_template(1, unknown).expect("compile");
+        assert!(compiled.validate_sectors(&registry).is_err());
+
+        let invalid: EventTemplateRaw = serde_json::from_str(
+            r#"{
+                "id": "y", "name": "y", "description": "d", "condition": "approval > 0",
+                "effects": [{ "type": "sector_modifier", "sector": "energy:diesel", "duration_minutes": 0 }]
+            }"#,
+        )
+        .expect("json");
+        assert!(compile_template(2, invalid).is_err());
+    }
+
+    #[test]
+    fn story_effects_update_flags_variables_and_list_follow_ups() {
+        let raw: EventTemplateRaw = serde_yaml::from_str(
+            r#"
+id: protest
+name: 抗議
+description: desc
+condition: "stability < 40"
+effects:
+  - type: set_flag
+    flag: protest
+  - type: clear_flag
+    flag: calm
+  - type: adjust_variable
+    variable: unrest
+    delta: 1.5
+  - type: schedule_event
+    event: general_strike
+    delay_minutes: 1440
+"#,
+        )
+        .expect("yaml");
+        let template = compile_template(0, raw).expect("compile");
+        assert!(template.auto_trigger());
+        assert_eq!(
+            template.follow_ups(None).collect::<Vec<_>>(),
+            vec![("general_strike", 1440)]
+        );
+
+        let mut country = sample_country();
+        country.story_mut().set_flag("calm");
+        let mut rng = StdRng::seed_from_u64(0);
+        template.apply_effects(&mut country, None, &mut rng);
+        template.apply_effects(&mut country, None, &mut rng);
+        assert!(country.story().has_flag("protest"));
+        assert!(!country.story().has_flag("calm"));
+        assert_eq!(country.story().variable("unrest"), 3.0);
+
+        let invalid: EventTemplateRaw = serde_json::from_str(
+            r#"{
+                "id": "z", "name": "z", "description": "d", "condition": "approval > 0",
+                "effects": [{ "type": "set_flag", "flag": "bad.name" }]
+            }"#,
+        )
+        .expect("json");
+        assert!(compile_template(3, invalid).is_err());
+    }
+
+    #[test]
+    fn diplomacy_and_fiscal_effects_apply_and_validate() {
+        let raw: EventTemplateRaw = serde_yaml::from_str(
+            r#"
+id: sanctions
+name: 制裁
+description: desc
+scope: bilateral
+condition: "relation < 0"
+effects:
+  - type: adjust_relation
+    delta: -20
+  - type: change_credit_rating
+    direction: upgrade
+  - type: issue_debt
+    amount: 50.0
+  - type: commodity_shock
+    multiplier: 1.2
+target_effects:
+  - type: adjust_relation
+    delta: -30
+  - type: change_credit_rating
+    direction: downgrade
+    steps: 2
+  - type: temporary_tax
+    tax: corporate
+    delta: 0.05
+    duration_minutes: 600
+  - type: set_allocation
+    category: military
+    percent: 12.5
+  - type: forgive_debt
+    share: 0.5
+"#,
+        )
+        .expect("yaml");
+        let template = compile_template(0, raw).expect("compile");
+        assert_eq!(
+            template.commodity_shocks(None).collect::<Vec<_>>(),
+            vec![1.2]
+        );
+
+        let mut actor = sample_country();
+        let mut target = sample_country();
+        target.name = "Otherland".to_string();
+        actor.relations.insert("Otherland".to_string(), -10);
+        target.relations.insert("Testland".to_string(), -80);
+        target.fiscal_mut().add_debt(100.0);
+        let actor_cash = actor.fiscal.cash_reserve();
+        let actor_debt = actor.fiscal.debt();
+        let target_debt = target.fiscal.debt();
+
+        let mut rng = StdRng::seed_from_u64(0);
+        template.apply_pair_effects(&mut actor, &mut target, None, &mut rng);
+        assert_eq!(actor.relations["Otherland"], -30);
+        assert_eq!(target.relations["Testland"], -100);
+        assert_eq!(actor.fiscal.credit_rating, CreditRating::AA);
+        assert_eq!(target.fiscal.credit_rating, CreditRating::BB);
+        assert!((actor.fiscal.cash_reserve() - actor_cash - 50.0).abs() < 1e-9);
+        assert!((actor.fiscal.debt() - actor_debt - 50.0).abs() < 1e-9);
+        assert!((target.fiscal.debt() - target_debt * 0.5).abs() < 1e-9);
+        assert!((target.tax_policy.effective_rate(TaxRateKind::Corporate) - 0.27).abs() < 1e-9);
+        assert_eq!(target.tax_policy().temporary_changes().len(), 1);
+        assert_eq!(target.allocations().military, 12.5);
+
+        // 市場へのアクセスを失った国は発行できず、資金も増えない。
+        actor.fiscal_mut().set_market_access(false);
+        let (cash, debt) = (actor.fiscal.cash_reserve(), actor.fiscal.debt());
+        template.apply_pair_effects(&mut actor, &mut target, None, &mut rng);
+        assert_eq!(actor.fiscal.cash_reserve(), cash);
+        assert_eq!(actor.fiscal.debt(), debt);
+
+        for (label, effects) in [
+            (
+                "country scope",
+                r#"[{ "type": "adjust_relation", "delta": 5 }]"#,
+            ),
+            (
+                "steps",
+                r#"[{ "type": "change_credit_rating", "direction": "downgrade", "steps": 0 }]"#,
+            ),
+            (
+                "tax kind",
+                r#"[{ "type": "temporary_tax", "tax": "tariff", "delta": 0.1, "duration_minutes": 60 }]"#,
+            ),
+            (
+                "duration",
+                r#"[{ "type": "temporary_tax", "tax": "income", "delta": 0.1, "duration_minutes": 0 }]"#,
+            ),
+            (
+                "category",
+                r#"[{ "type": "set_allocation", "category": "space", "percent": 5 }]"#,
+            ),
+            ("amount", r#"[{ "type": "issue_debt", "amount": -10 }]"#),
+            ("share", r#"[{ "type": "forgive_debt", "share": 1.5 }]"#),
+            (
+                "multiplier",
+                r#"[{ "type": "commodity_shock", "multiplier": 0 }]"#,
+            ),
+        ] {
+            let json = format!(
+                r#"{{ "id": "bad", "name": "bad", "description": "d", "condition": "approval > 0", "effects": {} }}"#,
+                effects
+            );
+            let rejected = serde_json::from_str::<EventTemplateRaw>(&json)
+                .map_err(anyhow::Error::from)
+                .and_then(|raw| compile_template(0, raw));
+            assert!(rejected.is_err(), "{} should be rejected", label);
+        }
+    }
+
+    #[test]
+    fn formula_deltas_are_evaluated_per_country() {
+        let raw: EventTemplateRaw = serde_yaml::from_str(
+            r#"
+id: crisis
+name: 危機
+description: desc
+condition: "true"
+effects:
+  - type: adjust_metric
+    metric: debt
+    delta: "0.1 * gdp"
+  - type: adjust_metric
+    metric: approval
+    delta: "uniform(-8, -4)"
+  - type: adjust_variable
+    variable: pressure
+    delta: "debt / max(gdp, 1) * 10"
+"#,
+        )
+        .expect("yaml");
+        let template = compile_template(0, raw).expect("compile");
+        let mut rng = StdRng::seed_from_u64(3);
+        for gdp in [500.0, 5_000.0] {
+            let mut country = sample_country();
+            country.gdp = gdp;
+            let debt = country.fiscal.debt();
+            template.apply_effects(&mut country, None, &mut rng);
+            assert!((country.fiscal.debt() - debt - 0.1 * gdp).abs() < 1e-6);
+            assert!((37.0..=41.0).contains(&country.approval));
+            let expected = country.fiscal.debt() / gdp * 10.0;
+            assert!((country.story().variable("pressure") - expected).abs() < 1e-9);
+        }
+
+        let pair: EventTemplateRaw = serde_yaml::from_str(
+            r#"
+id: tribute
+name: 貢納
+description: desc
+scope: bilateral
+condition: "true"
+effects:
+  - type: adjust_metric
+    metric: gdp
+    delta: "0.1 * target.gdp"
+target_effects:
+  - type: adjust_metric
+    metric: gdp
+    delta: "-0.1 * target.gdp"
+"#,
+        )
+        .expect("yaml");
+        let template = compile_template(1, pair).expect("compile");
+        let mut actor = sample_country();
+        let mut target = sample_country();
+        target.name = "Otherland".to_string();
+        target.gdp = 2_000.0;
+        template.apply_pair_effects(&mut actor, &mut target, None, &mut rng);
+        assert!((actor.gdp - 700.0).abs() < 1e-9);
+        assert!((target.gdp - 1_800.0).abs() < 1e-9);
+
+        for delta in ["\"0.1 * target.gdp\"", "\"gdp *\"", "\"uniform(3, 1)\""] {
+            let json = format!(
+                r#"{{ "id": "bad", "name": "bad", "description": "d", "condition": "true",
+                     "effects": [{{ "type": "adjust_metric", "metric": "gdp", "delta": {} }}] }}"#,
+                delta
+            );
+            let raw: EventTemplateRaw = serde_json::from_str(&json).expect("json");
+            assert!(
+                compile_template(0, raw).is_err(),
+                "{} should be rejected",
+                delta
+            );
+        }
+    }
 }
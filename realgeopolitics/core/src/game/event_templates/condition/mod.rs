@@ -1,9 +1,14 @@
 mod parser;
+mod value;
 
 use crate::game::country::CountryState;
 
 pub(crate) trait ConditionEvaluator {
     fn evaluate(&self, country: &CountryState) -> bool;
+    /// 二国間テンプレート用。`target.` 接頭辞や `relation` などの組指標を評価できる。
+    fn evaluate_pair(&self, actor: &CountryState, target: &CountryState) -> bool;
+    fn references_target(&self) -> bool;
 }
 
-pub(crate) use parser::parse_condition;
+pub(crate) use parser::{matches_country_name, parse_condition, parse_world_condition};
+pub(crate) use value::{ValueExpression, parse_value};
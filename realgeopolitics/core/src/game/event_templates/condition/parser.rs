@@ -1,7 +1,8 @@
-use anyhow::{Result, anyhow};
+use anyhow::{Result, anyhow, ensure};
 
 use crate::game::country::CountryState;
 use crate::game::economy::CreditRating;
+use crate::game::{MAX_RELATION, MIN_RELATION};
 
 use super::ConditionEvaluator;
 
@@ -10,11 +11,48 @@ pub(crate) fn parse_condition(text: &str) -> Result<Box<dyn ConditionEvaluator>>
     Ok(Box::new(expr))
 }
 
+/// 国名付きのメトリクス (`Asteria.debt_ratio > 100`) で世界全体の状態を判定する条件式を解析する。
+/// 国名の空白は `_` で書く。`actor.` / `target.` や組指標は使えない。
+pub(crate) fn parse_world_condition(text: &str) -> Result<WorldCondition> {
+    let tokens = tokenize(text)?;
+    let mut parser = Parser::new(tokens);
+    parser.world = true;
+    let expr = parser.parse_expression()?;
+    parser.expect_end()?;
+    Ok(WorldCondition { expr })
+}
+
+/// `parse_world_condition` で解析した条件式。
+#[derive(Debug, Clone)]
+pub(crate) struct WorldCondition {
+    expr: ConditionExpr,
+}
+
+impl WorldCondition {
+    /// 条件式が参照する国名 (書かれたままの表記)。
+    pub(crate) fn country_names(&self) -> Vec<&str> {
+        let mut names = Vec::new();
+        self.expr.collect_names(&mut names);
+        names
+    }
+
+    pub(crate) fn evaluate(&self, countries: &[CountryState]) -> bool {
+        self.expr.eval_world(countries)
+    }
+}
+
+/// 条件式中の国名が `name` の国を指すか。空白を `_` に置き換え、大文字小文字は区別しない。
+pub(crate) fn matches_country_name(name: &str, written: &str) -> bool {
+    name.replace(' ', "_").eq_ignore_ascii_case(written)
+}
+
 #[derive(Debug, Clone)]
 enum ConditionExpr {
     And(Box<ConditionExpr>, Box<ConditionExpr>),
     Or(Box<ConditionExpr>, Box<ConditionExpr>),
     Comparison(Comparison),
+    /// `true` / `false`。常に発生させるテンプレートに使う。
+    Constant(bool),
 }
 
 impl ConditionExpr {
@@ -25,28 +63,101 @@ impl ConditionExpr {
         parser.expect_end()?;
         Ok(expr)
     }
+
+    fn eval(&self, subjects: &Subjects<'_>) -> bool {
+        match self {
+            ConditionExpr::And(lhs, rhs) => lhs.eval(subjects) && rhs.eval(subjects),
+            ConditionExpr::Or(lhs, rhs) => lhs.eval(subjects) || rhs.eval(subjects),
+            ConditionExpr::Comparison(comparison) => comparison.evaluate(subjects),
+            ConditionExpr::Constant(value) => *value,
+        }
+    }
+
+    fn eval_world(&self, countries: &[CountryState]) -> bool {
+        match self {
+            ConditionExpr::And(lhs, rhs) => lhs.eval_world(countries) && rhs.eval_world(countries),
+            ConditionExpr::Or(lhs, rhs) => lhs.eval_world(countries) || rhs.eval_world(countries),
+            ConditionExpr::Comparison(comparison) => comparison.evaluate_world(countries),
+            ConditionExpr::Constant(value) => *value,
+        }
+    }
+
+    fn collect_names<'a>(&'a self, names: &mut Vec<&'a str>) {
+        match self {
+            ConditionExpr::And(lhs, rhs) | ConditionExpr::Or(lhs, rhs) => {
+                lhs.collect_names(names);
+                rhs.collect_names(names);
+            }
+            ConditionExpr::Comparison(Comparison {
+                operand: Operand::Named(name, _),
+                ..
+            }) => {
+                if !names.iter().any(|known| known.eq_ignore_ascii_case(name)) {
+                    names.push(name);
+                }
+            }
+            ConditionExpr::Comparison(_) | ConditionExpr::Constant(_) => {}
+        }
+    }
 }
 
 impl ConditionEvaluator for ConditionExpr {
     fn evaluate(&self, country: &CountryState) -> bool {
+        self.eval(&Subjects {
+            actor: country,
+            target: None,
+        })
+    }
+
+    fn evaluate_pair(&self, actor: &CountryState, target: &CountryState) -> bool {
+        self.eval(&Subjects {
+            actor,
+            target: Some(target),
+        })
+    }
+
+    fn references_target(&self) -> bool {
         match self {
-            ConditionExpr::And(lhs, rhs) => lhs.evaluate(country) && rhs.evaluate(country),
-            ConditionExpr::Or(lhs, rhs) => lhs.evaluate(country) || rhs.evaluate(country),
-            ConditionExpr::Comparison(comparison) => comparison.evaluate(country),
+            ConditionExpr::And(lhs, rhs) | ConditionExpr::Or(lhs, rhs) => {
+                lhs.references_target() || rhs.references_target()
+            }
+            ConditionExpr::Comparison(comparison) => comparison.operand.references_target(),
+            ConditionExpr::Constant(_) => false,
         }
     }
 }
 
+/// 条件式の評価対象。単独の国を対象とするテンプレートでは `target` は `None`。
+pub(super) struct Subjects<'a> {
+    pub(super) actor: &'a CountryState,
+    pub(super) target: Option<&'a CountryState>,
+}
+
 #[derive(Debug, Clone)]
 struct Comparison {
-    metric: MetricKey,
+    operand: Operand,
     op: CompareOp,
     value: f64,
 }
 
 impl Comparison {
-    fn evaluate(&self, country: &CountryState) -> bool {
-        let left = self.metric.value(country);
+    fn evaluate(&self, subjects: &Subjects<'_>) -> bool {
+        self.operand
+            .value(subjects)
+            .is_some_and(|left| self.compare(left))
+    }
+
+    fn evaluate_world(&self, countries: &[CountryState]) -> bool {
+        let Operand::Named(name, metric) = &self.operand else {
+            return false;
+        };
+        countries
+            .iter()
+            .find(|country| matches_country_name(&country.name, name))
+            .is_some_and(|country| self.compare(metric.value(country)))
+    }
+
+    fn compare(&self, left: f64) -> bool {
         match self.op {
             CompareOp::Lt => left < self.value,
             CompareOp::Le => left <= self.value,
@@ -68,8 +179,122 @@ enum CompareOp {
     Ne,
 }
 
+/// 比較の左辺。接頭辞なしのメトリクスは `actor` (単独テンプレートでは対象国) を指す。
+#[derive(Debug, Clone)]
+pub(super) enum Operand {
+    Country(Subject, MetricKey),
+    Pair(PairMetric),
+    /// 国名で指定した国の指標。`parse_world_condition` でのみ使える。
+    Named(String, MetricKey),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Subject {
+    Actor,
+    Target,
+}
+
+impl Operand {
+    pub(super) fn parse(ident: &str) -> Result<Self> {
+        if let Some((head, rest)) = ident.split_once('.') {
+            match head.to_ascii_lowercase().as_str() {
+                "actor" => return Ok(Self::Country(Subject::Actor, MetricKey::from_str(rest)?)),
+                "target" => return Ok(Self::Country(Subject::Target, MetricKey::from_str(rest)?)),
+                _ => {}
+            }
+        }
+        if let Some(pair) = PairMetric::from_str(ident) {
+            return Ok(Self::Pair(pair));
+        }
+        Ok(Self::Country(Subject::Actor, MetricKey::from_str(ident)?))
+    }
+
+    fn parse_named(ident: &str) -> Result<Self> {
+        let Some((head, rest)) = ident.split_once('.') else {
+            return Err(anyhow!(
+                "'{}' には国名を付けてください (例: Asteria.debt_ratio)",
+                ident
+            ));
+        };
+        ensure!(
+            !matches!(
+                head.to_ascii_lowercase().as_str(),
+                "actor" | "target" | "flag" | "var"
+            ),
+            "'{}' には国名を付けてください (例: Asteria.debt_ratio)",
+            ident
+        );
+        Ok(Self::Named(head.to_string(), MetricKey::from_str(rest)?))
+    }
+
+    pub(super) fn references_target(&self) -> bool {
+        matches!(
+            self,
+            Operand::Country(Subject::Target, _) | Operand::Pair(_)
+        )
+    }
+
+    fn is_flag(&self) -> bool {
+        matches!(
+            self,
+            Operand::Country(_, MetricKey::Flag(_)) | Operand::Named(_, MetricKey::Flag(_))
+        )
+    }
+
+    pub(super) fn value(&self, subjects: &Subjects<'_>) -> Option<f64> {
+        match self {
+            Operand::Country(Subject::Actor, metric) => Some(metric.value(subjects.actor)),
+            Operand::Country(Subject::Target, metric) => {
+                subjects.target.map(|target| metric.value(target))
+            }
+            Operand::Pair(pair) => subjects
+                .target
+                .map(|target| pair.value(subjects.actor, target)),
+            Operand::Named(..) => None,
+        }
+    }
+}
+
+/// actor と target の組で決まる指標。
 #[derive(Debug, Clone, Copy)]
-enum MetricKey {
+pub(super) enum PairMetric {
+    /// actor から見た target との関係値 (-100〜100)。
+    Relation,
+    /// actor の軍事力 ÷ target の軍事力。
+    MilitaryRatio,
+    /// 両国の GDP の幾何平均に関係値 (0〜1 に換算) を掛けた二国間貿易量の推計。
+    TradeVolume,
+}
+
+impl PairMetric {
+    fn from_str(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "relation" => Some(Self::Relation),
+            "military_ratio" => Some(Self::MilitaryRatio),
+            "trade_volume" => Some(Self::TradeVolume),
+            _ => None,
+        }
+    }
+
+    fn value(&self, actor: &CountryState, target: &CountryState) -> f64 {
+        match self {
+            PairMetric::Relation => relation_between(actor, target),
+            PairMetric::MilitaryRatio => actor.military / target.military.max(1.0),
+            PairMetric::TradeVolume => {
+                let affinity = (relation_between(actor, target) - MIN_RELATION as f64)
+                    / (MAX_RELATION - MIN_RELATION) as f64;
+                (actor.gdp.max(0.0) * target.gdp.max(0.0)).sqrt() * affinity
+            }
+        }
+    }
+}
+
+fn relation_between(actor: &CountryState, target: &CountryState) -> f64 {
+    actor.relations.get(&target.name).copied().unwrap_or(0) as f64
+}
+
+#[derive(Debug, Clone)]
+pub(super) enum MetricKey {
     Stability,
     Approval,
     Military,
@@ -80,10 +305,31 @@ enum MetricKey {
     DebtRatio,
     InterestRate,
     CreditRatingTier,
+    Cpi,
+    Inflation,
+    Emissions,
+    Pollution,
+    CarbonPrice,
+    /// `flag.名前`: フラグが立っていれば 1、なければ 0。
+    Flag(String),
+    /// `var.名前`: 国ごとの変数。未設定は 0。
+    Variable(String),
 }
 
 impl MetricKey {
     fn from_str(value: &str) -> Result<Self> {
+        if let Some((prefix, name)) = value.split_once('.') {
+            ensure!(
+                !name.is_empty() && !name.contains('.'),
+                "'{}' の名前が不正です",
+                value
+            );
+            return match prefix.to_ascii_lowercase().as_str() {
+                "flag" => Ok(Self::Flag(name.to_string())),
+                "var" => Ok(Self::Variable(name.to_string())),
+                other => Err(anyhow!("未知の条件メトリクス '{}' が指定されました", other)),
+            };
+        }
         match value.to_ascii_lowercase().as_str() {
             "stability" => Ok(Self::Stability),
             "approval" => Ok(Self::Approval),
@@ -95,28 +341,46 @@ impl MetricKey {
             "debt_ratio" => Ok(Self::DebtRatio),
             "interest_rate" => Ok(Self::InterestRate),
             "credit_rating_tier" => Ok(Self::CreditRatingTier),
+            "cpi" => Ok(Self::Cpi),
+            "inflation" => Ok(Self::Inflation),
+            "emissions" => Ok(Self::Emissions),
+            "pollution" => Ok(Self::Pollution),
+            "carbon_price" => Ok(Self::CarbonPrice),
             other => Err(anyhow!("未知の条件メトリクス '{}' が指定されました", other)),
         }
     }
 
     fn value(&self, country: &CountryState) -> f64 {
         match self {
-            MetricKey::Stability => country.stability as f64,
-            MetricKey::Approval => country.approval as f64,
-            MetricKey::Military => country.military as f64,
+            MetricKey::Stability => country.stability,
+            MetricKey::Approval => country.approval,
+            MetricKey::Military => country.military,
             MetricKey::Resources => country.resources as f64,
             MetricKey::Gdp => country.gdp.max(0.0),
-            MetricKey::Debt => country.fiscal.debt.max(0.0),
+            MetricKey::Debt => country.fiscal.debt().max(0.0),
             MetricKey::CashReserve => country.fiscal.cash_reserve().max(0.0),
             MetricKey::DebtRatio => compute_debt_ratio(country),
             MetricKey::InterestRate => country.fiscal.interest_rate.max(0.0),
             MetricKey::CreditRatingTier => credit_rating_tier(country.fiscal.credit_rating),
+            MetricKey::Cpi => country.consumer_prices().cpi(),
+            MetricKey::Inflation => country.consumer_prices().inflation(),
+            MetricKey::Emissions => country.environment().emissions_per_day(),
+            MetricKey::Pollution => country.environment().pollution(),
+            MetricKey::CarbonPrice => country.environment().carbon_price(),
+            MetricKey::Flag(flag) => {
+                if country.story().has_flag(flag) {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            MetricKey::Variable(name) => country.story().variable(name),
         }
     }
 }
 
 fn compute_debt_ratio(country: &CountryState) -> f64 {
-    let debt = country.fiscal.debt.max(0.0);
+    let debt = country.fiscal.debt().max(0.0);
     let gdp = country.gdp.max(0.0);
     if gdp <= f64::EPSILON {
         if debt <= f64::EPSILON {
@@ -193,7 +457,7 @@ fn tokenize(input: &str) -> Result<Vec<Token>> {
                 let op = read_operator(&mut chars)?;
                 tokens.push(Token::Operator(op));
             }
-            '0'..='9' | '.' => {
+            '0'..='9' | '.' | '-' | '+' => {
                 let number = read_number(&mut chars)?;
                 tokens.push(Token::Number(number));
             }
@@ -274,7 +538,7 @@ fn read_number(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> Result<f
     }
 }
 
-fn read_ident(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> String {
+pub(super) fn read_ident(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> String {
     let mut ident = String::new();
     while let Some(&ch) = chars.peek() {
         if is_ident_part(ch) {
@@ -287,22 +551,28 @@ fn read_ident(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> String {
     ident
 }
 
-const fn is_ident_start(ch: char) -> bool {
+pub(super) const fn is_ident_start(ch: char) -> bool {
     ch.is_ascii_alphabetic() || ch == '_'
 }
 
 const fn is_ident_part(ch: char) -> bool {
-    ch.is_ascii_alphanumeric() || ch == '_'
+    ch.is_ascii_alphanumeric() || ch == '_' || ch == '.'
 }
 
 struct Parser {
     tokens: Vec<Token>,
     pos: usize,
+    /// 国名付きのメトリクスだけを受け付ける (`parse_world_condition`)。
+    world: bool,
 }
 
 impl Parser {
     fn new(tokens: Vec<Token>) -> Self {
-        Self { tokens, pos: 0 }
+        Self {
+            tokens,
+            pos: 0,
+            world: false,
+        }
     }
 
     fn parse_expression(&mut self) -> Result<ConditionExpr> {
@@ -343,10 +613,27 @@ impl Parser {
 
     fn parse_comparison(&mut self) -> Result<ConditionExpr> {
         let ident = self.expect_ident()?;
-        let metric = MetricKey::from_str(&ident)?;
+        match ident.to_ascii_lowercase().as_str() {
+            "true" => return Ok(ConditionExpr::Constant(true)),
+            "false" => return Ok(ConditionExpr::Constant(false)),
+            _ => {}
+        }
+        let operand = if self.world {
+            Operand::parse_named(&ident)?
+        } else {
+            Operand::parse(&ident)?
+        };
+        if operand.is_flag() && !matches!(self.peek(), Some(Token::Operator(_))) {
+            // 比較を省略した `flag.名前` はフラグが立っていることを表す。
+            return Ok(ConditionExpr::Comparison(Comparison {
+                operand,
+                op: CompareOp::Ge,
+                value: 1.0,
+            }));
+        }
         let op = self.expect_operator()?;
         let value = self.expect_number()?;
-        Ok(ConditionExpr::Comparison(Comparison { metric, op, value }))
+        Ok(ConditionExpr::Comparison(Comparison { operand, op, value }))
     }
 
     fn expect_end(&self) -> Result<()> {
@@ -440,9 +727,9 @@ pub(crate) mod tests {
             "Republic".to_string(),
             12.0,
             600.0,
-            55,
-            45,
-            48,
+            55.0,
+            45.0,
+            48.0,
             70,
             FiscalAccount::new(300.0, CreditRating::A),
             TaxPolicy::default(),
@@ -456,12 +743,70 @@ pub(crate) mod tests {
             .expect("condition should parse");
         let mut country = sample_country();
         assert!(evaluator.evaluate(&country));
-        country.approval = 40;
+        country.approval = 40.0;
         assert!(evaluator.evaluate(&country));
-        country.stability = 40;
+        country.stability = 40.0;
         assert!(!evaluator.evaluate(&country));
     }
 
+    #[test]
+    fn parse_condition_reads_price_metrics() {
+        let evaluator =
+            parse_condition("cpi >= 100 && inflation < 1").expect("condition should parse");
+        let mut country = sample_country();
+        assert!(evaluator.evaluate(&country));
+        let prices = crate::game::economy::HouseholdPrices {
+            overall: 1.3,
+            energy: 1.0,
+        };
+        let day = crate::game::constants::MINUTES_PER_DAY as f64;
+        country
+            .consumer_prices_mut()
+            .observe(prices, 0.08, day, 0.0, 1.0);
+        assert!(evaluator.evaluate(&country));
+        country
+            .consumer_prices_mut()
+            .observe(prices, 0.2, day, 2.0 * day, 1.0);
+        assert!(!evaluator.evaluate(&country));
+    }
+
+    #[test]
+    fn parse_condition_reads_environment_metrics() {
+        let evaluator = parse_condition("pollution > 60 && emissions > 0 && carbon_price == 0")
+            .expect("condition should parse");
+        let mut country = sample_country();
+        assert!(!evaluator.evaluate(&country));
+        let day = crate::game::constants::MINUTES_PER_DAY as f64;
+        for _ in 0..30 {
+            country.environment_mut().absorb(6_000.0, day, 600.0);
+        }
+        assert!(evaluator.evaluate(&country));
+    }
+
+    #[test]
+    fn parse_condition_reads_story_flags_and_variables() {
+        let evaluator = parse_condition("flag.protest && var.unrest >= 2 && flag.strike == 0")
+            .expect("condition should parse");
+        let mut country = sample_country();
+        assert!(!evaluator.evaluate(&country));
+        country.story_mut().set_flag("protest");
+        country.story_mut().adjust_variable("unrest", 1.0);
+        assert!(!evaluator.evaluate(&country));
+        country.story_mut().adjust_variable("unrest", 1.5);
+        assert!(evaluator.evaluate(&country));
+        country.story_mut().set_flag("strike");
+        assert!(!evaluator.evaluate(&country));
+
+        assert!(parse_condition("var.unrest").is_err());
+        assert!(parse_condition("true").unwrap().evaluate(&country));
+        assert!(
+            !parse_condition("false || flag.calm")
+                .unwrap()
+                .evaluate(&country)
+        );
+        assert!(parse_condition("flags.protest").is_err());
+    }
+
     #[test]
     fn parse_condition_rejects_unknown_metric() {
         match parse_condition("unknown_metric > 0") {
@@ -471,4 +816,66 @@ pub(crate) mod tests {
             }
         }
     }
+
+    #[test]
+    fn parse_condition_reads_pair_metrics() {
+        let evaluator = parse_condition(
+            "relation < -20 && military_ratio > 1.5 && target.stability < 50 && actor.approval >= 45",
+        )
+        .expect("condition should parse");
+        assert!(evaluator.references_target());
+        assert!(
+            !parse_condition("actor.approval > 0")
+                .unwrap()
+                .references_target()
+        );
+
+        let mut actor = sample_country();
+        let mut target = sample_country();
+        target.name = "Rivalia".to_string();
+        target.military = 20.0;
+        target.stability = 40.0;
+        actor.relations.insert("Rivalia".to_string(), -40);
+        assert!(evaluator.evaluate_pair(&actor, &target));
+        assert!(
+            !evaluator.evaluate(&actor),
+            "target なしでは target 指標は偽"
+        );
+        actor.relations.insert("Rivalia".to_string(), 10);
+        assert!(!evaluator.evaluate_pair(&actor, &target));
+
+        let trade = parse_condition("trade_volume > 300").unwrap();
+        actor.relations.insert("Rivalia".to_string(), 100);
+        assert!(trade.evaluate_pair(&actor, &target));
+        actor.relations.insert("Rivalia".to_string(), -100);
+        assert!(!trade.evaluate_pair(&actor, &target));
+    }
+
+    #[test]
+    fn parse_world_condition_reads_named_country_metrics() {
+        let condition = parse_world_condition(
+            "Evalia.stability > 50 && (north_Evalia.approval < 30 || NORTH_EVALIA.flag.protest)",
+        )
+        .expect("condition should parse");
+        assert_eq!(condition.country_names(), vec!["Evalia", "north_Evalia"]);
+
+        let evalia = sample_country();
+        let mut north = sample_country();
+        north.name = "North Evalia".to_string();
+        let mut countries = vec![evalia, north];
+        assert!(!condition.evaluate(&countries));
+        countries[1].story_mut().set_flag("protest");
+        assert!(condition.evaluate(&countries));
+        assert!(
+            !condition.evaluate(&countries[1..]),
+            "存在しない国の指標は偽"
+        );
+        countries[0].stability = 40.0;
+        assert!(!condition.evaluate(&countries));
+
+        assert!(parse_world_condition("stability > 50").is_err());
+        assert!(parse_world_condition("actor.stability > 50").is_err());
+        assert!(parse_world_condition("Evalia.unknown > 0").is_err());
+        assert!(parse_condition("Evalia.stability > 50").is_err());
+    }
 }
@@ -0,0 +1,395 @@
+use anyhow::{Result, anyhow, ensure};
+use rand::Rng;
+use rand::rngs::StdRng;
+
+use crate::game::country::CountryState;
+
+use super::parser::{Operand, Subjects, is_ident_start, read_ident};
+
+/// 効果の量を表す数式。条件式と同じメトリクスを参照でき、四則演算と
+/// `min(a, b)` / `max(a, b)` / `uniform(a, b)` (ゲームの乱数による一様乱数) を使える。
+#[derive(Debug, Clone)]
+pub(crate) struct ValueExpression {
+    root: ValueNode,
+}
+
+pub(crate) fn parse_value(text: &str) -> Result<ValueExpression> {
+    let tokens = tokenize(text)?;
+    let mut parser = Parser { tokens, pos: 0 };
+    let root = parser.parse_sum()?;
+    ensure!(
+        parser.pos == parser.tokens.len(),
+        "数式 '{}' の末尾に解釈できないトークンがあります",
+        text
+    );
+    Ok(ValueExpression { root })
+}
+
+impl ValueExpression {
+    pub(crate) fn constant(value: f64) -> Self {
+        Self {
+            root: ValueNode::Number(value),
+        }
+    }
+
+    pub(crate) fn references_target(&self) -> bool {
+        self.root.references_target()
+    }
+
+    /// 接頭辞なしのメトリクスは条件式と同じく `actor` を指す。結果が有限でなければ 0 とする。
+    pub(crate) fn evaluate(
+        &self,
+        actor: &CountryState,
+        target: Option<&CountryState>,
+        rng: &mut StdRng,
+    ) -> f64 {
+        let value = self.root.eval(&Subjects { actor, target }, rng);
+        if value.is_finite() { value } else { 0.0 }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum ValueNode {
+    Number(f64),
+    Metric(Operand),
+    Negate(Box<ValueNode>),
+    Binary(BinaryOp, Box<ValueNode>, Box<ValueNode>),
+    Function(Function, Box<ValueNode>, Box<ValueNode>),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Function {
+    Min,
+    Max,
+    Uniform,
+}
+
+impl Function {
+    fn from_str(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "min" => Some(Self::Min),
+            "max" => Some(Self::Max),
+            "uniform" => Some(Self::Uniform),
+            _ => None,
+        }
+    }
+}
+
+impl ValueNode {
+    fn eval(&self, subjects: &Subjects<'_>, rng: &mut StdRng) -> f64 {
+        match self {
+            ValueNode::Number(value) => *value,
+            // target のない評価で target 指標を参照した場合は 0 とみなす。
+            ValueNode::Metric(operand) => operand.value(subjects).unwrap_or(0.0),
+            ValueNode::Negate(inner) => -inner.eval(subjects, rng),
+            ValueNode::Binary(op, lhs, rhs) => {
+                let lhs = lhs.eval(subjects, rng);
+                let rhs = rhs.eval(subjects, rng);
+                match op {
+                    BinaryOp::Add => lhs + rhs,
+                    BinaryOp::Sub => lhs - rhs,
+                    BinaryOp::Mul => lhs * rhs,
+                    BinaryOp::Div => lhs / rhs,
+                }
+            }
+            ValueNode::Function(function, first, second) => {
+                let first = first.eval(subjects, rng);
+                let second = second.eval(subjects, rng);
+                match function {
+                    Function::Min => first.min(second),
+                    Function::Max => first.max(second),
+                    Function::Uniform => {
+                        let (low, high) = (first.min(second), first.max(second));
+                        if high - low <= f64::EPSILON || !(high - low).is_finite() {
+                            low
+                        } else {
+                            rng.gen_range(low..high)
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn references_target(&self) -> bool {
+        match self {
+            ValueNode::Number(_) => false,
+            ValueNode::Metric(operand) => operand.references_target(),
+            ValueNode::Negate(inner) => inner.references_target(),
+            ValueNode::Binary(_, lhs, rhs) | ValueNode::Function(_, lhs, rhs) => {
+                lhs.references_target() || rhs.references_target()
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Token {
+    Number(f64),
+    Ident(String),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    LParen,
+    RParen,
+    Comma,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>> {
+    let mut chars = input.chars().peekable();
+    let mut tokens = Vec::new();
+    while let Some(&ch) = chars.peek() {
+        let token = match ch {
+            ' ' | '\t' | '\r' | '\n' => {
+                chars.next();
+                continue;
+            }
+            '0'..='9' | '.' => {
+                let mut buffer = String::new();
+                while let Some(&digit) = chars.peek() {
+                    if !(digit.is_ascii_digit() || digit == '.') {
+                        break;
+                    }
+                    buffer.push(digit);
+                    chars.next();
+                }
+                let value = buffer
+                    .parse()
+                    .map_err(|err| anyhow!("数値 '{}' の解析に失敗しました: {}", buffer, err))?;
+                tokens.push(Token::Number(value));
+                continue;
+            }
+            _ if is_ident_start(ch) => {
+                tokens.push(Token::Ident(read_ident(&mut chars)));
+                continue;
+            }
+            '+' => Token::Plus,
+            '-' => Token::Minus,
+            '*' => Token::Star,
+            '/' => Token::Slash,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            ',' => Token::Comma,
+            _ => return Err(anyhow!("数式に解釈できない文字 '{}' が含まれています", ch)),
+        };
+        chars.next();
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn parse_sum(&mut self) -> Result<ValueNode> {
+        let mut node = self.parse_product()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Plus) => BinaryOp::Add,
+                Some(Token::Minus) => BinaryOp::Sub,
+                _ => return Ok(node),
+            };
+            self.pos += 1;
+            let rhs = self.parse_product()?;
+            node = fold(op, node, rhs);
+        }
+    }
+
+    fn parse_product(&mut self) -> Result<ValueNode> {
+        let mut node = self.parse_unary()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Star) => BinaryOp::Mul,
+                Some(Token::Slash) => BinaryOp::Div,
+                _ => return Ok(node),
+            };
+            self.pos += 1;
+            let rhs = self.parse_unary()?;
+            node = fold(op, node, rhs);
+        }
+    }
+
+    fn parse_unary(&mut self) -> Result<ValueNode> {
+        match self.peek() {
+            Some(Token::Minus) => {
+                self.pos += 1;
+                Ok(match self.parse_unary()? {
+                    ValueNode::Number(value) => ValueNode::Number(-value),
+                    inner => ValueNode::Negate(Box::new(inner)),
+                })
+            }
+            Some(Token::Plus) => {
+                self.pos += 1;
+                self.parse_unary()
+            }
+            _ => self.parse_primary(),
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<ValueNode> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        match token {
+            Some(Token::Number(value)) => Ok(ValueNode::Number(value)),
+            Some(Token::LParen) => {
+                let node = self.parse_sum()?;
+                self.expect(Token::RParen, "')'")?;
+                Ok(node)
+            }
+            Some(Token::Ident(name)) => {
+                if !matches!(self.peek(), Some(Token::LParen)) {
+                    return Ok(ValueNode::Metric(Operand::parse(&name)?));
+                }
+                let function = Function::from_str(&name)
+                    .ok_or_else(|| anyhow!("未知の関数 '{}' が指定されました", name))?;
+                self.pos += 1;
+                let first = self.parse_sum()?;
+                self.expect(Token::Comma, "','")?;
+                let second = self.parse_sum()?;
+                self.expect(Token::RParen, "')'")?;
+                if let (Function::Uniform, ValueNode::Number(low), ValueNode::Number(high)) =
+                    (function, &first, &second)
+                {
+                    ensure!(
+                        low < high,
+                        "uniform の範囲は下限 < 上限で指定してください ({} , {})",
+                        low,
+                        high
+                    );
+                }
+                Ok(ValueNode::Function(
+                    function,
+                    Box::new(first),
+                    Box::new(second),
+                ))
+            }
+            Some(token) => Err(anyhow!("予期しないトークン {:?} が出現しました", token)),
+            None => Err(anyhow!("数式が途中で終了しました")),
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn expect(&mut self, expected: Token, label: &str) -> Result<()> {
+        let matched = self.peek().is_some_and(|token| {
+            std::mem::discriminant(token) == std::mem::discriminant(&expected)
+        });
+        ensure!(matched, "数式に {} が必要です", label);
+        self.pos += 1;
+        Ok(())
+    }
+}
+
+/// 定数同士の演算はコンパイル時に畳み込む。
+fn fold(op: BinaryOp, lhs: ValueNode, rhs: ValueNode) -> ValueNode {
+    if let (ValueNode::Number(a), ValueNode::Number(b)) = (&lhs, &rhs) {
+        let folded = match op {
+            BinaryOp::Add => a + b,
+            BinaryOp::Sub => a - b,
+            BinaryOp::Mul => a * b,
+            BinaryOp::Div => a / b,
+        };
+        if folded.is_finite() {
+            return ValueNode::Number(folded);
+        }
+    }
+    ValueNode::Binary(op, Box::new(lhs), Box::new(rhs))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::game::country::BudgetAllocation;
+    use crate::game::economy::{CreditRating, FiscalAccount, TaxPolicy};
+
+    fn sample_country(name: &str, gdp: f64) -> CountryState {
+        CountryState::new(
+            name.to_string(),
+            "Republic".to_string(),
+            12.0,
+            gdp,
+            55.0,
+            45.0,
+            48.0,
+            70,
+            FiscalAccount::new(300.0, CreditRating::A),
+            TaxPolicy::default(),
+            BudgetAllocation::default(),
+        )
+    }
+
+    #[test]
+    fn value_expressions_read_metrics_and_fold_constants() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let country = sample_country("Evalia", 2_000.0);
+        let expr = parse_value("-0.05 * gdp + (stability - 5) / 2").expect("parse");
+        assert!((expr.evaluate(&country, None, &mut rng) - -75.0).abs() < 1e-9);
+        assert!(matches!(
+            parse_value("2 * (3 + 4)").unwrap().root,
+            ValueNode::Number(value) if value == 14.0
+        ));
+        assert_eq!(
+            parse_value("max(-gdp, -150)")
+                .unwrap()
+                .evaluate(&country, None, &mut rng),
+            -150.0
+        );
+        assert_eq!(
+            parse_value("gdp / 0")
+                .unwrap()
+                .evaluate(&country, None, &mut rng),
+            0.0
+        );
+
+        let rival = sample_country("Rivalia", 800.0);
+        let pair = parse_value("0.1 * target.gdp").expect("parse");
+        assert!(pair.references_target());
+        assert!((pair.evaluate(&country, Some(&rival), &mut rng) - 80.0).abs() < 1e-9);
+
+        for invalid in [
+            "gdp +",
+            "unknown * 2",
+            "uniform(5, 1)",
+            "sqrt(gdp, 1)",
+            "gdp % 2",
+        ] {
+            assert!(
+                parse_value(invalid).is_err(),
+                "{} should be rejected",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn uniform_draws_from_the_seeded_rng() {
+        let country = sample_country("Evalia", 1_000.0);
+        let expr = parse_value("uniform(-8, -4)").expect("parse");
+        let draws = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (0..20)
+                .map(|_| expr.evaluate(&country, None, &mut rng))
+                .collect::<Vec<_>>()
+        };
+        let first = draws(7);
+        assert_eq!(first, draws(7));
+        assert!(first.iter().all(|value| (-8.0..-4.0).contains(value)));
+        assert!(first.windows(2).any(|pair| pair[0] != pair[1]));
+    }
+}
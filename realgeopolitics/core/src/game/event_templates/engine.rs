@@ -1,8 +1,15 @@
-use anyhow::Result;
+use std::collections::HashMap;
+
+use anyhow::{Result, anyhow};
+use rand::Rng;
+use rand::rngs::StdRng;
 
 use crate::game::country::CountryState;
+use crate::game::economy::SectorShock;
+use crate::game::economy::industry::SectorRegistry;
+use crate::{ScheduleSpec, TaskCategory};
 
-use super::compiler::CompiledEventTemplate;
+use super::compiler::{CompiledEventTemplate, Targeting};
 use super::loader::load_event_templates;
 use super::{ScriptedEventReport, format_reports};
 
@@ -10,16 +17,53 @@ use super::{ScriptedEventReport, format_reports};
 pub(crate) struct ScriptedEventEngine {
     templates: Vec<CompiledEventTemplate>,
     instances: Vec<ScriptedEventInstance>,
+    /// 発生したイベントのうち、産業セクターへ適用待ちの効果 (セクター指定, ショック)。
+    pending_sector_shocks: Vec<(String, SectorShock)>,
+    /// 資源市場へ適用待ちの価格ショックの倍率。
+    pending_commodity_shocks: Vec<f64>,
+    pending_follow_ups: Vec<ScriptedFollowUp>,
 }
 
-#[derive(Debug)]
+/// `schedule_event` 効果によって予約された後続イベント。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ScriptedFollowUp {
+    pub template: usize,
+    pub country: usize,
+    /// 二国間イベントから予約された場合の相手国。後続が二国間テンプレートなら組として評価する。
+    pub target: Option<usize>,
+    pub delay_minutes: u64,
+}
+
+/// クールダウンを管理する単位。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum CooldownKey {
+    Country(usize),
+    /// 二国間テンプレートの組。向きは問わない。
+    Pair(usize, usize),
+    /// `targeting` が `each` 以外のテンプレートは、テンプレート全体で 1 つのクールダウンを共有する。
+    Template,
+}
+
+#[derive(Debug, Default)]
 struct ScriptedEventInstance {
-    last_triggered: Vec<Option<f64>>,
+    last_triggered: HashMap<CooldownKey, f64>,
+}
+
+/// 条件を評価する単位。二国間テンプレートでは `target` に相手国が入る。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Subject {
+    country: usize,
+    target: Option<usize>,
+}
+
+/// 1 回の発生。`targeting: global` では特定の国を持たない。
+#[derive(Debug, Clone, Copy)]
+struct Trigger {
+    subject: Option<Subject>,
+    pool_entry: Option<usize>,
 }
 impl ScriptedEventEngine {
-    /// Creates a ScriptedEventEngine populated with the built-in compiled event templates for the specified number of countries.
-    ///
-    /// Returns an engine whose templates are loaded from the built-in source and whose per-template state is initialized for `country_count`.
+    /// Creates a ScriptedEventEngine populated with the built-in compiled event templates.
     ///
     /// # Errors
     ///
@@ -28,34 +72,35 @@ impl ScriptedEventEngine {
     /// # Examples
     ///
     /// ```ignore
-    /// let engine = ScriptedEventEngine::from_builtin(3).unwrap();
+    /// let engine = ScriptedEventEngine::from_builtin().unwrap();
     /// assert!(engine.len() >= 0);
     /// ```ignore
-    pub(crate) fn from_builtin(country_count: usize) -> Result<Self> {
+    pub(crate) fn from_builtin() -> Result<Self> {
         let templates = load_event_templates()?;
-        Ok(Self::with_templates(templates, country_count))
+        let engine = Self::with_templates(templates);
+        engine.validate_follow_ups()?;
+        Ok(engine)
     }
 
-    /// Constructs a ScriptedEventEngine from compiled templates and initializes per-template,
-    /// per-country execution state for the given number of countries.
+    /// Constructs a ScriptedEventEngine from compiled templates with empty per-template cooldown state.
     ///
     /// # Examples
     ///
     /// ```ignore
-    /// let engine = ScriptedEventEngine::with_templates(vec![], 3);
+    /// let engine = ScriptedEventEngine::with_templates(vec![]);
     /// assert_eq!(engine.len(), 0);
     /// ```ignore
-    pub(super) fn with_templates(
-        templates: Vec<CompiledEventTemplate>,
-        country_count: usize,
-    ) -> Self {
+    pub(super) fn with_templates(templates: Vec<CompiledEventTemplate>) -> Self {
         let instances = templates
             .iter()
-            .map(|_| ScriptedEventInstance::new(country_count))
+            .map(|_| ScriptedEventInstance::default())
             .collect();
         Self {
             templates,
             instances,
+            pending_sector_shocks: Vec::new(),
+            pending_commodity_shocks: Vec::new(),
+            pending_follow_ups: Vec::new(),
         }
     }
 
@@ -64,7 +109,7 @@ impl ScriptedEventEngine {
     /// # Examples
     ///
     /// ```ignore
-    /// let engine = ScriptedEventEngine::with_templates(Vec::new(), 0);
+    /// let engine = ScriptedEventEngine::with_templates(Vec::new());
     /// assert_eq!(engine.len(), 0);
     /// ```ignore
     ///
@@ -73,22 +118,27 @@ impl ScriptedEventEngine {
         self.templates.len()
     }
 
-    /// Gets the check interval in minutes for the template at the given index.
-    ///
-    /// The returned value is the number of minutes between automatic checks for that template.
+    /// 定期チェックの規則 (`schedule` または `check_minutes` ごと)。
     ///
     /// # Panics
     ///
     /// Panics if `idx` is not a valid template index.
-    ///
-    /// # Examples
-    ///
-    /// ```ignore
-    /// let minutes = engine.check_minutes(0);
-    /// assert!(minutes > 0);
-    /// ```ignore
-    pub(crate) fn check_minutes(&self, idx: usize) -> u64 {
-        self.template_ref(idx).check_minutes()
+    pub(crate) fn schedule(&self, idx: usize) -> ScheduleSpec {
+        self.template_ref(idx).schedule()
+    }
+
+    /// 二国間のテンプレートと `targeting: global` のテンプレートは国際カテゴリとして扱う。
+    pub(crate) fn category(&self, idx: usize) -> TaskCategory {
+        let template = self.template_ref(idx);
+        if template.is_bilateral() || matches!(template.targeting(), Targeting::Global) {
+            TaskCategory::International
+        } else {
+            TaskCategory::Domestic
+        }
+    }
+
+    pub(crate) fn template_id(&self, idx: usize) -> &str {
+        self.template_ref(idx).id()
     }
 
     /// Returns the initial delay, in minutes, before the template at the given index is first eligible to run.
@@ -100,7 +150,7 @@ impl ScriptedEventEngine {
     /// # Examples
     ///
     /// ```ignoreno_run
-    /// let engine = ScriptedEventEngine::with_templates(templates, country_count);
+    /// let engine = ScriptedEventEngine::with_templates(templates);
     /// let delay = engine.initial_delay_minutes(0);
     /// println!("Initial delay: {} minutes", delay);
     /// ```ignore
@@ -108,6 +158,11 @@ impl ScriptedEventEngine {
         self.template_ref(idx).initial_delay_minutes()
     }
 
+    /// 定期チェックの対象かどうか。false のテンプレートは後続イベントとしてのみ発生する。
+    pub(crate) fn auto_trigger(&self, idx: usize) -> bool {
+        self.template_ref(idx).auto_trigger()
+    }
+
     /// Finds the index of a template whose id or name matches the provided string, case-insensitively.
     ///
     /// The search compares the given `id` against each template's `id()` and `name()` using
@@ -159,6 +214,7 @@ impl ScriptedEventEngine {
     /// - `idx`: Index of the compiled event template to run.
     /// - `countries`: Mutable slice of country states to apply the template to.
     /// - `current_minutes`: Current simulation time in minutes used for cooldown checks.
+    /// - `rng`: Random source for the template's `probability` roll and `pool` selection.
     ///
     /// # Returns
     ///
@@ -172,27 +228,172 @@ impl ScriptedEventEngine {
     ///
     /// ```ignoreno_run
     /// // Assuming `engine` is a ScriptedEventEngine with at least one template and `countries` is a mutable slice:
-    /// // let mut engine = ScriptedEventEngine::with_templates(templates, country_count);
+    /// // let mut engine = ScriptedEventEngine::with_templates(templates);
     /// // let mut countries = vec![sample_country("A")];
-    /// // let reports = engine.execute(0, &mut countries, 1234.0);
+    /// // let reports = engine.execute(0, &mut countries, 1234.0, &mut rng);
     /// ```ignore
     pub(crate) fn execute(
         &mut self,
         idx: usize,
         countries: &mut [CountryState],
         current_minutes: f64,
+        rng: &mut StdRng,
     ) -> Vec<String> {
-        let (templates, instances) = (&self.templates, &mut self.instances);
-        let template = templates
+        let template = self
+            .templates
             .get(idx)
             .unwrap_or_else(|| panic!("無効なイベントテンプレートインデックス: {}", idx));
-        let instance = instances
+        let instance = self
+            .instances
             .get_mut(idx)
             .unwrap_or_else(|| panic!("無効なイベントテンプレートインデックス: {}", idx));
-        let reports = instance.execute(template, countries, current_minutes);
+        let (reports, triggered) = instance.execute(template, countries, current_minutes, rng);
+        self.record_triggers(idx, &triggered);
         format_reports(&reports)
     }
 
+    /// `schedule_event` で予約された後続イベントを 1 か国 (二国間テンプレートなら 1 組) について実行する。
+    ///
+    /// クールダウンと `targeting` は無視するが、条件式と発生確率は発生時点で判定する。満たさなければ何も起きない。
+    pub(crate) fn execute_follow_up(
+        &mut self,
+        idx: usize,
+        country_idx: usize,
+        target_idx: Option<usize>,
+        countries: &mut [CountryState],
+        current_minutes: f64,
+        rng: &mut StdRng,
+    ) -> Vec<String> {
+        let template = self.template_ref(idx);
+        if country_idx >= countries.len() {
+            return Vec::new();
+        }
+        let subject = if template.is_bilateral() {
+            let Some(target_idx) =
+                target_idx.filter(|&target| target != country_idx && target < countries.len())
+            else {
+                return Vec::new();
+            };
+            Subject {
+                country: country_idx,
+                target: Some(target_idx),
+            }
+        } else {
+            Subject {
+                country: country_idx,
+                target: None,
+            }
+        };
+        let matched = match subject.target {
+            Some(target) => {
+                template.pair_condition_matches(&countries[country_idx], &countries[target])
+            }
+            None => template.condition_matches(&countries[country_idx]),
+        };
+        if !matched || !template.roll_probability(rng) {
+            return Vec::new();
+        }
+        let pool_entry = template.choose_pool_entry(rng);
+        let reports = fire(template, countries, subject, pool_entry, rng);
+        let template = &self.templates[idx];
+        self.instances[idx].record(template, subject, current_minutes);
+        self.record_triggers(
+            idx,
+            &[Trigger {
+                subject: Some(subject),
+                pool_entry,
+            }],
+        );
+        format_reports(&reports)
+    }
+
+    /// 発生ごとに後続イベントを、発生した抽選結果ごとにセクター・資源市場の効果を積む。target 側の効果が予約する後続は target を起点にする。
+    fn record_triggers(&mut self, idx: usize, triggered: &[Trigger]) {
+        let template = &self.templates[idx];
+        let mut shocked_entries = Vec::new();
+        for trigger in triggered {
+            // 産業と資源市場は世界で 1 つなので、複数の国で発生しても同じ効果は 1 度だけ積む。
+            if !shocked_entries.contains(&trigger.pool_entry) {
+                shocked_entries.push(trigger.pool_entry);
+                self.pending_sector_shocks.extend(
+                    template
+                        .sector_shocks(trigger.pool_entry)
+                        .map(|(sector, shock)| (sector.to_string(), shock)),
+                );
+                self.pending_commodity_shocks
+                    .extend(template.commodity_shocks(trigger.pool_entry));
+            }
+            let Some(subject) = trigger.subject else {
+                continue;
+            };
+            let actor_side = template
+                .follow_ups(trigger.pool_entry)
+                .map(|follow_up| (follow_up, subject.country, subject.target));
+            let target_side = subject.target.into_iter().flat_map(|target| {
+                template
+                    .target_follow_ups(trigger.pool_entry)
+                    .map(move |follow_up| (follow_up, target, Some(subject.country)))
+            });
+            for ((event, delay_minutes), country, target) in actor_side.chain(target_side) {
+                if let Some(template) = self.find_index(event) {
+                    self.pending_follow_ups.push(ScriptedFollowUp {
+                        template,
+                        country,
+                        target,
+                        delay_minutes,
+                    });
+                }
+            }
+        }
+    }
+
+    /// 発生したイベントが予約した後続イベントを取り出す。
+    pub(crate) fn take_follow_ups(&mut self) -> Vec<ScriptedFollowUp> {
+        std::mem::take(&mut self.pending_follow_ups)
+    }
+
+    /// `schedule_event` の参照先がすべて既知のテンプレートであることを確認する。
+    pub(crate) fn validate_follow_ups(&self) -> Result<()> {
+        for template in &self.templates {
+            for (event, _) in template.all_follow_ups() {
+                let Some(next) = self.find_index(event) else {
+                    return Err(anyhow!(
+                        "イベント {} の後続イベント '{}' が見つかりません",
+                        template.id(),
+                        event
+                    ));
+                };
+                if self.templates[next].is_bilateral() && !template.is_bilateral() {
+                    return Err(anyhow!(
+                        "イベント {} は単独の国を対象とするため、二国間イベント '{}' を後続にできません",
+                        template.id(),
+                        event
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// `execute` で発生した産業セクターへの効果を取り出す。何か国で発生しても、
+    /// 1 回の `execute` につき発生したテンプレート (プール内のエントリー) ごとに 1 件だけ含まれる。
+    pub(crate) fn take_sector_shocks(&mut self) -> Vec<(String, SectorShock)> {
+        std::mem::take(&mut self.pending_sector_shocks)
+    }
+
+    /// 発生したイベントによる資源市場の価格ショック (倍率) を取り出す。
+    pub(crate) fn take_commodity_shocks(&mut self) -> Vec<f64> {
+        std::mem::take(&mut self.pending_commodity_shocks)
+    }
+
+    /// 全テンプレートのセクター指定が産業カタログで解決できることを確認する。
+    pub(crate) fn validate_sectors(&self, registry: &SectorRegistry) -> Result<()> {
+        for template in &self.templates {
+            template.validate_sectors(registry)?;
+        }
+        Ok(())
+    }
+
     /// Returns a reference to the compiled event template at the specified index.
     ///
     /// # Panics
@@ -203,7 +404,7 @@ impl ScriptedEventEngine {
     /// # Examples
     ///
     /// ```ignoreno_run
-    /// let engine = ScriptedEventEngine::with_templates(vec![/* CompiledEventTemplate */], 1);
+    /// let engine = ScriptedEventEngine::with_templates(vec![/* CompiledEventTemplate */]);
     /// let template = engine.template_ref(0);
     /// ```ignore
     fn template_ref(&self, idx: usize) -> &CompiledEventTemplate {
@@ -213,84 +414,214 @@ impl ScriptedEventEngine {
     }
 }
 impl ScriptedEventInstance {
-    /// Creates a new ScriptedEventInstance with per-country last-trigger times initialized to `None`.
-    ///
-    /// The returned instance has `country_count` entries of `None`, indicating that no country has
-    /// triggered an event yet.
-    ///
-    /// # Examples
-    ///
-    /// ```ignore
-    /// let instance = ScriptedEventInstance::new(3);
-    /// // instance now tracks three countries, each with no prior trigger time
-    /// ```ignore
-    fn new(country_count: usize) -> Self {
-        Self {
-            last_triggered: vec![None; country_count],
+    fn cooldown_key(template: &CompiledEventTemplate, subject: Subject) -> CooldownKey {
+        match (template.targeting(), subject.target) {
+            (Targeting::Each, Some(target)) => {
+                let (a, b) = pair_key(subject.country, target);
+                CooldownKey::Pair(a, b)
+            }
+            (Targeting::Each, None) => CooldownKey::Country(subject.country),
+            _ => CooldownKey::Template,
         }
     }
 
-    /// Ensure the internal per-country cooldown vector can hold at least `country_count` entries.
-    ///
-    /// Expands `last_triggered` with `None` entries if it is shorter than `country_count`.
-    ///
-    /// # Examples
-    ///
-    /// ```ignore
-    /// let mut inst = ScriptedEventInstance::new(1);
-    /// inst.ensure_capacity(3);
-    /// assert_eq!(inst.last_triggered.len(), 3);
-    /// ```ignore
-    fn ensure_capacity(&mut self, country_count: usize) {
-        if self.last_triggered.len() < country_count {
-            self.last_triggered.resize(country_count, None);
+    fn record(&mut self, template: &CompiledEventTemplate, subject: Subject, current_minutes: f64) {
+        self.last_triggered
+            .insert(Self::cooldown_key(template, subject), current_minutes);
+    }
+
+    /// 条件式とクールダウンを満たしているか。
+    fn matches(
+        &self,
+        template: &CompiledEventTemplate,
+        countries: &[CountryState],
+        subject: Subject,
+        current_minutes: f64,
+    ) -> bool {
+        let last = self
+            .last_triggered
+            .get(&Self::cooldown_key(template, subject))
+            .copied();
+        let country = &countries[subject.country];
+        match subject.target {
+            Some(target) => {
+                template.can_trigger_pair(country, &countries[target], last, current_minutes)
+            }
+            None => template.can_trigger(country, last, current_minutes),
         }
     }
 
-    /// Executes a compiled event template against all provided countries, applying effects where the template may trigger and recording per-country last-triggered times.
+    /// テンプレートを `targeting` に従って評価し、発生した分の効果を適用する。
     ///
-    /// The method ensures internal per-country state is large enough for `countries`, checks each country with `template.can_trigger` using its last-triggered timestamp and `current_minutes`, applies effects for countries that may trigger, updates their last-triggered time to `current_minutes`, and returns all reports produced by applied effects.
-    ///
-    /// # Parameters
-    ///
-    /// - `template`: The compiled event template to evaluate and apply.
-    /// - `countries`: Mutable slice of country states that may be modified by the template's effects.
-    /// - `current_minutes`: Current time in minutes used for cooldown and trigger checks.
-    ///
-    /// # Returns
-    ///
-    /// A `Vec<String>` containing all reports produced by applying the template's effects to countries.
-    ///
-    /// # Examples
-    ///
-    /// ```ignore
-    /// # // Helpers such as `sample_country` and `compile_sample_template` are available in this crate's tests.
-    /// use crate::event_templates::{ScriptedEventInstance, CompiledEventTemplate};
-    ///
-    /// let mut instance = ScriptedEventInstance::new(1);
-    /// let mut countries = vec![sample_country("Country A")];
-    /// let template: CompiledEventTemplate = compile_sample_template(); // a template that may produce reports
-    ///
-    /// let reports = instance.execute(&template, &mut countries, 0.0);
-    /// // `reports` contains messages produced by the template's applied effects
-    /// ```ignore
+    /// `each` では条件を満たしたものごとに発生確率を判定する。それ以外では条件を満たした候補を
+    /// 集めてから 1 回だけ判定し、`global` は世界全体に 1 回、`random_one` は候補から 1 つ、
+    /// `top_n` / `bottom_n` は `metric` の順に上位から発生させる。
     fn execute(
         &mut self,
         template: &CompiledEventTemplate,
         countries: &mut [CountryState],
         current_minutes: f64,
-    ) -> Vec<ScriptedEventReport> {
-        self.ensure_capacity(countries.len());
+        rng: &mut StdRng,
+    ) -> (Vec<ScriptedEventReport>, Vec<Trigger>) {
+        let subjects = subjects(template, countries.len());
+        if matches!(template.targeting(), Targeting::Each) {
+            return self.execute_each(template, subjects, countries, current_minutes, rng);
+        }
+
+        let candidates: Vec<Subject> = subjects
+            .into_iter()
+            .filter(|subject| self.matches(template, countries, *subject, current_minutes))
+            .collect();
+        if candidates.is_empty() || !template.roll_probability(rng) {
+            return (Vec::new(), Vec::new());
+        }
+        let selected = match template.targeting() {
+            Targeting::Global => {
+                let pool_entry = template.choose_pool_entry(rng);
+                let names: Vec<&str> = candidates
+                    .iter()
+                    .map(|subject| countries[subject.country].name.as_str())
+                    .collect();
+                let reports = template.global_reports(pool_entry, &names);
+                self.last_triggered
+                    .insert(CooldownKey::Template, current_minutes);
+                return (
+                    reports,
+                    vec![Trigger {
+                        subject: None,
+                        pool_entry,
+                    }],
+                );
+            }
+            Targeting::RandomOne => vec![candidates[rng.gen_range(0..candidates.len())]],
+            Targeting::Ranked {
+                metric,
+                count,
+                highest_first,
+            } => {
+                let mut ranked: Vec<(f64, Subject)> = candidates
+                    .into_iter()
+                    .map(|subject| {
+                        let target = subject.target.map(|target| &countries[target]);
+                        let value = metric.evaluate(&countries[subject.country], target, rng);
+                        (value, subject)
+                    })
+                    .collect();
+                // 同値は国の並び順を保つ。
+                ranked.sort_by(|a, b| {
+                    let order = a.0.total_cmp(&b.0);
+                    if *highest_first {
+                        order.reverse()
+                    } else {
+                        order
+                    }
+                });
+                ranked
+                    .into_iter()
+                    .take(*count)
+                    .map(|(_, subject)| subject)
+                    .collect()
+            }
+            Targeting::Each => unreachable!("each は先に処理済み"),
+        };
+
+        let mut reports = Vec::new();
+        let mut triggered = Vec::new();
+        for subject in selected {
+            let pool_entry = template.choose_pool_entry(rng);
+            reports.extend(fire(template, countries, subject, pool_entry, rng));
+            self.record(template, subject, current_minutes);
+            triggered.push(Trigger {
+                subject: Some(subject),
+                pool_entry,
+            });
+        }
+        (reports, triggered)
+    }
+
+    /// 先に発生した効果が後の候補の条件に影響するよう、1 件ずつ評価して発生させる。
+    fn execute_each(
+        &mut self,
+        template: &CompiledEventTemplate,
+        subjects: Vec<Subject>,
+        countries: &mut [CountryState],
+        current_minutes: f64,
+        rng: &mut StdRng,
+    ) -> (Vec<ScriptedEventReport>, Vec<Trigger>) {
         let mut reports = Vec::new();
-        for (idx, country) in countries.iter_mut().enumerate() {
-            if !template.can_trigger(country, self.last_triggered[idx], current_minutes) {
+        let mut triggered = Vec::new();
+        for subject in subjects {
+            if !self.matches(template, countries, subject, current_minutes)
+                || !template.roll_probability(rng)
+            {
                 continue;
             }
-            let mut local_reports = template.apply_effects(country);
-            reports.append(&mut local_reports);
-            self.last_triggered[idx] = Some(current_minutes);
+            let pool_entry = template.choose_pool_entry(rng);
+            reports.extend(fire(template, countries, subject, pool_entry, rng));
+            self.record(template, subject, current_minutes);
+            triggered.push(Trigger {
+                subject: Some(subject),
+                pool_entry,
+            });
         }
-        reports
+        (reports, triggered)
+    }
+}
+
+/// 評価対象の一覧。二国間テンプレートではすべての順序対になる。
+fn subjects(template: &CompiledEventTemplate, country_count: usize) -> Vec<Subject> {
+    if !template.is_bilateral() {
+        return (0..country_count)
+            .map(|country| Subject {
+                country,
+                target: None,
+            })
+            .collect();
+    }
+    (0..country_count)
+        .flat_map(|country| {
+            (0..country_count)
+                .filter(move |&target| target != country)
+                .map(move |target| Subject {
+                    country,
+                    target: Some(target),
+                })
+        })
+        .collect()
+}
+
+fn fire(
+    template: &CompiledEventTemplate,
+    countries: &mut [CountryState],
+    subject: Subject,
+    pool_entry: Option<usize>,
+    rng: &mut StdRng,
+) -> Vec<ScriptedEventReport> {
+    match subject.target {
+        Some(target) => {
+            let (actor, target) = pair_mut(countries, subject.country, target);
+            template.apply_pair_effects(actor, target, pool_entry, rng)
+        }
+        None => template.apply_effects(&mut countries[subject.country], pool_entry, rng),
+    }
+}
+
+fn pair_key(a: usize, b: usize) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
+
+fn pair_mut(
+    countries: &mut [CountryState],
+    a: usize,
+    b: usize,
+) -> (&mut CountryState, &mut CountryState) {
+    assert_ne!(a, b, "同じ国を組にすることはできません");
+    if a < b {
+        let (left, right) = countries.split_at_mut(b);
+        (&mut left[a], &mut right[0])
+    } else {
+        let (left, right) = countries.split_at_mut(a);
+        (&mut right[0], &mut left[b])
     }
 }
 #[cfg(test)]
@@ -300,17 +631,22 @@ mod tests {
     use crate::game::country::{BudgetAllocation, CountryState};
     use crate::game::economy::CreditRating;
     use crate::game::economy::{FiscalAccount, TaxPolicy};
+    use rand::SeedableRng;
     use serde_json;
 
+    fn rng() -> StdRng {
+        StdRng::seed_from_u64(38)
+    }
+
     fn sample_country(name: &str) -> CountryState {
         CountryState::new(
             name.to_string(),
             "Republic".to_string(),
             10.0,
             500.0,
-            50,
-            40,
-            45,
+            50.0,
+            40.0,
+            45.0,
             60,
             FiscalAccount::new(200.0, CreditRating::A),
             TaxPolicy::default(),
@@ -357,22 +693,22 @@ mod tests {
             }"#,
         );
         let template = compile_template(0, raw).expect("valid template should compile");
-        let mut engine = ScriptedEventEngine::with_templates(vec![template], 1);
+        let mut engine = ScriptedEventEngine::with_templates(vec![template]);
 
         assert_eq!(engine.len(), 1);
-        assert_eq!(engine.check_minutes(0), 60);
+        assert_eq!(engine.schedule(0), ScheduleSpec::EveryMinutes(60));
         assert_eq!(engine.initial_delay_minutes(0), 5);
         assert_eq!(engine.description_of("approval_push"), Some("desc"));
 
         let mut countries = vec![sample_country("Testland")];
-        let reports = engine.execute(0, &mut countries, 300.0);
+        let reports = engine.execute(0, &mut countries, 300.0, &mut rng());
         assert_eq!(reports.len(), 1);
         assert_eq!(reports[0], "Testland improved approval");
-        assert_eq!(countries[0].approval, 55);
+        assert_eq!(countries[0].approval, 55.0);
 
-        let reports_second = engine.execute(0, &mut countries, 360.0);
+        let reports_second = engine.execute(0, &mut countries, 360.0, &mut rng());
         assert!(reports_second.is_empty());
-        assert_eq!(countries[0].approval, 55);
+        assert_eq!(countries[0].approval, 55.0);
     }
     #[test]
     fn engine_expands_instance_capacity_for_additional_countries() {
@@ -391,14 +727,399 @@ mod tests {
             }"#,
         );
         let template = compile_template(0, raw).expect("template should compile");
-        let mut engine = ScriptedEventEngine::with_templates(vec![template], 1);
+        let mut engine = ScriptedEventEngine::with_templates(vec![template]);
 
         let mut countries = vec![sample_country("Alpha"), sample_country("Beta")];
         let baseline_alpha = countries[0].approval;
         let baseline_beta = countries[1].approval;
-        let reports = engine.execute(0, &mut countries, 45.0);
+        let reports = engine.execute(0, &mut countries, 45.0, &mut rng());
         assert!(reports.is_empty());
-        assert_eq!(countries[0].approval, baseline_alpha + 5);
-        assert_eq!(countries[1].approval, baseline_beta + 5);
+        assert_eq!(countries[0].approval, baseline_alpha + 5.0);
+        assert_eq!(countries[1].approval, baseline_beta + 5.0);
+    }
+
+    #[test]
+    fn engine_collects_sector_shocks_once_per_execution() {
+        let raw = parse_raw(
+            r#"{
+                "id": "supply_glut",
+                "name": "Supply Glut",
+                "description": "desc",
+                "condition": "approval >= 0",
+                "effects": [
+                    { "type": "scale_sector", "sector": "energy:crude_oil", "target": "supply_capacity", "factor": 1.2 }
+                ]
+            }"#,
+        );
+        let template = compile_template(0, raw).expect("template should compile");
+        let mut engine = ScriptedEventEngine::with_templates(vec![template]);
+        let mut countries = vec![sample_country("Alpha"), sample_country("Beta")];
+        engine.execute(0, &mut countries, 0.0, &mut rng());
+        // 2 か国で発生しても、世界共通の産業への効果は 1 度だけ積む。
+        let shocks = engine.take_sector_shocks();
+        assert_eq!(shocks.len(), 1);
+        assert_eq!(shocks[0].0, "energy:crude_oil");
+        assert!(engine.take_sector_shocks().is_empty());
+    }
+
+    #[test]
+    fn follow_ups_chain_per_country_and_recheck_conditions() {
+        let protest = parse_raw(
+            r#"{
+                "id": "protest",
+                "name": "Protest",
+                "description": "desc",
+                "condition": "approval < 50",
+                "effects": [
+                    { "type": "set_flag", "flag": "protest" },
+                    { "type": "schedule_event", "event": "strike", "delay_minutes": 600 }
+                ]
+            }"#,
+        );
+        let strike = parse_raw(
+            r#"{
+                "id": "strike",
+                "name": "Strike",
+                "description": "desc",
+                "condition": "flag.protest",
+                "auto_trigger": false,
+                "effects": [
+                    { "type": "clear_flag", "flag": "protest" },
+                    { "type": "adjust_variable", "variable": "strikes", "delta": 1 }
+                ]
+            }"#,
+        );
+        let templates = vec![
+            compile_template(0, protest).expect("compile"),
+            compile_template(1, strike).expect("compile"),
+        ];
+        let mut engine = ScriptedEventEngine::with_templates(templates);
+        engine.validate_follow_ups().expect("follow-ups resolve");
+        assert!(engine.auto_trigger(0));
+        assert!(!engine.auto_trigger(1));
+
+        let mut countries = vec![sample_country("Alpha"), sample_country("Beta")];
+        countries[1].approval = 60.0;
+        engine.execute(0, &mut countries, 0.0, &mut rng());
+        let follow_ups = engine.take_follow_ups();
+        assert_eq!(
+            follow_ups,
+            vec![ScriptedFollowUp {
+                template: 1,
+                country: 0,
+                target: None,
+                delay_minutes: 600,
+            }]
+        );
+
+        engine.execute_follow_up(1, 0, None, &mut countries, 600.0, &mut rng());
+        assert!(!countries[0].story().has_flag("protest"));
+        assert_eq!(countries[0].story().variable("strikes"), 1.0);
+
+        // フラグが消えた後は後続イベントが届いても条件を満たさない。
+        engine.execute_follow_up(1, 0, None, &mut countries, 700.0, &mut rng());
+        assert_eq!(countries[0].story().variable("strikes"), 1.0);
+        assert!(countries[1].story().is_empty());
+    }
+
+    #[test]
+    fn unknown_follow_up_target_is_rejected() {
+        let raw = parse_raw(
+            r#"{
+                "id": "dangling",
+                "name": "Dangling",
+                "description": "desc",
+                "condition": "approval >= 0",
+                "effects": [
+                    { "type": "schedule_event", "event": "missing", "delay_minutes": 60 }
+                ]
+            }"#,
+        );
+        let engine =
+            ScriptedEventEngine::with_templates(vec![compile_template(0, raw).expect("compile")]);
+        let err = engine.validate_follow_ups().expect_err("missing target");
+        assert!(err.to_string().contains("missing"));
+    }
+
+    #[test]
+    fn probability_and_weighted_pool_select_outcomes() {
+        let raw = parse_raw(
+            r#"{
+                "id": "lottery",
+                "name": "Lottery",
+                "description": "desc",
+                "condition": "true",
+                "cooldown_minutes": 0,
+                "probability": 0.5,
+                "pool": [
+                    { "weight": 3, "effects": [{ "type": "set_flag", "flag": "common" }] },
+                    { "weight": 1, "effects": [
+                        { "type": "set_flag", "flag": "rare" },
+                        { "type": "scale_sector", "sector": "energy:diesel", "target": "supply_capacity", "factor": 1.1 }
+                    ] }
+                ]
+            }"#,
+        );
+        let template = compile_template(0, raw).expect("template should compile");
+        let mut engine = ScriptedEventEngine::with_templates(vec![template]);
+        let mut countries: Vec<_> = (0..400)
+            .map(|idx| sample_country(&format!("C{}", idx)))
+            .collect();
+        engine.execute(0, &mut countries, 0.0, &mut rng());
+
+        let common = countries
+            .iter()
+            .filter(|country| country.story().has_flag("common"))
+            .count();
+        let rare = countries
+            .iter()
+            .filter(|country| country.story().has_flag("rare"))
+            .count();
+        assert!(countries.iter().all(|country| {
+            !(country.story().has_flag("common") && country.story().has_flag("rare"))
+        }));
+        assert!(
+            (160..=240).contains(&(common + rare)),
+            "triggered {}",
+            common + rare
+        );
+        assert!(common > rare * 2, "common {} rare {}", common, rare);
+        assert!(rare > 0);
+        assert_eq!(engine.take_sector_shocks().len(), 1);
+    }
+
+    #[test]
+    fn invalid_probability_and_pool_weight_are_rejected() {
+        let probability = parse_raw(
+            r#"{ "id": "p", "name": "p", "description": "d", "condition": "true", "probability": 1.5 }"#,
+        );
+        assert!(compile_template(0, probability).is_err());
+        let weight = parse_raw(
+            r#"{ "id": "w", "name": "w", "description": "d", "condition": "true",
+                 "pool": [{ "weight": 0, "effects": [] }] }"#,
+        );
+        assert!(compile_template(1, weight).is_err());
+    }
+
+    #[test]
+    fn bilateral_templates_apply_to_both_sides_with_pair_cooldown() {
+        let raw = parse_raw(
+            r#"{
+                "id": "border_clash",
+                "name": "Border Clash",
+                "description": "desc",
+                "scope": "bilateral",
+                "condition": "relation < 0 && military_ratio > 1.5",
+                "cooldown_minutes": 600,
+                "effects": [
+                    { "type": "adjust_metric", "metric": "approval", "delta": 2 },
+                    { "type": "report", "message": "{actor} が {target} を攻撃 ({country})" }
+                ],
+                "target_effects": [
+                    { "type": "adjust_metric", "metric": "stability", "delta": -4 },
+                    { "type": "report", "message": "{country} が {actor} に反撃" }
+                ]
+            }"#,
+        );
+        let template = compile_template(0, raw).expect("template should compile");
+        let mut engine = ScriptedEventEngine::with_templates(vec![template]);
+        let mut countries = vec![
+            sample_country("Alpha"),
+            sample_country("Beta"),
+            sample_country("Gamma"),
+        ];
+        countries[0].military = 90.0;
+        countries[0].relations.insert("Beta".to_string(), -20);
+        countries[1].relations.insert("Alpha".to_string(), -20);
+        countries[0].relations.insert("Gamma".to_string(), 30);
+
+        let reports = engine.execute(0, &mut countries, 0.0, &mut rng());
+        assert_eq!(
+            reports,
+            vec![
+                "Alpha が Beta を攻撃 (Alpha)".to_string(),
+                "Beta が Alpha に反撃".to_string()
+            ]
+        );
+        assert_eq!(countries[0].approval, 47.0);
+        assert_eq!(countries[1].stability, 46.0);
+        assert_eq!(countries[2].stability, 50.0);
+
+        // 向きを入れ替えた組も同じクールダウンに従う。
+        countries[1].military = 200.0;
+        assert!(
+            engine
+                .execute(0, &mut countries, 300.0, &mut rng())
+                .is_empty()
+        );
+        let reports = engine.execute(0, &mut countries, 600.0, &mut rng());
+        assert_eq!(reports[0], "Beta が Alpha を攻撃 (Beta)");
+    }
+
+    #[test]
+    fn bilateral_follow_ups_keep_the_pair() {
+        let clash = parse_raw(
+            r#"{
+                "id": "clash", "name": "Clash", "description": "d", "scope": "bilateral",
+                "condition": "relation < 0",
+                "effects": [{ "type": "schedule_event", "event": "talks", "delay_minutes": 60 }],
+                "target_effects": [{ "type": "schedule_event", "event": "protest", "delay_minutes": 30 }]
+            }"#,
+        );
+        let talks = parse_raw(
+            r#"{
+                "id": "talks", "name": "Talks", "description": "d", "scope": "bilateral",
+                "condition": "true", "auto_trigger": false,
+                "effects": [{ "type": "set_flag", "flag": "negotiating" }],
+                "target_effects": [{ "type": "set_flag", "flag": "negotiating" }]
+            }"#,
+        );
+        let protest = parse_raw(
+            r#"{
+                "id": "protest", "name": "Protest", "description": "d", "condition": "true",
+                "auto_trigger": false,
+                "effects": [{ "type": "schedule_event", "event": "talks", "delay_minutes": 10 }]
+            }"#,
+        );
+        let templates = vec![
+            compile_template(0, clash).expect("compile"),
+            compile_template(1, talks).expect("compile"),
+            compile_template(2, protest).expect("compile"),
+        ];
+        let mut engine = ScriptedEventEngine::with_templates(templates);
+        let err = engine
+            .validate_follow_ups()
+            .expect_err("単独テンプレートから二国間テンプレートは予約できない");
+        assert!(err.to_string().contains("protest"));
+
+        let mut countries = vec![sample_country("Alpha"), sample_country("Beta")];
+        countries[0].relations.insert("Beta".to_string(), -10);
+        engine.execute(0, &mut countries, 0.0, &mut rng());
+        let follow_ups = engine.take_follow_ups();
+        assert_eq!(follow_ups.len(), 2);
+        assert_eq!((follow_ups[0].country, follow_ups[0].target), (0, Some(1)));
+        assert_eq!((follow_ups[1].country, follow_ups[1].target), (1, Some(0)));
+
+        engine.execute_follow_up(1, 0, Some(1), &mut countries, 60.0, &mut rng());
+        assert!(countries[0].story().has_flag("negotiating"));
+        assert!(countries[1].story().has_flag("negotiating"));
+        assert!(
+            engine
+                .execute_follow_up(1, 0, None, &mut countries, 70.0, &mut rng())
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn single_country_templates_reject_target_references() {
+        let raw = parse_raw(
+            r#"{ "id": "x", "name": "x", "description": "d", "condition": "relation < 0" }"#,
+        );
+        let err = compile_template(0, raw).expect_err("scope が必要");
+        assert!(err.to_string().contains("bilateral"));
+        let raw = parse_raw(
+            r#"{ "id": "y", "name": "y", "description": "d", "condition": "true",
+                 "target_effects": [{ "type": "set_flag", "flag": "a" }] }"#,
+        );
+        assert!(compile_template(1, raw).is_err());
+    }
+
+    #[test]
+    fn targeting_modes_select_global_random_and_ranked_subjects() {
+        let global = parse_raw(
+            r#"{
+                "id": "global", "name": "Global", "description": "d",
+                "condition": "stability < 50", "cooldown_minutes": 600, "targeting": "global",
+                "effects": [
+                    { "type": "commodity_shock", "multiplier": 0.9 },
+                    { "type": "report", "message": "{count} か国で不安: {countries}" }
+                ]
+            }"#,
+        );
+        let random = parse_raw(
+            r#"{
+                "id": "random", "name": "Random", "description": "d",
+                "condition": "true", "cooldown_minutes": 600, "targeting": "random_one",
+                "effects": [{ "type": "adjust_variable", "variable": "hits", "delta": 1 }]
+            }"#,
+        );
+        let worst = parse_raw(
+            r#"{
+                "id": "worst", "name": "Worst", "description": "d", "condition": "true",
+                "targeting": "bottom_n", "rank_by": "stability", "target_count": 2,
+                "effects": [{ "type": "set_flag", "flag": "aid" }]
+            }"#,
+        );
+        let templates = vec![
+            compile_template(0, global).expect("compile"),
+            compile_template(1, random).expect("compile"),
+            compile_template(2, worst).expect("compile"),
+        ];
+        let mut engine = ScriptedEventEngine::with_templates(templates);
+        assert_eq!(engine.category(0), TaskCategory::International);
+        assert_eq!(engine.category(1), TaskCategory::Domestic);
+        let mut countries: Vec<_> = ["A", "B", "C", "D"]
+            .into_iter()
+            .zip([10.0, 40.0, 20.0, 70.0])
+            .map(|(name, stability)| {
+                let mut country = sample_country(name);
+                country.stability = stability;
+                country
+            })
+            .collect();
+        let mut rng = rng();
+
+        let reports = engine.execute(0, &mut countries, 0.0, &mut rng);
+        assert_eq!(reports, vec!["3 か国で不安: A、B、C".to_string()]);
+        assert_eq!(engine.take_commodity_shocks(), vec![0.9]);
+        assert!(
+            engine
+                .execute(0, &mut countries, 300.0, &mut rng)
+                .is_empty()
+        );
+
+        let hits = |countries: &[CountryState]| -> f64 {
+            countries
+                .iter()
+                .map(|country| country.story().variable("hits"))
+                .sum()
+        };
+        engine.execute(1, &mut countries, 0.0, &mut rng);
+        assert_eq!(hits(&countries), 1.0);
+        engine.execute(1, &mut countries, 300.0, &mut rng);
+        assert_eq!(
+            hits(&countries),
+            1.0,
+            "クールダウンはテンプレート全体で共有"
+        );
+        engine.execute(1, &mut countries, 600.0, &mut rng);
+        assert_eq!(hits(&countries), 2.0);
+
+        engine.execute(2, &mut countries, 0.0, &mut rng);
+        let flagged: Vec<_> = countries
+            .iter()
+            .filter(|country| country.story().has_flag("aid"))
+            .map(|country| country.name.as_str())
+            .collect();
+        assert_eq!(flagged, vec!["A", "C"]);
+    }
+
+    #[test]
+    fn invalid_targeting_is_rejected() {
+        for json in [
+            r#"{ "id": "a", "name": "a", "description": "d", "condition": "true",
+                 "targeting": "global",
+                 "effects": [{ "type": "adjust_metric", "metric": "approval", "delta": 1 }] }"#,
+            r#"{ "id": "b", "name": "b", "description": "d", "condition": "true",
+                 "targeting": "global", "scope": "bilateral" }"#,
+            r#"{ "id": "c", "name": "c", "description": "d", "condition": "true",
+                 "targeting": "top_n", "rank_by": "gdp", "target_count": 0 }"#,
+            r#"{ "id": "d", "name": "d", "description": "d", "condition": "true",
+                 "targeting": "top_n", "rank_by": "target.gdp" }"#,
+            r#"{ "id": "e", "name": "e", "description": "d", "condition": "true",
+                 "targeting": "top_n", "rank_by": "gdp +" }"#,
+        ] {
+            assert!(compile_template(0, parse_raw(json)).is_err(), "{}", json);
+        }
     }
 }
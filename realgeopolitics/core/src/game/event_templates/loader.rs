@@ -11,6 +11,58 @@ const BUILTIN_TEMPLATES: &[TemplateSource] = &[
         "resource_boom.json",
         include_str!("../../../../config/events/resource_boom.json"),
     ),
+    TemplateSource::Yaml(
+        "smog_crisis.yaml",
+        include_str!("../../../../config/events/smog_crisis.yaml"),
+    ),
+    TemplateSource::Yaml(
+        "energy_crisis.yaml",
+        include_str!("../../../../config/events/energy_crisis.yaml"),
+    ),
+    TemplateSource::Yaml(
+        "logistics_disruption.yaml",
+        include_str!("../../../../config/events/logistics_disruption.yaml"),
+    ),
+    TemplateSource::Yaml(
+        "civil_protest.yaml",
+        include_str!("../../../../config/events/civil_protest.yaml"),
+    ),
+    TemplateSource::Yaml(
+        "general_strike.yaml",
+        include_str!("../../../../config/events/general_strike.yaml"),
+    ),
+    TemplateSource::Yaml(
+        "government_collapse.yaml",
+        include_str!("../../../../config/events/government_collapse.yaml"),
+    ),
+    TemplateSource::Yaml(
+        "random_incidents.yaml",
+        include_str!("../../../../config/events/random_incidents.yaml"),
+    ),
+    TemplateSource::Yaml(
+        "border_clash.yaml",
+        include_str!("../../../../config/events/border_clash.yaml"),
+    ),
+    TemplateSource::Yaml(
+        "joint_venture.yaml",
+        include_str!("../../../../config/events/joint_venture.yaml"),
+    ),
+    TemplateSource::Yaml(
+        "diplomatic_scandal.yaml",
+        include_str!("../../../../config/events/diplomatic_scandal.yaml"),
+    ),
+    TemplateSource::Yaml(
+        "emergency_austerity.yaml",
+        include_str!("../../../../config/events/emergency_austerity.yaml"),
+    ),
+    TemplateSource::Yaml(
+        "global_inflation_scare.yaml",
+        include_str!("../../../../config/events/global_inflation_scare.yaml"),
+    ),
+    TemplateSource::Yaml(
+        "humanitarian_aid.yaml",
+        include_str!("../../../../config/events/humanitarian_aid.yaml"),
+    ),
 ];
 
 #[derive(Debug, Clone, Copy)]
@@ -110,13 +162,14 @@ fn parse_template(source: &TemplateSource) -> Result<EventTemplateRaw> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ScheduleSpec;
 
     #[test]
     fn load_builtin_templates_success() {
         let templates = load_event_templates().expect("built-in templates should load");
-        assert_eq!(templates.len(), 2);
+        assert_eq!(templates.len(), 15);
         assert_eq!(templates[0].id(), "debt_crisis");
-        assert_eq!(templates[0].check_minutes(), 180);
+        assert_eq!(templates[0].schedule(), ScheduleSpec::EveryMinutes(180));
         assert_eq!(templates[1].id(), "resource_boom");
         assert_eq!(templates[1].initial_delay_minutes(), 120);
     }
@@ -146,4 +199,31 @@ mod tests {
         let message = format!("{}", err);
         assert!(message.contains("check_minutes"));
     }
+
+    #[test]
+    fn load_from_sources_parses_calendar_schedules() {
+        let sources = [
+            TemplateSource::Yaml(
+                "fiscal.yaml",
+                "id: fiscal\nname: Fiscal\ndescription: d\ncondition: \"gdp > 0\"\nschedule: \"0 0 L 3 *\"\n",
+            ),
+            TemplateSource::Yaml(
+                "pulse.yaml",
+                "id: pulse\nname: Pulse\ndescription: d\ncondition: \"gdp > 0\"\nschedule: \"@every 6h\"\n",
+            ),
+        ];
+        let templates = load_from_sources(&sources).expect("schedules should parse");
+        assert_eq!(
+            templates[0].schedule(),
+            ScheduleSpec::Calendar(crate::CalendarRule::parse("0 0 L 3 *").unwrap())
+        );
+        assert_eq!(templates[1].schedule(), ScheduleSpec::EveryMinutes(360));
+
+        let sources = [TemplateSource::Yaml(
+            "broken.yaml",
+            "id: broken\nname: Broken\ndescription: d\ncondition: \"gdp > 0\"\nschedule: \"0 0 31 2 *\"\n",
+        )];
+        let err = load_from_sources(&sources).expect_err("impossible dates should be rejected");
+        assert!(format!("{:#}", err).contains("schedule"));
+    }
 }
@@ -3,6 +3,9 @@ mod condition;
 mod engine;
 mod formatter;
 mod loader;
+mod story;
 
+pub(crate) use condition::{matches_country_name, parse_world_condition};
 pub(crate) use engine::ScriptedEventEngine;
 pub(crate) use formatter::{ScriptedEventReport, format_reports};
+pub use story::StoryState;
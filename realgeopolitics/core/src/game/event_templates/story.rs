@@ -0,0 +1,53 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+/// イベントチェーンが国ごとに保持するフラグと変数。条件式から `flag.名前` / `var.名前` で参照する。
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StoryState {
+    flags: BTreeSet<String>,
+    variables: BTreeMap<String, f64>,
+}
+
+impl StoryState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn has_flag(&self, flag: &str) -> bool {
+        self.flags.contains(flag)
+    }
+
+    pub fn flags(&self) -> impl Iterator<Item = &str> {
+        self.flags.iter().map(String::as_str)
+    }
+
+    /// 未設定の変数は 0 として扱う。
+    pub fn variable(&self, name: &str) -> f64 {
+        self.variables.get(name).copied().unwrap_or(0.0)
+    }
+
+    pub fn variables(&self) -> impl Iterator<Item = (&str, f64)> {
+        self.variables
+            .iter()
+            .map(|(name, value)| (name.as_str(), *value))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.flags.is_empty() && self.variables.is_empty()
+    }
+
+    pub(crate) fn set_flag(&mut self, flag: &str) {
+        self.flags.insert(flag.to_string());
+    }
+
+    pub(crate) fn clear_flag(&mut self, flag: &str) {
+        self.flags.remove(flag);
+    }
+
+    pub(crate) fn set_variable(&mut self, name: &str, value: f64) {
+        self.variables.insert(name.to_string(), value);
+    }
+
+    pub(crate) fn adjust_variable(&mut self, name: &str, delta: f64) {
+        *self.variables.entry(name.to_string()).or_insert(0.0) += delta;
+    }
+}
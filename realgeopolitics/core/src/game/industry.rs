@@ -1,5 +1,6 @@
 #[cfg(test)]
 use std::collections::HashMap;
+use std::collections::HashSet;
 
 use anyhow::Result;
 
@@ -8,26 +9,72 @@ use crate::game::country::CountryState;
 use crate::game::economy::industry::SectorMetrics;
 use crate::game::economy::industry::SectorRegistry;
 use crate::game::economy::{
-    ExpenseKind, IndustryRuntime, IndustryTickOutcome, RevenueKind, SectorId, SectorOverview,
+    DependencyGraph, ExpenseKind, IndustryRuntime, IndustryTickOutcome, PowerGridStatus,
+    RevenueKind, SectorId, SectorOverview, SectorShock,
 };
+use crate::game::research::{TechEffect, TechnologyDefinition};
+
+/// インフラ支出のうち産業の資本ストックとして蓄積される割合。
+const PUBLIC_CAPITAL_SHARE: f64 = 0.5;
 
 pub(crate) struct IndustryEngine {
     runtime: IndustryRuntime,
+    applied_technologies: HashSet<String>,
 }
 
 impl IndustryEngine {
     pub fn new(runtime: IndustryRuntime) -> Self {
-        Self { runtime }
+        Self {
+            runtime,
+            applied_technologies: HashSet::new(),
+        }
+    }
+
+    /// 技術のセクター効果を適用する。産業は全世界共通のため、最初に完成した国の時点で一度だけ反映する。
+    pub fn apply_technology(&mut self, tech: &TechnologyDefinition) -> Result<Vec<String>> {
+        let mut reports = Vec::new();
+        if !self.applied_technologies.insert(tech.key.clone()) {
+            return Ok(reports);
+        }
+        for effect in &tech.effects {
+            if let TechEffect::UnlockSector { category, sector } = effect {
+                let name = sector.name.clone();
+                self.runtime.unlock_sector(*category, sector.clone())?;
+                reports.push(format!(
+                    "技術「{}」により新産業「{}」が誕生しました。",
+                    tech.name, name
+                ));
+            } else if let Some((id, efficiency, cost, emissions)) = effect.sector_adjustment() {
+                self.runtime
+                    .improve_sector(&id?, efficiency, cost, emissions)?;
+            }
+        }
+        Ok(reports)
     }
 
     pub fn overview(&self) -> Vec<SectorOverview> {
         self.runtime.overview()
     }
 
+    pub fn dependency_graph(&self, weighted: bool) -> DependencyGraph {
+        self.runtime.dependency_graph(weighted)
+    }
+
+    pub fn grid_status(&self) -> PowerGridStatus {
+        self.runtime.grid_status()
+    }
+
     pub fn sector_registry(&self) -> &SectorRegistry {
         self.runtime.registry()
     }
 
+    /// `category:key` またはキーで指定したセクターにイベントのショックを与える。
+    pub fn apply_sector_shock(&mut self, token: &str, shock: SectorShock) -> Result<SectorId> {
+        let id = self.sector_registry().resolve(token)?;
+        self.runtime.apply_shock(&id, shock)?;
+        Ok(id)
+    }
+
     pub fn apply_industry_subsidy(&mut self, token: &str, percent: f64) -> Result<SectorOverview> {
         let id = self.sector_registry().resolve(token)?;
         self.apply_industry_subsidy_by_id(&id, percent)
@@ -47,6 +94,16 @@ impl IndustryEngine {
         scale: f64,
         countries: &mut [CountryState],
     ) -> IndustryTickOutcome {
+        let infrastructure: f64 = countries
+            .iter()
+            .flat_map(|country| country.fiscal.expenses.iter())
+            .filter(|item| item.kind == ExpenseKind::Infrastructure)
+            .map(|item| item.amount)
+            .sum();
+        self.runtime
+            .invest_public(infrastructure * PUBLIC_CAPITAL_SHARE);
+        self.runtime
+            .set_carbon_price(average_carbon_price(countries));
         let outcome = self.runtime.simulate_tick(minutes, scale);
         self.distribute_outcome(&outcome, countries);
         outcome
@@ -83,6 +140,16 @@ impl IndustryEngine {
         self.runtime.metrics()
     }
 
+    #[cfg(test)]
+    pub(crate) fn runtime(&self) -> &IndustryRuntime {
+        &self.runtime
+    }
+
+    #[cfg(test)]
+    pub(crate) fn runtime_mut(&mut self) -> &mut IndustryRuntime {
+        &mut self.runtime
+    }
+
     #[cfg(test)]
     pub(crate) fn set_modifier_for_test(
         &mut self,
@@ -105,6 +172,18 @@ impl IndustryEngine {
     }
 }
 
+/// 産業ランタイムは全世界共通のため、各国の実効炭素価格の平均を適用する。
+fn average_carbon_price(countries: &[CountryState]) -> f64 {
+    if countries.is_empty() {
+        return 0.0;
+    }
+    countries
+        .iter()
+        .map(|country| country.environment().carbon_price())
+        .sum::<f64>()
+        / countries.len() as f64
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -117,9 +196,9 @@ mod tests {
             "Republic".to_string(),
             30.0,
             1500.0,
-            60,
-            55,
-            50,
+            60.0,
+            55.0,
+            50.0,
             70,
             FiscalAccount::new(300.0, CreditRating::BBB),
             TaxPolicy::default(),
@@ -213,6 +292,28 @@ mod tests {
         assert_eq!(follow_up.id, overview.id);
     }
 
+    #[test]
+    fn infrastructure_spending_builds_sector_capital() {
+        let catalog = IndustryCatalog::from_embedded().expect("catalog");
+        let mut idle = IndustryEngine::new(IndustryRuntime::from_catalog(catalog.clone()));
+        let mut funded = IndustryEngine::new(IndustryRuntime::from_catalog(catalog));
+        let mut idle_countries = vec![sample_country("Asteria")];
+        let mut funded_countries = vec![sample_country("Asteria")];
+        for _ in 0..24 {
+            funded_countries[0]
+                .fiscal_mut()
+                .record_expense(ExpenseKind::Infrastructure, 50_000.0);
+            idle.simulate_tick(60.0, 1.0, idle_countries.as_mut_slice());
+            funded.simulate_tick(60.0, 1.0, funded_countries.as_mut_slice());
+            funded_countries[0].fiscal_mut().clear_flows();
+        }
+        let capital = |engine: &IndustryEngine| -> f64 {
+            engine.overview().iter().map(|o| o.capital_stock).sum()
+        };
+        assert!(capital(&funded) > capital(&idle));
+        assert!(funded.overview().iter().any(|o| o.last_investment > 0.0));
+    }
+
     #[test]
     fn metrics_forward_to_runtime() {
         let catalog = IndustryCatalog::from_embedded().expect("catalog");
@@ -50,6 +50,11 @@ impl CommodityMarket {
         message
     }
 
+    /// イベントなどによる外生的な価格ショック。通常の変動と同じ範囲に収める。
+    pub(crate) fn apply_shock(&mut self, multiplier: f64) {
+        self.price = (self.price * multiplier).clamp(self.base_price * 0.4, self.base_price * 1.9);
+    }
+
     pub fn revenue_for(&self, resource_index: i32, scale: f64) -> f64 {
         let resources = resource_index.max(0) as f64;
         let export_volume = resources * 0.45;
@@ -6,6 +6,7 @@ mod economy;
 mod event_templates;
 mod industry;
 mod market;
+mod research;
 mod state;
 pub(crate) mod systems;
 mod time;
@@ -15,8 +16,14 @@ pub use bootstrap::GameBuilder;
 pub use country::{BudgetAllocation, CountryDefinition, CountryState};
 #[allow(unused_imports)]
 pub use economy::{
-    DependencyKind, FiscalSnapshot, FiscalTrendPoint, IndustryCatalog, IndustryCategory,
-    SectorDefinition, SectorDependency, SectorId, SectorOverview, SectorState, TaxPolicy,
-    TaxPolicyConfig,
+    BailoutConditions, BailoutOffer, BailoutProgram, BlackoutRecord, BondIssue, CarbonPolicy,
+    CatalogIssue, CatalogLint, ConsumerPriceIndex, CpiPoint, DebtStatus, DependencyGraph,
+    DependencyKind, EnvironmentState, FiscalSnapshot, FiscalTrendPoint, GraphEdge, GraphFormat,
+    GraphNode, IndustryCatalog, IndustryCategory, LintSeverity, MaturityBucket, PowerGridStatus,
+    RestructuringTerms, SectorDefinition, SectorDependency, SectorId, SectorOverview, SectorState,
+    SovereignDebt, TaxPolicy, TaxPolicyChange, TaxPolicyConfig, TaxRateKind, TaxSegment,
+    TaxTransition, TemporaryTaxChange,
 };
-pub use state::{GameState, TimeStatus};
+pub use event_templates::StoryState;
+pub use research::{ResearchState, TechEffect, TechTree, TechnologyDefinition};
+pub use state::{GameState, RunUntilOutcome, TimeStatus};
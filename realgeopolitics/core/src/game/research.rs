@@ -0,0 +1,446 @@
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+use anyhow::{Context, Result, anyhow, bail, ensure};
+use serde::Deserialize;
+
+use crate::game::economy::{IndustryCatalog, IndustryCategory, SectorDefinition, SectorId};
+
+const EMBEDDED_TECHNOLOGIES: &str = include_str!("../../../config/research/technologies.yaml");
+const MAX_TAX_COLLECTION_BONUS: f64 = 0.5;
+const MAX_ADMINISTRATION_REDUCTION: f64 = 0.8;
+
+/// 技術が完成したときに発生する恒久的な効果。セクターは `category:key` 形式で指定する。
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TechEffect {
+    SectorEfficiency {
+        sector: String,
+        multiplier: f64,
+    },
+    SectorCost {
+        sector: String,
+        multiplier: f64,
+    },
+    SectorEmissions {
+        sector: String,
+        multiplier: f64,
+    },
+    UnlockSector {
+        category: IndustryCategory,
+        sector: SectorDefinition,
+    },
+    /// 税収の捕捉率を上乗せする (0.05 で +5%)。
+    TaxCollection {
+        bonus: f64,
+    },
+    /// 必須行政経費を削減する (0.1 で -10%)。
+    AdministrationCost {
+        reduction: f64,
+    },
+}
+
+impl TechEffect {
+    /// セクターに対する効果であれば、対象セクターと (効率, コスト, 排出) の倍率を返す。
+    pub(crate) fn sector_adjustment(&self) -> Option<(Result<SectorId>, f64, f64, f64)> {
+        match self {
+            TechEffect::SectorEfficiency { sector, multiplier } => {
+                Some((parse_sector_token(sector), *multiplier, 1.0, 1.0))
+            }
+            TechEffect::SectorCost { sector, multiplier } => {
+                Some((parse_sector_token(sector), 1.0, *multiplier, 1.0))
+            }
+            TechEffect::SectorEmissions { sector, multiplier } => {
+                Some((parse_sector_token(sector), 1.0, 1.0, *multiplier))
+            }
+            _ => None,
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        match self {
+            TechEffect::SectorEfficiency { sector, multiplier }
+            | TechEffect::SectorCost { sector, multiplier }
+            | TechEffect::SectorEmissions { sector, multiplier } => {
+                parse_sector_token(sector)?;
+                ensure!(
+                    multiplier.is_finite() && *multiplier > 0.0,
+                    "セクター {} の倍率は正の数値で指定してください",
+                    sector
+                );
+            }
+            TechEffect::UnlockSector { sector, .. } => {
+                ensure!(
+                    !sector.key.trim().is_empty(),
+                    "解放するセクターのキーが空です"
+                );
+            }
+            TechEffect::TaxCollection { bonus } => {
+                ensure!(
+                    bonus.is_finite() && (0.0..1.0).contains(bonus),
+                    "税収捕捉率の上乗せは 0 以上 1 未満で指定してください"
+                );
+            }
+            TechEffect::AdministrationCost { reduction } => {
+                ensure!(
+                    reduction.is_finite() && (0.0..1.0).contains(reduction),
+                    "行政経費の削減率は 0 以上 1 未満で指定してください"
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TechnologyDefinition {
+    pub key: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub cost: f64,
+    #[serde(default)]
+    pub prerequisites: Vec<String>,
+    #[serde(default)]
+    pub effects: Vec<TechEffect>,
+}
+
+impl TechnologyDefinition {
+    pub fn prerequisites_met(&self, completed: &[String]) -> bool {
+        self.prerequisites
+            .iter()
+            .all(|prerequisite| completed.iter().any(|done| done == prerequisite))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TechnologyFile {
+    #[serde(default)]
+    technologies: Vec<TechnologyDefinition>,
+}
+
+/// YAML で定義された技術ツリー。
+#[derive(Debug, Clone, Default)]
+pub struct TechTree {
+    technologies: Vec<TechnologyDefinition>,
+}
+
+impl TechTree {
+    pub fn from_embedded() -> Result<Self> {
+        Self::from_yaml_str(EMBEDDED_TECHNOLOGIES)
+            .context("組み込み技術ツリーの読み込みに失敗しました")
+    }
+
+    pub fn from_yaml_str(content: &str) -> Result<Self> {
+        let file: TechnologyFile =
+            serde_yaml::from_str(content).context("技術ツリー YAML の解析に失敗しました")?;
+        let tree = Self {
+            technologies: file.technologies,
+        };
+        tree.validate()?;
+        Ok(tree)
+    }
+
+    pub fn technologies(&self) -> &[TechnologyDefinition] {
+        &self.technologies
+    }
+
+    pub fn get(&self, key: &str) -> Option<&TechnologyDefinition> {
+        self.technologies.iter().find(|tech| tech.key == key)
+    }
+
+    fn validate(&self) -> Result<()> {
+        let mut keys = HashSet::new();
+        for tech in &self.technologies {
+            ensure!(!tech.key.trim().is_empty(), "技術キーが空です");
+            ensure!(
+                keys.insert(tech.key.as_str()),
+                "技術キーが重複しています: {}",
+                tech.key
+            );
+            ensure!(
+                tech.cost.is_finite() && tech.cost > 0.0,
+                "技術 {} の研究コストは正の数値で指定してください",
+                tech.key
+            );
+            for effect in &tech.effects {
+                effect
+                    .validate()
+                    .with_context(|| format!("技術 {} の効果が不正です", tech.key))?;
+            }
+        }
+        for tech in &self.technologies {
+            for prerequisite in &tech.prerequisites {
+                ensure!(
+                    keys.contains(prerequisite.as_str()),
+                    "技術 {} の前提技術が存在しません: {}",
+                    tech.key,
+                    prerequisite
+                );
+            }
+        }
+        self.ensure_acyclic()
+    }
+
+    fn ensure_acyclic(&self) -> Result<()> {
+        // 0: 未訪問, 1: 探索中, 2: 完了
+        let mut marks: HashMap<&str, u8> = HashMap::new();
+        for tech in &self.technologies {
+            self.visit(tech.key.as_str(), &mut marks)?;
+        }
+        Ok(())
+    }
+
+    fn visit<'a>(&'a self, key: &'a str, marks: &mut HashMap<&'a str, u8>) -> Result<()> {
+        match marks.get(key).copied().unwrap_or(0) {
+            2 => return Ok(()),
+            1 => bail!("技術ツリーに循環参照があります: {}", key),
+            _ => {}
+        }
+        marks.insert(key, 1);
+        if let Some(tech) = self.get(key) {
+            for prerequisite in &tech.prerequisites {
+                self.visit(prerequisite.as_str(), marks)?;
+            }
+        }
+        marks.insert(key, 2);
+        Ok(())
+    }
+
+    /// ツリー内の技術によって解放されるセクター。
+    pub fn unlocked_sectors(&self) -> Vec<SectorId> {
+        self.technologies
+            .iter()
+            .flat_map(|tech| tech.effects.iter())
+            .filter_map(|effect| match effect {
+                TechEffect::UnlockSector { category, sector } => Some(sector.id(*category)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// 効果の対象セクターが既存カタログか、ツリー内で解放されるセクターであることを確認する。
+    pub(crate) fn validate_sectors(&self, catalog: &IndustryCatalog) -> Result<()> {
+        let mut unlocked = HashSet::new();
+        for tech in &self.technologies {
+            for effect in &tech.effects {
+                if let TechEffect::UnlockSector { category, sector } = effect {
+                    let id = sector.id(*category);
+                    ensure!(
+                        catalog.get(&id).is_none() && unlocked.insert(id.clone()),
+                        "技術 {} が解放するセクターは既に存在します: {}:{}",
+                        tech.key,
+                        id.category,
+                        id.key
+                    );
+                }
+            }
+        }
+        for tech in &self.technologies {
+            for effect in &tech.effects {
+                if let Some((id, _, _, _)) = effect.sector_adjustment() {
+                    let id = id?;
+                    ensure!(
+                        catalog.get(&id).is_some() || unlocked.contains(&id),
+                        "技術 {} の対象セクターが存在しません: {}:{}",
+                        tech.key,
+                        id.category,
+                        id.key
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_sector_token(token: &str) -> Result<SectorId> {
+    let (category, key) = token
+        .split_once(':')
+        .ok_or_else(|| anyhow!("セクターは category:key 形式で指定してください: {}", token))?;
+    let key = key.trim();
+    ensure!(!key.is_empty(), "セクターキーが空です: {}", token);
+    Ok(SectorId::new(IndustryCategory::from_str(category)?, key))
+}
+
+/// 国ごとの研究ポイント・研究の優先順位・完成済み技術。
+#[derive(Debug, Clone, Default)]
+pub struct ResearchState {
+    points: f64,
+    progress: HashMap<String, f64>,
+    priorities: Vec<String>,
+    completed: Vec<String>,
+    tax_collection_bonus: f64,
+    administration_cost_reduction: f64,
+}
+
+impl ResearchState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// まだどの技術にも投じられていない研究ポイント。
+    pub fn points(&self) -> f64 {
+        self.points
+    }
+
+    pub fn progress(&self, key: &str) -> f64 {
+        self.progress.get(key).copied().unwrap_or(0.0)
+    }
+
+    pub fn priorities(&self) -> &[String] {
+        &self.priorities
+    }
+
+    pub fn completed(&self) -> &[String] {
+        &self.completed
+    }
+
+    pub fn is_completed(&self, key: &str) -> bool {
+        self.completed.iter().any(|done| done == key)
+    }
+
+    pub fn tax_collection_bonus(&self) -> f64 {
+        self.tax_collection_bonus
+    }
+
+    pub fn administration_cost_multiplier(&self) -> f64 {
+        1.0 - self.administration_cost_reduction
+    }
+
+    /// 優先順位のうち、前提技術を満たして現在研究を進められる技術。
+    pub fn current_target<'a>(&self, tree: &'a TechTree) -> Option<&'a TechnologyDefinition> {
+        self.priorities
+            .iter()
+            .filter_map(|key| tree.get(key))
+            .find(|tech| !self.is_completed(&tech.key) && tech.prerequisites_met(&self.completed))
+    }
+
+    pub(crate) fn add_points(&mut self, amount: f64) {
+        if amount.is_finite() && amount > 0.0 {
+            self.points += amount;
+        }
+    }
+
+    pub(crate) fn set_priorities(
+        &mut self,
+        tree: &TechTree,
+        priorities: Vec<String>,
+    ) -> Result<()> {
+        let mut seen = HashSet::new();
+        for key in &priorities {
+            ensure!(tree.get(key).is_some(), "未知の技術です: {}", key);
+            ensure!(!self.is_completed(key), "技術 {} は既に研究済みです", key);
+            ensure!(seen.insert(key.as_str()), "技術 {} が重複しています", key);
+        }
+        self.priorities = priorities;
+        Ok(())
+    }
+
+    /// 蓄積した研究ポイントを優先順位に沿って投入し、完成した技術を返す。
+    pub(crate) fn advance(&mut self, tree: &TechTree) -> Vec<TechnologyDefinition> {
+        let mut finished = Vec::new();
+        while self.points > f64::EPSILON {
+            let Some(tech) = self.current_target(tree) else {
+                break;
+            };
+            let progress = self.progress.entry(tech.key.clone()).or_insert(0.0);
+            let applied = (tech.cost - *progress).max(0.0).min(self.points);
+            *progress += applied;
+            self.points -= applied;
+            if *progress + f64::EPSILON < tech.cost {
+                break;
+            }
+            self.complete(tech);
+            finished.push(tech.clone());
+        }
+        finished
+    }
+
+    fn complete(&mut self, tech: &TechnologyDefinition) {
+        self.progress.remove(&tech.key);
+        self.priorities.retain(|key| key != &tech.key);
+        self.completed.push(tech.key.clone());
+        for effect in &tech.effects {
+            match effect {
+                TechEffect::TaxCollection { bonus } => {
+                    self.tax_collection_bonus =
+                        (self.tax_collection_bonus + bonus).min(MAX_TAX_COLLECTION_BONUS);
+                }
+                TechEffect::AdministrationCost { reduction } => {
+                    let remaining = (1.0 - self.administration_cost_reduction) * (1.0 - reduction);
+                    self.administration_cost_reduction =
+                        (1.0 - remaining).min(MAX_ADMINISTRATION_REDUCTION);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embedded_tree_is_valid_against_catalog() {
+        let tree = TechTree::from_embedded().expect("tech tree");
+        let catalog = IndustryCatalog::from_embedded().expect("catalog");
+        tree.validate_sectors(&catalog).expect("sectors");
+        assert!(tree.get("digital_infrastructure").is_some());
+    }
+
+    #[test]
+    fn tree_rejects_cycles_and_unknown_prerequisites() {
+        let cyclic = r#"
+technologies:
+  - { key: a, name: A, cost: 10, prerequisites: [b] }
+  - { key: b, name: B, cost: 10, prerequisites: [a] }
+"#;
+        let err = TechTree::from_yaml_str(cyclic).unwrap_err();
+        assert!(format!("{err:#}").contains("循環"));
+        let missing = r#"
+technologies:
+  - { key: a, name: A, cost: 10, prerequisites: [ghost] }
+"#;
+        assert!(TechTree::from_yaml_str(missing).is_err());
+    }
+
+    #[test]
+    fn points_flow_through_priorities_respecting_prerequisites() {
+        let tree = TechTree::from_yaml_str(
+            r#"
+technologies:
+  - key: base
+    name: 基礎
+    cost: 100
+    effects:
+      - { type: tax_collection, bonus: 0.05 }
+  - key: advanced
+    name: 応用
+    cost: 200
+    prerequisites: [base]
+    effects:
+      - { type: administration_cost, reduction: 0.2 }
+"#,
+        )
+        .expect("tree");
+        let mut research = ResearchState::new();
+        research.add_points(150.0);
+        research
+            .set_priorities(&tree, vec!["advanced".into(), "base".into()])
+            .expect("priorities");
+        let done = research.advance(&tree);
+        assert_eq!(done.len(), 1);
+        assert_eq!(done[0].key, "base");
+        assert!((research.progress("advanced") - 50.0).abs() < 1e-9);
+        assert!((research.tax_collection_bonus() - 0.05).abs() < 1e-9);
+
+        research.add_points(200.0);
+        let done = research.advance(&tree);
+        assert_eq!(done[0].key, "advanced");
+        assert!((research.points() - 50.0).abs() < 1e-9);
+        assert!((research.administration_cost_multiplier() - 0.8).abs() < 1e-9);
+        assert!(research.set_priorities(&tree, vec!["base".into()]).is_err());
+    }
+}
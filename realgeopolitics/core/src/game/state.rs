@@ -1,20 +1,25 @@
-use anyhow::{Result, anyhow};
+use anyhow::{Result, anyhow, ensure};
 #[cfg(test)]
 use rand::SeedableRng;
 use rand::rngs::StdRng;
 
 use super::{
+    MAX_METRIC, MIN_METRIC,
     bootstrap::{GameBootstrap, GameBuilder},
     country::{BudgetAllocation, CountryDefinition, CountryState},
-    economy::{FiscalSnapshot, SectorId, SectorOverview},
-    event_templates::ScriptedEventEngine,
+    economy::{
+        CarbonPolicy, DependencyGraph, FiscalSnapshot, PowerGridStatus, RestructuringTerms,
+        SectorId, SectorOverview, TaxPolicyChange,
+    },
+    event_templates::{ScriptedEventEngine, matches_country_name, parse_world_condition},
     industry::IndustryEngine,
     market::CommodityMarket,
+    research::TechTree,
     systems::facade::SystemsFacade,
-    time::SimulationClock,
+    time::{SimulationClock, TickOutcome, TickSteps},
 };
 use crate::game::economy::industry::SectorRegistry;
-use crate::{CalendarDate, ScheduledTask};
+use crate::{CalendarDate, PeriodEnd, ScheduledTask, TaskCategory, TaskId, TaskKind};
 
 pub struct GameState {
     simulation_clock: SimulationClock,
@@ -23,7 +28,9 @@ pub struct GameState {
     commodity_market: CommodityMarket,
     scripted_events: ScriptedEventEngine,
     industry_engine: IndustryEngine,
+    technologies: TechTree,
     systems: SystemsFacade,
+    last_period_ends: Vec<PeriodEnd>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -34,6 +41,16 @@ pub struct TimeStatus {
     pub time_multiplier: f64,
 }
 
+/// `GameState::run_until` の結果。
+#[derive(Debug, Clone)]
+pub struct RunUntilOutcome {
+    pub reports: Vec<String>,
+    /// 実際に進めた実効分。
+    pub elapsed_minutes: f64,
+    /// 条件が成り立って止まったか。`false` なら上限まで進めて打ち切った。
+    pub satisfied: bool,
+}
+
 impl GameState {
     pub fn from_definitions(definitions: Vec<CountryDefinition>) -> Result<Self> {
         GameBuilder::new(definitions).build()
@@ -70,14 +87,19 @@ impl GameState {
     /// ```
     pub(crate) fn new(bootstrap: GameBootstrap) -> Self {
         let mut game = Self {
-            simulation_clock: SimulationClock::new(bootstrap.scheduler),
+            simulation_clock: SimulationClock::new(bootstrap.scheduler, bootstrap.start_date),
             rng: bootstrap.rng,
             countries: bootstrap.countries,
             commodity_market: bootstrap.commodity_market,
             scripted_events: bootstrap.scripted_events,
             industry_engine: bootstrap.industry_engine,
+            technologies: bootstrap.technologies,
             systems: SystemsFacade::new(),
+            last_period_ends: Vec::new(),
         };
+        for country in game.countries.iter_mut() {
+            country.fiscal_mut().sync_clock(0.0, bootstrap.start_date);
+        }
         game.capture_fiscal_history();
         game
     }
@@ -90,6 +112,15 @@ impl GameState {
         self.simulation_clock.calendar_date()
     }
 
+    pub fn start_date(&self) -> CalendarDate {
+        self.simulation_clock.start_date()
+    }
+
+    /// 直前の `tick_minutes` で締めを迎えた月末・四半期末・年末。
+    pub fn last_period_ends(&self) -> &[PeriodEnd] {
+        &self.last_period_ends
+    }
+
     pub fn commodity_price(&self) -> f64 {
         self.commodity_market.price()
     }
@@ -102,6 +133,14 @@ impl GameState {
         self.industry_engine.overview()
     }
 
+    pub fn industry_dependency_graph(&self, weighted: bool) -> DependencyGraph {
+        self.industry_engine.dependency_graph(weighted)
+    }
+
+    pub fn power_grid_status(&self) -> PowerGridStatus {
+        self.industry_engine.grid_status()
+    }
+
     pub fn sector_registry(&self) -> &SectorRegistry {
         self.industry_engine.sector_registry()
     }
@@ -127,6 +166,39 @@ impl GameState {
         self.simulation_clock.next_event_in_minutes()
     }
 
+    /// 予約済みタスクを実行時刻順に最大 `limit` 件返す。年表表示用。
+    pub fn upcoming_tasks(&self, limit: usize) -> Vec<ScheduledTask> {
+        self.simulation_clock
+            .upcoming_tasks()
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    /// 予約済みタスクを取り消す。該当するタスクがなければ `false`。
+    pub fn cancel_task(&mut self, id: TaskId) -> bool {
+        self.simulation_clock.cancel_task(id).is_some()
+    }
+
+    /// 国内・国際のタスクをカテゴリ単位で一時停止・再開する。停止中に期限を迎えたタスクは
+    /// 再開後に 1 回だけ実行される。
+    pub fn set_task_category_paused(&mut self, category: TaskCategory, paused: bool) {
+        self.simulation_clock.set_category_paused(category, paused);
+    }
+
+    pub fn is_task_category_paused(&self, category: TaskCategory) -> bool {
+        self.simulation_clock.is_category_paused(category)
+    }
+
+    /// 1 tick あたりに実行するカテゴリ内のタスク数を制限する。`None` で制限なし。
+    pub fn set_task_category_throttle(
+        &mut self,
+        category: TaskCategory,
+        limit: Option<usize>,
+    ) -> Result<()> {
+        self.simulation_clock.set_category_throttle(category, limit)
+    }
+
     pub fn time_status(&self) -> TimeStatus {
         TimeStatus {
             simulation_minutes: self.simulation_clock.simulation_minutes(),
@@ -251,38 +323,211 @@ impl GameState {
         Ok(())
     }
 
+    /// 税制変更を予約する。変更は日次の政策処理ごとに `phase_steps` 段階に分けて適用される。
+    pub fn update_tax_policy(&mut self, idx: usize, change: TaxPolicyChange) -> Result<()> {
+        let country = self
+            .countries
+            .get_mut(idx)
+            .ok_or_else(|| anyhow!("指定された国の番号が無効です: {}", idx + 1))?;
+        country.tax_policy_mut().schedule_change(change)
+    }
+
+    pub fn restructure_debt(&mut self, idx: usize, terms: RestructuringTerms) -> Result<()> {
+        let country = self
+            .countries
+            .get_mut(idx)
+            .ok_or_else(|| anyhow!("指定された国の番号が無効です: {}", idx + 1))?;
+        country.begin_debt_restructuring(terms)
+    }
+
+    /// 提示中の支援融資を受諾し、初回の融資額を返す。
+    pub fn accept_bailout(&mut self, idx: usize) -> Result<f64> {
+        let country = self
+            .countries
+            .get_mut(idx)
+            .ok_or_else(|| anyhow!("指定された国の番号が無効です: {}", idx + 1))?;
+        let tranche = country.accept_bailout()?;
+        country.approval = (country.approval - 3.0).clamp(MIN_METRIC, MAX_METRIC);
+        Ok(tranche)
+    }
+
+    pub fn decline_bailout(&mut self, idx: usize) -> Result<()> {
+        let country = self
+            .countries
+            .get_mut(idx)
+            .ok_or_else(|| anyhow!("指定された国の番号が無効です: {}", idx + 1))?;
+        country.decline_bailout()
+    }
+
+    pub fn technology_tree(&self) -> &TechTree {
+        &self.technologies
+    }
+
+    /// 研究の優先順位を置き換える。先頭から順に、前提技術を満たすものへ研究ポイントが投じられる。
+    pub fn set_research_priorities(&mut self, idx: usize, priorities: Vec<String>) -> Result<()> {
+        let country = self
+            .countries
+            .get_mut(idx)
+            .ok_or_else(|| anyhow!("指定された国の番号が無効です: {}", idx + 1))?;
+        country
+            .research_mut()
+            .set_priorities(&self.technologies, priorities)
+    }
+
+    /// 指定した技術を優先順位の先頭に移動する。
+    pub fn prioritize_research(&mut self, idx: usize, key: &str) -> Result<()> {
+        let country = self
+            .countries
+            .get(idx)
+            .ok_or_else(|| anyhow!("指定された国の番号が無効です: {}", idx + 1))?;
+        let mut priorities = vec![key.to_string()];
+        priorities.extend(
+            country
+                .research()
+                .priorities()
+                .iter()
+                .filter(|existing| existing.as_str() != key)
+                .cloned(),
+        );
+        self.set_research_priorities(idx, priorities)
+    }
+
+    pub fn set_carbon_policy(&mut self, idx: usize, policy: CarbonPolicy) -> Result<()> {
+        let country = self
+            .countries
+            .get_mut(idx)
+            .ok_or_else(|| anyhow!("指定された国の番号が無効です: {}", idx + 1))?;
+        country.environment_mut().set_policy(policy)
+    }
+
+    /// `minutes` (時間倍率を掛ける前) だけシミュレーションを進める。
+    ///
+    /// 進行は `TickSteps` で時計の正時ごとに区切る。タスクはステップごとに実行し、市場・各国の処理は
+    /// 正時に達したときにまとめて行う。1 日分を 1 回で進めても 1 時間ずつ 24 回進めても、
+    /// 90 分ずつ 2 回進めても 180 分を 1 回で進めても同じ結果になる。
     pub fn tick_minutes(&mut self, minutes: f64) -> Result<Vec<String>> {
-        let tick = self.simulation_clock.advance(minutes)?;
+        let effective_minutes = self.simulation_clock.effective_minutes(minutes)?;
+        Ok(self.run_for(effective_minutes, |_| false).reports)
+    }
+
+    /// 次に予定されたタスクの実行時刻までちょうど進める。時間倍率は掛けない。
+    pub fn advance_to_next_task(&mut self) -> Result<Vec<String>> {
+        let delay = self
+            .next_event_minutes()
+            .ok_or_else(|| anyhow!("予定されたタスクがありません"))?;
+        // 実行時刻を過ぎて待っているタスクは次の 1 分で実行される。
+        Ok(self.run_for(delay.max(1) as f64, |_| false).reports)
+    }
+
+    /// `date` の 0 時まで進める。時間倍率は掛けない。
+    pub fn run_until_date(&mut self, date: CalendarDate) -> Result<Vec<String>> {
+        let minutes = self.simulation_clock.minutes_until(date)?;
+        Ok(self.run_for(minutes, |_| false).reports)
+    }
+
+    /// 国名付きの条件式 (例: `Asteria.debt_ratio > 100`) が成り立つまで進める。
+    ///
+    /// 条件はステップごとに判定し、`max_minutes` (実効分) 進めても成り立たなければ打ち切る。
+    /// 国名の空白は `_` で書く。
+    pub fn run_until(&mut self, condition: &str, max_minutes: f64) -> Result<RunUntilOutcome> {
+        let condition = parse_world_condition(condition)?;
+        for name in condition.country_names() {
+            ensure!(
+                self.countries
+                    .iter()
+                    .any(|country| matches_country_name(&country.name, name)),
+                "条件式の国 '{}' が見つかりません",
+                name
+            );
+        }
+        ensure!(
+            max_minutes.is_finite() && max_minutes > 0.0,
+            "上限時間は正の値で指定してください"
+        );
+        if condition.evaluate(&self.countries) {
+            return Ok(RunUntilOutcome {
+                reports: Vec::new(),
+                elapsed_minutes: 0.0,
+                satisfied: true,
+            });
+        }
+        Ok(self.run_for(max_minutes, |game| condition.evaluate(&game.countries)))
+    }
+
+    /// 実効分 `effective_minutes` を `TickSteps` で区切って進める。`stop` が真を返したステップで止める。
+    fn run_for(
+        &mut self,
+        effective_minutes: f64,
+        mut stop: impl FnMut(&Self) -> bool,
+    ) -> RunUntilOutcome {
+        let mut outcome = RunUntilOutcome {
+            reports: Vec::new(),
+            elapsed_minutes: 0.0,
+            satisfied: false,
+        };
+        let mut ran_tasks = false;
+        let mut period_ends = Vec::new();
+        let start_minutes = self.simulation_clock.simulation_minutes();
+        for step in TickSteps::new(start_minutes, effective_minutes) {
+            let tick = self.simulation_clock.advance(step);
+            outcome.elapsed_minutes += step;
+            ran_tasks |= !tick.ready_tasks.is_empty();
+            period_ends.extend_from_slice(&tick.period_ends);
+            outcome.reports.extend(self.run_step(tick));
+            if stop(self) {
+                outcome.satisfied = true;
+                break;
+            }
+        }
+        if !ran_tasks {
+            outcome.reports.push(format!(
+                "{:.1} 分経過しましたが、スケジュールされた処理はありません。",
+                outcome.elapsed_minutes
+            ));
+        }
+        self.last_period_ends = period_ends;
+        outcome
+    }
+
+    fn run_step(&mut self, tick: TickOutcome) -> Vec<String> {
         let effective_minutes = tick.effective_minutes;
         let scale = tick.scale;
         let mut reports = Vec::new();
 
-        self.systems
-            .ensure_fiscal_prepared(&mut self.countries, scale);
+        let simulation_minutes = self.simulation_minutes();
+        let date = self.calendar_date();
+        for country in self.countries.iter_mut() {
+            country.fiscal_mut().sync_clock(simulation_minutes, date);
+        }
+        // 時間幅に比例する処理は正時ごとにまとめて進め、途中のステップではタスクだけを実行する。
+        if tick.hour_completed {
+            self.systems
+                .ensure_fiscal_prepared(&mut self.countries, scale);
 
-        if let Some(market_report) = self.commodity_market.update(&mut self.rng, scale) {
-            reports.push(market_report);
+            if let Some(market_report) = self.commodity_market.update(&mut self.rng, scale) {
+                reports.push(market_report);
+            }
+            reports.extend(
+                self.systems
+                    .expire_temporary_taxes(&mut self.countries, effective_minutes),
+            );
         }
 
-        if tick.ready_tasks.is_empty() {
-            reports.push(format!(
-                "{:.1} 分経過しましたが、スケジュールされた処理はありません。",
-                effective_minutes
-            ));
-        } else {
-            for task in tick.ready_tasks {
-                let mut task_reports = task.execute(self, scale);
-                if !task_reports.is_empty() {
-                    reports.append(&mut task_reports);
-                }
+        for task in tick.ready_tasks {
+            let mut task_reports = task.execute(self, scale);
+            if !task_reports.is_empty() {
+                reports.append(&mut task_reports);
             }
         }
+        if !tick.hour_completed {
+            self.systems.finish_fiscal_cycle();
+            return reports;
+        }
 
         for idx in 0..self.countries.len() {
             let mut country_reports = self.systems.apply_country_systems(
                 &mut self.countries,
                 &self.commodity_market,
-                &mut self.rng,
                 idx,
                 scale,
             );
@@ -291,20 +536,40 @@ impl GameState {
             }
         }
 
+        reports.extend(self.process_research());
         reports.extend(self.process_industry_tick(effective_minutes, scale));
+        for period in tick.period_ends {
+            reports.extend(self.close_period(period));
+        }
 
         self.capture_fiscal_history();
         self.systems.finish_fiscal_cycle();
-        Ok(reports)
+        reports
+    }
+
+    /// 締め日を越えたときの処理。四半期末と年末に各国の主要指標をまとめて報告する。
+    fn close_period(&mut self, period: PeriodEnd) -> Vec<String> {
+        if matches!(period, PeriodEnd::MonthEnd { .. }) {
+            return Vec::new();
+        }
+        let mut reports = vec![format!("{}の締めを迎えました。", period.label())];
+        for country in &self.countries {
+            reports.push(format!(
+                "  {}: GDP {:.1} / 予算残高 {:.1} / 安定 {:.0} / 支持 {:.0}",
+                country.name,
+                country.gdp,
+                country.cash_reserve(),
+                country.stability,
+                country.approval
+            ));
+        }
+        reports
     }
 
     pub(crate) fn process_economic_tick(&mut self, scale: f64) -> Vec<String> {
-        let reports = self.systems.process_economic_tick(
-            &mut self.countries,
-            &self.commodity_market,
-            &mut self.rng,
-            scale,
-        );
+        let reports =
+            self.systems
+                .process_economic_tick(&mut self.countries, &self.commodity_market, scale);
         self.capture_fiscal_history();
         reports
     }
@@ -321,6 +586,29 @@ impl GameState {
         self.systems.process_diplomatic_pulse(&mut self.countries)
     }
 
+    fn process_research(&mut self) -> Vec<String> {
+        let mut reports = Vec::new();
+        for idx in 0..self.countries.len() {
+            let finished = self.countries[idx]
+                .research_mut()
+                .advance(&self.technologies);
+            for tech in finished {
+                reports.push(format!(
+                    "{} が技術「{}」の研究を完了しました。",
+                    self.countries[idx].name, tech.name
+                ));
+                match self.industry_engine.apply_technology(&tech) {
+                    Ok(mut tech_reports) => reports.append(&mut tech_reports),
+                    Err(err) => reports.push(format!(
+                        "技術「{}」の効果を適用できませんでした: {}",
+                        tech.name, err
+                    )),
+                }
+            }
+        }
+        reports
+    }
+
     fn process_industry_tick(&mut self, minutes: f64, scale: f64) -> Vec<String> {
         if scale <= 0.0 {
             return Vec::new();
@@ -328,7 +616,24 @@ impl GameState {
         let outcome = self
             .industry_engine
             .simulate_tick(minutes, scale, &mut self.countries);
-        outcome.reports
+        let mut reports = outcome.reports;
+        let simulation_minutes = self.simulation_minutes();
+        reports.extend(self.systems.apply_household_prices(
+            &mut self.countries,
+            outcome.household_prices,
+            minutes,
+            simulation_minutes,
+            scale,
+        ));
+        reports.extend(self.systems.apply_emissions(
+            &mut self.countries,
+            outcome.total_emissions,
+            minutes,
+        ));
+        if let Some(blackout) = outcome.blackout {
+            reports.extend(self.systems.apply_blackout(&mut self.countries, blackout));
+        }
+        reports
     }
 
     /// Executes a scripted event template at the current simulation time and returns the textual reports produced by that execution.
@@ -351,8 +656,61 @@ impl GameState {
     /// ```
     pub(crate) fn process_scripted_event(&mut self, template_idx: usize) -> Vec<String> {
         let minutes = self.simulation_clock.simulation_minutes();
-        self.scripted_events
-            .execute(template_idx, &mut self.countries, minutes)
+        let reports =
+            self.scripted_events
+                .execute(template_idx, &mut self.countries, minutes, &mut self.rng);
+        self.finish_scripted_event(reports)
+    }
+
+    /// `schedule_event` で予約された後続イベントを対象国にだけ発生させる。
+    pub(crate) fn process_scripted_follow_up(
+        &mut self,
+        template_idx: usize,
+        country_idx: usize,
+        target_idx: Option<usize>,
+    ) -> Vec<String> {
+        let minutes = self.simulation_clock.simulation_minutes();
+        let reports = self.scripted_events.execute_follow_up(
+            template_idx,
+            country_idx,
+            target_idx,
+            &mut self.countries,
+            minutes,
+            &mut self.rng,
+        );
+        self.finish_scripted_event(reports)
+    }
+
+    /// イベントが残したセクター効果を適用し、後続イベントをスケジューラへ登録する。
+    fn finish_scripted_event(&mut self, mut reports: Vec<String>) -> Vec<String> {
+        for follow_up in self.scripted_events.take_follow_ups() {
+            self.simulation_clock.schedule_after(
+                TaskKind::ScriptedFollowUp {
+                    template: follow_up.template,
+                    country: follow_up.country,
+                    target: follow_up.target,
+                },
+                self.scripted_events.category(follow_up.template),
+                follow_up.delay_minutes,
+            );
+        }
+        for (sector, shock) in self.scripted_events.take_sector_shocks() {
+            if let Err(err) = self.industry_engine.apply_sector_shock(&sector, shock) {
+                reports.push(format!(
+                    "イベント効果をセクター {} に適用できませんでした: {}",
+                    sector, err
+                ));
+            }
+        }
+        for multiplier in self.scripted_events.take_commodity_shocks() {
+            self.commodity_market.apply_shock(multiplier);
+            reports.push(format!(
+                "イベントの影響で資源市場の価格が変動しました (倍率 x{:.2}, 現在値 {:.1})",
+                multiplier,
+                self.commodity_market.price()
+            ));
+        }
+        reports
     }
 
     /// Record a fiscal snapshot for every country at the simulation's current time.
@@ -384,11 +742,13 @@ impl ScheduledTask {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::game::economy::industry::{IndustryCatalog, IndustryRuntime};
-    use crate::game::economy::{CreditRating, ExpenseKind, FiscalAccount, RevenueKind};
+    use crate::game::economy::industry::{IndustryCatalog, IndustryRuntime, IndustryTickOutcome};
+    use crate::game::economy::{CreditRating, DebtStatus, ExpenseKind, FiscalAccount, RevenueKind};
     use crate::game::{IndustryCategory, SectorId};
-    use crate::scheduler::{ONE_YEAR_MINUTES, ScheduleSpec};
-    use crate::{GameClock, Scheduler, TaskKind};
+    use crate::scheduler::ScheduleSpec;
+    use crate::{GameClock, Scheduler, TaskCategory, TaskKind};
+
+    const ONE_YEAR_MINUTES: u64 = 365 * 24 * 60;
 
     fn sample_definitions() -> Vec<CountryDefinition> {
         serde_json::from_str::<Vec<CountryDefinition>>(
@@ -446,7 +806,7 @@ mod tests {
         );
         let country = &game.countries()[0];
         assert_ne!(country.fiscal.credit_rating, baseline_rating);
-        assert!(country.fiscal.debt > 400.0);
+        assert!(country.fiscal.debt() > 400.0);
         assert!(country.stability < baseline_stability);
     }
 
@@ -558,6 +918,334 @@ mod tests {
         assert!(ready_second[0].execute_at.minutes >= 60);
     }
 
+    #[test]
+    fn scheduler_cancels_reschedules_and_lists_upcoming_tasks() {
+        let mut scheduler = Scheduler::new();
+        let tick = scheduler.schedule(
+            ScheduledTask::new(TaskKind::EconomicTick, 5)
+                .with_schedule(ScheduleSpec::EveryMinutes(60)),
+        );
+        let policy = scheduler.schedule(ScheduledTask::new(
+            TaskKind::PolicyResolution,
+            ONE_YEAR_MINUTES + 120,
+        ));
+        let pulse = scheduler.schedule(ScheduledTask::new(TaskKind::DiplomaticPulse, 120));
+        let trigger = scheduler.schedule(ScheduledTask::new(TaskKind::EventTrigger, 120));
+        assert_ne!(tick, policy);
+
+        let order: Vec<_> = scheduler.upcoming().map(|task| task.id()).collect();
+        assert_eq!(order, vec![tick, pulse, trigger, policy]);
+
+        assert!(scheduler.reschedule(policy, 30));
+        assert_eq!(
+            scheduler
+                .upcoming()
+                .map(|task| task.execute_at.minutes)
+                .collect::<Vec<_>>(),
+            vec![5, 30, 120, 120]
+        );
+        let cancelled = scheduler.cancel(pulse).expect("pulse is queued");
+        assert_eq!(cancelled.kind, TaskKind::DiplomaticPulse);
+        assert!(scheduler.cancel(pulse).is_none());
+        assert!(!scheduler.reschedule(pulse, 10));
+
+        let mut clock = GameClock::new();
+        clock.advance_minutes(5.0);
+        let ready = scheduler.next_ready_tasks(&clock);
+        assert_eq!(ready[0].id(), tick);
+        // 再登録された繰り返しタスクは同じ ID で取り消せる。
+        assert_eq!(
+            scheduler.cancel(tick).map(|task| task.execute_at.minutes),
+            Some(65)
+        );
+
+        clock.advance_minutes(200.0);
+        let ready: Vec<_> = scheduler
+            .next_ready_tasks(&clock)
+            .iter()
+            .map(|task| task.kind)
+            .collect();
+        assert_eq!(
+            ready,
+            vec![TaskKind::PolicyResolution, TaskKind::EventTrigger]
+        );
+        assert!(scheduler.is_empty());
+    }
+
+    #[test]
+    fn large_ticks_match_many_small_ticks() {
+        fn snapshot(game: &GameState) -> String {
+            let countries: Vec<String> = game
+                .countries()
+                .iter()
+                .map(|country| {
+                    let mut relations: Vec<_> = country.relations.iter().collect();
+                    relations.sort();
+                    format!(
+                        "{} {:.6} {} {} {} {:.6} {:.6} {:?}",
+                        country.name,
+                        country.gdp,
+                        country.stability,
+                        country.approval,
+                        country.military,
+                        country.cash_reserve(),
+                        country.fiscal.debt(),
+                        relations
+                    )
+                })
+                .collect();
+            format!(
+                "{:?} {} {:.6} {:?}",
+                game.calendar_date(),
+                game.simulation_minutes(),
+                game.commodity_price(),
+                countries
+            )
+        }
+
+        let mut single = GameState::from_definitions_with_seed(sample_definitions(), 48).unwrap();
+        single.tick_minutes(24.0 * 60.0).expect("tick");
+
+        let mut hourly = GameState::from_definitions_with_seed(sample_definitions(), 48).unwrap();
+        for _ in 0..24 {
+            hourly.tick_minutes(60.0).expect("tick");
+        }
+        assert_eq!(snapshot(&single), snapshot(&hourly));
+
+        // 時間倍率で伸ばした進行も同じ長さのステップに区切られる。
+        let mut fast = GameState::from_definitions_with_seed(sample_definitions(), 48).unwrap();
+        fast.set_time_multiplier(4.0).expect("multiplier");
+        for _ in 0..3 {
+            fast.tick_minutes(120.0).expect("tick");
+        }
+        assert_eq!(snapshot(&fast), snapshot(&single));
+
+        // 正時で割り切れない進行を分けて呼んでも、正時ごとの処理は同じになる。
+        let mut whole = GameState::from_definitions_with_seed(sample_definitions(), 48).unwrap();
+        whole.tick_minutes(180.0).expect("tick");
+        let mut halves = GameState::from_definitions_with_seed(sample_definitions(), 48).unwrap();
+        halves.tick_minutes(90.0).expect("tick");
+        halves.tick_minutes(90.0).expect("tick");
+        assert_eq!(snapshot(&halves), snapshot(&whole));
+    }
+
+    #[test]
+    fn run_controls_stop_at_the_next_task_a_date_or_a_condition() {
+        let mut game = GameState::from_definitions_with_seed(sample_definitions(), 50).unwrap();
+        // どの操作も時間倍率に関係なく目標の時刻で止まる。
+        game.set_time_multiplier(3.0).unwrap();
+        let delay = game.next_event_minutes().expect("scheduled task");
+        game.advance_to_next_task().expect("next task");
+        assert_eq!(game.simulation_minutes(), delay.max(1) as f64);
+
+        let tomorrow = CalendarDate::new(2025, 1, 2);
+        game.run_until_date(tomorrow).expect("date");
+        assert_eq!(game.calendar_date(), tomorrow);
+        assert_eq!(game.simulation_minutes(), 24.0 * 60.0);
+        assert!(game.run_until_date(tomorrow).is_err());
+
+        let name = game.countries()[0].name.replace(' ', "_");
+        let gdp = game.countries()[0].gdp;
+        let outcome = game
+            .run_until(&format!("{name}.gdp != {gdp}"), 600.0)
+            .expect("condition");
+        assert!(outcome.satisfied);
+        assert_eq!(outcome.elapsed_minutes, 60.0);
+
+        let outcome = game
+            .run_until(&format!("{name}.stability > 100"), 150.0)
+            .expect("condition");
+        assert!(!outcome.satisfied);
+        assert_eq!(outcome.elapsed_minutes, 150.0);
+        assert_eq!(game.simulation_minutes(), 24.0 * 60.0 + 210.0);
+
+        let outcome = game
+            .run_until(&format!("{name}.stability >= 0"), 150.0)
+            .expect("condition");
+        assert!(outcome.satisfied && outcome.elapsed_minutes == 0.0);
+        assert!(game.run_until("Atlantis.gdp > 0", 60.0).is_err());
+        assert!(game.run_until(&format!("{name}.gdp > 0"), 0.0).is_err());
+    }
+
+    #[test]
+    fn start_date_drives_calendar_and_period_end_reports() {
+        let start = CalendarDate::new(2030, 12, 31);
+        let mut game = GameBuilder::new(sample_definitions())
+            .with_rng(StdRng::seed_from_u64(47))
+            .with_start_date(start)
+            .build()
+            .expect("game");
+        assert_eq!(game.start_date(), start);
+        assert_eq!(game.calendar_date(), start);
+
+        let mut reports = Vec::new();
+        for _ in 0..23 {
+            reports.extend(game.tick_minutes(60.0).expect("tick"));
+            assert!(game.last_period_ends().is_empty());
+        }
+        reports.extend(game.tick_minutes(60.0).expect("tick"));
+        assert_eq!(game.calendar_date(), CalendarDate::new(2031, 1, 1));
+        assert_eq!(
+            game.last_period_ends().last(),
+            Some(&PeriodEnd::YearEnd { year: 2030 })
+        );
+        assert!(reports.iter().any(|line| line.contains("2030年末の締め")));
+        assert!(
+            reports
+                .iter()
+                .any(|line| line.contains("2030年第4四半期末の締め"))
+        );
+
+        game.tick_minutes(60.0).expect("tick");
+        assert!(game.last_period_ends().is_empty());
+
+        let invalid = GameBuilder::new(sample_definitions())
+            .with_start_date(CalendarDate::new(2031, 2, 29))
+            .build();
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    fn calendar_schedules_follow_month_lengths() {
+        const DAY: u64 = 24 * 60;
+        let mut scheduler = Scheduler::new().with_epoch(CalendarDate::new(2027, 12, 1));
+        let month_end = scheduler
+            .schedule_recurring(
+                TaskKind::PolicyResolution,
+                ScheduleSpec::Monthly { day: 31 },
+                0,
+            )
+            .expect("month end exists");
+        let fiscal = scheduler
+            .schedule_recurring(TaskKind::EventTrigger, ScheduleSpec::fiscal_year_end(2), 0)
+            .expect("fiscal year end exists");
+        let timeline: Vec<_> = scheduler
+            .upcoming()
+            .map(|task| (task.id(), task.execute_at.minutes / DAY))
+            .collect();
+        assert_eq!(timeline, vec![(month_end, 30), (fiscal, 90)]);
+
+        let mut fired = Vec::new();
+        let mut clock = GameClock::new();
+        for _ in 0..100 {
+            clock.advance_minutes(DAY as f64);
+            for task in scheduler.next_ready_tasks(&clock) {
+                fired.push((task.kind, task.execute_at.minutes / DAY));
+            }
+        }
+        // 2027-12-31, 2028-01-31, 2028-02-29 (閏年)。同時刻は優先度順。
+        assert_eq!(
+            fired,
+            vec![
+                (TaskKind::PolicyResolution, 30),
+                (TaskKind::PolicyResolution, 61),
+                (TaskKind::PolicyResolution, 90),
+                (TaskKind::EventTrigger, 90),
+            ]
+        );
+
+        assert_eq!(
+            "@quarterly".parse::<ScheduleSpec>().unwrap(),
+            ScheduleSpec::Quarterly { day: 1 }
+        );
+        assert_eq!(
+            " @every 2d ".parse::<ScheduleSpec>().unwrap(),
+            ScheduleSpec::EveryMinutes(2 * DAY)
+        );
+        for invalid in [
+            "@every 0h",
+            "@every 5",
+            "@every 5分",
+            "@every 時h",
+            "@hourly",
+            "0 0 1 13 *",
+        ] {
+            assert!(invalid.parse::<ScheduleSpec>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn same_minute_tasks_run_by_priority_then_registration() {
+        let kinds = [
+            TaskKind::ScriptedEvent(1),
+            TaskKind::DiplomaticPulse,
+            TaskKind::ScriptedEvent(0),
+            TaskKind::EconomicTick,
+            TaskKind::PolicyResolution,
+        ];
+        let expected = vec![
+            TaskKind::EconomicTick,
+            TaskKind::PolicyResolution,
+            TaskKind::DiplomaticPulse,
+            TaskKind::ScriptedEvent(1),
+            TaskKind::ScriptedEvent(0),
+        ];
+        // 登録の経路 (期限切れでの登録・上位レベルからの振り直し) によらず同じ順に並ぶ。
+        for (advance_first, at) in [(0, 5_000), (6_000, 5_000), (0, 20)] {
+            let mut scheduler = Scheduler::new();
+            let mut clock = GameClock::new();
+            clock.advance_minutes(advance_first as f64);
+            scheduler.next_ready_tasks(&clock);
+            for kind in kinds {
+                scheduler.schedule(ScheduledTask::new(kind, at));
+            }
+            let listed: Vec<_> = scheduler.upcoming().map(|task| task.kind).collect();
+            assert_eq!(listed, expected);
+            clock.advance_minutes(6_000.0);
+            let ready: Vec<_> = scheduler
+                .next_ready_tasks(&clock)
+                .into_iter()
+                .map(|task| task.kind)
+                .collect();
+            assert_eq!(ready, expected);
+        }
+    }
+
+    #[test]
+    fn task_categories_can_be_paused_and_throttled() {
+        let mut scheduler = Scheduler::new();
+        scheduler.schedule(
+            ScheduledTask::new(TaskKind::DiplomaticPulse, 60)
+                .with_schedule(ScheduleSpec::EveryMinutes(60)),
+        );
+        for idx in 0..5 {
+            scheduler.schedule(ScheduledTask::new(TaskKind::ScriptedEvent(idx), 60));
+        }
+        scheduler.pause(TaskCategory::International);
+        scheduler
+            .set_throttle(TaskCategory::Domestic, Some(2))
+            .expect("valid throttle");
+        assert!(
+            scheduler
+                .set_throttle(TaskCategory::Domestic, Some(0))
+                .is_err()
+        );
+
+        let mut clock = GameClock::new();
+        let mut counts = Vec::new();
+        for _ in 0..3 {
+            clock.advance_minutes(120.0);
+            let ready = scheduler.next_ready_tasks(&clock);
+            assert!(
+                ready
+                    .iter()
+                    .all(|task| task.category == TaskCategory::Domestic)
+            );
+            counts.push(ready.len());
+        }
+        assert_eq!(counts, vec![2, 2, 1]);
+        // 停止中の繰り返しタスクは保留された 1 件だけで、次回分は積み上がらない。
+        assert_eq!(scheduler.len(), 1);
+
+        scheduler.resume(TaskCategory::International);
+        let ready = scheduler.next_ready_tasks(&clock);
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].kind, TaskKind::DiplomaticPulse);
+        assert_eq!(ready[0].execute_at.minutes, 360);
+        clock.advance_minutes(60.0);
+        assert_eq!(scheduler.next_ready_tasks(&clock).len(), 1);
+    }
+
     #[test]
     fn scheduled_task_economic_tick_applies_budget_effects() {
         let mut game = GameState::from_definitions_with_seed(sample_definitions(), 3).unwrap();
@@ -585,6 +1273,17 @@ mod tests {
         assert!(after_cash > before_cash);
     }
 
+    #[test]
+    fn industry_tick_updates_consumer_price_index() {
+        let mut game = GameState::from_definitions_with_seed(sample_definitions(), 42).unwrap();
+        game.tick_minutes(60.0).expect("tick");
+        let snapshot = game.fiscal_snapshot_of(0).expect("snapshot");
+        let prices = game.countries()[0].consumer_prices();
+        assert!(snapshot.cpi > 0.0);
+        assert!((snapshot.cpi - prices.cpi()).abs() < 1e-9);
+        assert_eq!(prices.history().len(), 1);
+    }
+
     #[test]
     fn energy_shortage_penalises_downstream_sectors() {
         let catalog = IndustryCatalog::from_embedded().expect("catalog");
@@ -593,26 +1292,31 @@ mod tests {
 
         let mut baseline_runtime = IndustryRuntime::from_catalog(catalog.clone());
         let baseline_outcome = baseline_runtime.simulate_tick(60.0, 1.0);
-        let baseline_cost = baseline_outcome
-            .sector_metrics
-            .get(&auto_id)
-            .map(|m| m.cost)
-            .unwrap_or(0.0);
+        let unit_cost = |outcome: &IndustryTickOutcome| {
+            outcome
+                .sector_metrics
+                .get(&auto_id)
+                .filter(|m| m.output > f64::EPSILON)
+                .map(|m| m.cost / m.output)
+                .unwrap_or(0.0)
+        };
+        let baseline_cost = unit_cost(&baseline_outcome);
         let baseline_cost_index = baseline_runtime.energy_cost_index();
 
         let mut shortage_runtime = IndustryRuntime::from_catalog(catalog);
         shortage_runtime.set_modifier_for_test(&energy_id, 0.0, -0.8, 120.0);
         let shortage_outcome = shortage_runtime.simulate_tick(60.0, 1.0);
-        let shortage_cost = shortage_outcome
-            .sector_metrics
-            .get(&auto_id)
-            .map(|m| m.cost)
-            .unwrap_or(0.0);
+        let shortage_cost = unit_cost(&shortage_outcome);
         let shortage_cost_index = shortage_runtime.energy_cost_index();
 
         assert!(
             shortage_cost >= baseline_cost - 1e-6,
-            "expected production cost ({shortage_cost}) to match or exceed baseline ({baseline_cost})"
+            "expected unit cost ({shortage_cost}) to match or exceed baseline ({baseline_cost})"
+        );
+        assert!(
+            shortage_outcome.sector_metrics[&auto_id].output
+                < baseline_outcome.sector_metrics[&auto_id].output,
+            "blackout should cut downstream output"
         );
         assert!(
             shortage_cost_index >= baseline_cost_index - 1e-6,
@@ -625,13 +1329,13 @@ mod tests {
         let mut game = GameState::from_definitions_with_seed(sample_definitions(), 4).unwrap();
         {
             let countries = game.countries_mut();
-            countries[0].stability = 30;
-            countries[0].approval = 50;
+            countries[0].stability = 30.0;
+            countries[0].approval = 50.0;
         }
         let task = ScheduledTask::new(TaskKind::EventTrigger, 0);
         let reports = task.execute(&mut game, 1.0);
         assert!(reports.iter().any(|r| r.contains("国民支持が低下しました")));
-        assert!(game.countries()[0].approval < 50);
+        assert!(game.countries()[0].approval < 50.0);
     }
 
     #[test]
@@ -652,6 +1356,80 @@ mod tests {
         assert!(game.countries()[0].gdp < before_gdp);
     }
 
+    #[test]
+    fn scheduled_policy_resolution_phases_in_tax_changes() {
+        let mut game = GameState::from_definitions_with_seed(sample_definitions(), 5).unwrap();
+        let change = TaxPolicyChange {
+            consumption_rate: Some(0.2),
+            phase_steps: 2,
+            ..TaxPolicyChange::default()
+        };
+        game.update_tax_policy(0, change)
+            .expect("schedule tax change");
+        assert!(game.update_tax_policy(9, change).is_err());
+        let before_approval = game.countries()[0].approval;
+
+        let task = ScheduledTask::new(TaskKind::PolicyResolution, 0);
+        let reports = task.execute(&mut game, 1.0);
+        assert!(reports.iter().any(|r| r.contains("税制改定")));
+        let tax = game.countries()[0].tax_policy();
+        assert!((tax.consumption_rate - 0.14).abs() < 1e-9);
+        assert_eq!(tax.transition().map(|t| t.remaining_steps), Some(1));
+
+        task.execute(&mut game, 1.0);
+        let tax = game.countries()[0].tax_policy();
+        assert!((tax.consumption_rate - 0.2).abs() < 1e-9);
+        assert!(tax.transition().is_none());
+        assert!(game.countries()[0].approval < before_approval);
+    }
+
+    #[test]
+    fn sovereign_default_leads_to_restructuring_and_recovery() {
+        let mut game = GameState::from_definitions_with_seed(sample_definitions(), 8).unwrap();
+        {
+            let country = &mut game.countries_mut()[0];
+            country.fiscal_mut().set_credit_rating(CreditRating::D);
+            country.fiscal_mut().add_debt(2_000.0);
+            country.fiscal_mut().set_cash_reserve(0.0);
+        }
+        assert!(
+            game.restructure_debt(0, RestructuringTerms::Austerity)
+                .is_err()
+        );
+        let relation_before = game.countries()[1].relations["Asteria"];
+
+        let task = ScheduledTask::new(TaskKind::PolicyResolution, 0);
+        let reports = task.execute(&mut game, 1.0);
+        assert!(reports.iter().any(|r| r.contains("デフォルト")));
+        let country = &game.countries()[0];
+        assert_eq!(country.sovereign_debt().status(), DebtStatus::Default);
+        assert!(!country.fiscal.has_market_access());
+        assert!(country.sovereign_debt().offer().is_some());
+        assert!(game.countries()[1].relations["Asteria"] < relation_before);
+
+        game.decline_bailout(0).expect("decline offer");
+        assert!(game.accept_bailout(0).is_err());
+        game.restructure_debt(0, RestructuringTerms::Austerity)
+            .expect("start restructuring");
+        let welfare_before = game.countries()[0].allocations().welfare;
+        let mut settled = false;
+        for _ in 0..30 {
+            let reports = task.execute(&mut game, 1.0);
+            settled |= reports
+                .iter()
+                .any(|r| r.contains("債務再編が緊縮条件付きで妥結"));
+        }
+        assert!(settled);
+        let country = &game.countries()[0];
+        assert_ne!(country.sovereign_debt().status(), DebtStatus::Default);
+        assert!(country.fiscal.has_market_access());
+        assert!(country.allocations().welfare < welfare_before);
+        assert_eq!(
+            country.fiscal_snapshot().debt_status,
+            country.sovereign_debt().status()
+        );
+    }
+
     #[test]
     fn scheduled_task_diplomatic_pulse_adjusts_relations() {
         let mut game = GameState::from_definitions_with_seed(sample_definitions(), 6).unwrap();
@@ -875,8 +1653,8 @@ mod tests {
         {
             let country = &mut game.countries_mut()[0];
             country.gdp = 1600.0;
-            country.stability = 42;
-            country.approval = 58;
+            country.stability = 42.0;
+            country.approval = 58.0;
             country.fiscal_mut().set_cash_reserve(280.0);
             country.fiscal_mut().add_debt(1500.0);
         }
@@ -885,7 +1663,7 @@ mod tests {
             (
                 country.stability,
                 country.approval,
-                country.fiscal.debt,
+                country.fiscal.debt(),
                 country.cash_reserve(),
             )
         };
@@ -894,8 +1672,8 @@ mod tests {
         let country = &game.countries()[0];
         assert!(country.stability < before.0);
         assert!(country.approval < before.1);
-        assert!(country.fiscal.debt > before.2);
-        assert!(country.cash_reserve() < before.3);
+        assert!((country.fiscal.debt() - before.2 - 0.09 * 1600.0).abs() < 1e-6);
+        assert!((before.3 - country.cash_reserve() - 0.075 * 1600.0).abs() < 1e-6);
         let second_reports = game.process_scripted_event(template_idx);
         assert!(second_reports.is_empty());
     }
@@ -913,8 +1691,8 @@ mod tests {
         {
             let country = &mut game.countries_mut()[1];
             country.resources = 96;
-            country.stability = 62;
-            country.approval = 54;
+            country.stability = 62.0;
+            country.approval = 54.0;
             country.gdp = 1700.0;
             country.fiscal_mut().set_cash_reserve(320.0);
         }
@@ -931,4 +1709,150 @@ mod tests {
         let second_reports = game.process_scripted_event(template_idx);
         assert!(second_reports.is_empty());
     }
+
+    #[test]
+    fn carbon_tax_collects_revenue_from_industry_emissions() {
+        let mut game = GameState::from_definitions_with_seed(sample_definitions(), 48).unwrap();
+        assert!(
+            game.set_carbon_policy(0, CarbonPolicy::Tax { price: 900.0 })
+                .is_err()
+        );
+        game.set_carbon_policy(0, CarbonPolicy::Tax { price: 30.0 })
+            .expect("炭素税設定");
+        game.tick_minutes(60.0).unwrap();
+        let carbon_revenue = |country: &CountryState| -> f64 {
+            country
+                .fiscal
+                .revenues
+                .iter()
+                .filter(|item| item.kind == RevenueKind::CarbonPricing)
+                .map(|item| item.amount)
+                .sum()
+        };
+        let taxed = &game.countries()[0];
+        assert!(taxed.environment().emissions_per_day() > 0.0);
+        assert!(carbon_revenue(taxed) > 0.0);
+        assert_eq!(carbon_revenue(&game.countries()[1]), 0.0);
+    }
+
+    #[test]
+    fn completed_research_unlocks_sector_and_improves_administration() {
+        let mut game = GameState::from_definitions_with_seed(sample_definitions(), 49).unwrap();
+        assert!(
+            game.set_research_priorities(0, vec!["warp_drive".to_string()])
+                .is_err()
+        );
+        game.set_research_priorities(0, vec!["digital_infrastructure".to_string()])
+            .expect("priorities");
+        game.prioritize_research(0, "digital_government")
+            .expect("prioritize");
+        assert_eq!(
+            game.countries()[0].research().priorities(),
+            ["digital_government", "digital_infrastructure"]
+        );
+        game.countries_mut()[0].research_mut().add_points(9_000.0);
+
+        let reports = game.tick_minutes(60.0).unwrap();
+        assert!(reports.iter().any(|r| r.contains("デジタル基盤")));
+        let research = game.countries()[0].research();
+        assert!(research.is_completed("digital_infrastructure"));
+        assert!(research.administration_cost_multiplier() < 1.0);
+        assert!(research.tax_collection_bonus() > 0.0);
+        assert!(
+            !game.countries()[1]
+                .research()
+                .is_completed("digital_government")
+        );
+
+        let ict = SectorId::new(IndustryCategory::Tertiary, "ict_services");
+        assert!(game.industry_overview().iter().any(|entry| entry.id == ict));
+        assert!(game.sector_registry().resolve("ict_services").is_ok());
+    }
+
+    #[test]
+    fn scripted_event_applies_sector_effects() {
+        fn sector_values(game: &GameState) -> (usize, f64, f64) {
+            let registry = game.sector_registry();
+            let runtime = game.industry_engine.runtime();
+            let logistics = registry.resolve("tertiary:logistics").unwrap();
+            let horticulture = registry.resolve("primary:horticulture").unwrap();
+            let automotive = registry.resolve("secondary:automotive").unwrap();
+            let modifiers = runtime.event_modifiers_for_test(&logistics);
+            assert!(
+                modifiers
+                    .iter()
+                    .all(|modifier| modifier.efficiency_bonus == -0.3)
+            );
+            (
+                modifiers.len(),
+                runtime.state_for_test(&horticulture).unwrap().inventory,
+                runtime
+                    .state_for_test(&automotive)
+                    .unwrap()
+                    .potential_demand,
+            )
+        }
+
+        // 発生した国の数によらず、世界共通の産業への効果は 1 度だけ適用される。
+        for troubled in [1, 2] {
+            let mut game = GameState::from_definitions_with_seed(sample_definitions(), 36).unwrap();
+            let idx = game
+                .scripted_events
+                .find_index("logistics_disruption")
+                .expect("template");
+            for (country_idx, country) in game.countries_mut().iter_mut().enumerate() {
+                country.stability = if country_idx < troubled { 20.0 } else { 80.0 };
+            }
+            let horticulture = game.sector_registry().resolve("horticulture").unwrap();
+            game.industry_engine
+                .runtime_mut()
+                .state_mut_for_test(&horticulture)
+                .unwrap()
+                .inventory = 50.0;
+            let (modifiers, inventory, demand) = sector_values(&game);
+            assert_eq!((modifiers, inventory), (0, 50.0));
+            assert!(demand > 0.0);
+
+            let reports = game.process_scripted_event(idx);
+            assert_eq!(reports.len(), troubled);
+            assert!(reports.iter().all(|line| line.contains("物流網が停滞")));
+            assert!(game.scripted_events.take_sector_shocks().is_empty());
+
+            let (modifiers_after, inventory_after, demand_after) = sector_values(&game);
+            assert_eq!(modifiers_after, 1);
+            assert!((inventory_after - inventory * 0.6).abs() < 1e-9);
+            assert!((demand_after - demand * 0.9).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn scripted_follow_up_runs_for_the_same_country_after_delay() {
+        let mut game = GameState::from_definitions_with_seed(sample_definitions(), 37).unwrap();
+        let idx = game
+            .scripted_events
+            .find_index("civil_protest")
+            .expect("template");
+        for country in game.countries_mut() {
+            country.approval = 80.0;
+        }
+        game.countries_mut()[0].approval = 10.0;
+        let mut allocations = game.allocations_of(0).unwrap();
+        allocations.infrastructure = 0.0;
+        allocations.welfare = 0.0;
+        game.update_allocations(0, allocations).unwrap();
+        let reports = game.process_scripted_event(idx);
+        assert_eq!(reports.len(), 1);
+        assert!(game.countries()[0].story().has_flag("protest_movement"));
+        assert!(game.countries()[1].story().is_empty());
+
+        let mut reports = Vec::new();
+        for _ in 0..24 {
+            reports.extend(game.tick_minutes(60.0).expect("tick"));
+        }
+        assert!(reports.iter().any(|line| line.contains("ゼネスト")));
+        let story = game.countries()[0].story();
+        assert!(!story.has_flag("protest_movement"));
+        assert!(story.has_flag("general_strike"));
+        assert_eq!(story.variable("unrest"), 3.0);
+    }
 }
@@ -88,25 +88,24 @@ fn clamp_relation(value: i32) -> i32 {
     value.clamp(MIN_RELATION, MAX_RELATION)
 }
 pub(crate) fn improve_relations(countries: &mut [CountryState], idx: usize, scale: f64) {
-    let delta_primary = (5.0 * scale) as i32;
-    let delta_secondary = (3.0 * scale) as i32;
+    shift_relations(countries, idx, 5.0 * scale, 3.0 * scale);
+}
 
-    for partner_idx in 0..countries.len() {
-        if partner_idx == idx {
-            continue;
-        }
-        adjust_bilateral_relation(countries, idx, partner_idx, delta_primary, delta_secondary);
-    }
+/// 軍事支出による関係悪化。1 時間あたり自国側 -2、相手国側 -1。
+pub(crate) fn penalise_after_military(countries: &mut [CountryState], idx: usize, scale: f64) {
+    shift_relations(countries, idx, -2.0 * scale, -scale);
 }
 
-pub(crate) fn penalise_after_military(countries: &mut [CountryState], idx: usize, delta: i32) {
-    if delta == 0 {
+/// `idx` の国と全ての相手国との関係値を動かす。整数に満たない変化は `idx` の国に持ち越す。
+fn shift_relations(countries: &mut [CountryState], idx: usize, own: f64, partner: f64) {
+    let (delta_own, delta_partner) = countries[idx].take_relation_deltas(own, partner);
+    if delta_own == 0 && delta_partner == 0 {
         return;
     }
     for partner_idx in 0..countries.len() {
         if partner_idx == idx {
             continue;
         }
-        adjust_bilateral_relation(countries, idx, partner_idx, delta, delta / 2);
+        adjust_bilateral_relation(countries, idx, partner_idx, delta_own, delta_partner);
     }
 }
@@ -0,0 +1,46 @@
+use crate::game::country::CountryState;
+use crate::game::economy::{ExpenseKind, RevenueKind};
+use crate::game::{MAX_METRIC, MIN_METRIC};
+
+/// 産業全体の排出量を各国へ均等に割り当て、炭素収入・保健支出・支持率への影響を反映する。
+pub(crate) fn apply_emissions(
+    countries: &mut [CountryState],
+    total_emissions: f64,
+    elapsed_minutes: f64,
+) -> Vec<String> {
+    let mut reports = Vec::new();
+    if countries.is_empty() || elapsed_minutes <= 0.0 {
+        return reports;
+    }
+    let share = total_emissions.max(0.0) / countries.len() as f64;
+    for country in countries.iter_mut() {
+        let gdp = country.gdp.max(0.0);
+        let outcome = country
+            .environment_mut()
+            .absorb(share, elapsed_minutes, gdp);
+        if outcome.carbon_revenue > 0.0 {
+            country
+                .fiscal_mut()
+                .record_revenue(RevenueKind::CarbonPricing, outcome.carbon_revenue);
+        }
+        if outcome.health_cost > 0.0 {
+            country
+                .fiscal_mut()
+                .record_expense(ExpenseKind::Health, outcome.health_cost);
+        }
+        if outcome.approval_penalty > 0.0 {
+            country.approval = clamp_metric(country.approval - outcome.approval_penalty);
+            reports.push(format!(
+                "{} で大気汚染が深刻化し、支持率が {:.1} 低下しました (汚染指数 {:.1})。",
+                country.name,
+                outcome.approval_penalty,
+                country.environment().pollution()
+            ));
+        }
+    }
+    reports
+}
+
+fn clamp_metric(value: f64) -> f64 {
+    value.clamp(MIN_METRIC, MAX_METRIC)
+}
@@ -2,7 +2,7 @@ use crate::game::country::CountryState;
 use crate::game::economy::{ExpenseKind, RevenueKind, TaxOutcome};
 use crate::game::market::CommodityMarket;
 use crate::game::systems::diplomacy;
-use crate::game::{MAX_METRIC, MAX_RESOURCES, MIN_METRIC, MIN_RESOURCES};
+use crate::game::{MAX_METRIC, MIN_METRIC};
 
 pub(crate) fn prepare_all_fiscal_flows(countries: &mut [CountryState], scale: f64) {
     if scale <= 0.0 {
@@ -35,9 +35,11 @@ pub(crate) fn apply_budget_effects(
         deferred,
     } = {
         let country = &mut countries[idx];
-        country
+        let mut outcome = country
             .tax_policy_mut()
-            .collect(gdp, employment_ratio, scale)
+            .collect(gdp, employment_ratio, scale);
+        outcome.immediate *= 1.0 + country.research().tax_collection_bonus();
+        outcome
     };
     if immediate > 0.0 {
         let country = &mut countries[idx];
@@ -93,7 +95,7 @@ pub(crate) fn apply_budget_effects(
             country
                 .fiscal_mut()
                 .record_expense(ExpenseKind::DebtService, actual);
-            let reduction = actual.min(country.fiscal_mut().debt);
+            let reduction = actual.min(country.fiscal_mut().debt());
             if reduction > 0.0 {
                 country.fiscal_mut().add_debt(-reduction);
             }
@@ -103,7 +105,7 @@ pub(crate) fn apply_budget_effects(
             ));
         } else if allocation.ensure_core_minimum {
             let country = &mut countries[idx];
-            country.fiscal_mut().add_debt(debt_desired * 0.25);
+            country.fiscal_mut().borrow(debt_desired * 0.25);
             reports.push(format!(
                 "{} は債務返済資金が不足し、返済を繰り延べました。",
                 country.name
@@ -125,7 +127,7 @@ pub(crate) fn apply_budget_effects(
             country
                 .fiscal_mut()
                 .record_expense(ExpenseKind::Administration, actual);
-            let stability_gain = (actual / 120.0).round() as i32;
+            let stability_gain = actual / 120.0;
             country.stability = clamp_metric(country.stability + stability_gain);
             reports.push(format!(
                 "{} は行政維持に {:.1} を投じています。",
@@ -133,7 +135,7 @@ pub(crate) fn apply_budget_effects(
             ));
         } else if allocation.ensure_core_minimum {
             let country = &mut countries[idx];
-            country.stability = clamp_metric(country.stability - 3);
+            country.stability = clamp_metric(country.stability - 3.0 * scale);
             reports.push(format!(
                 "{} は行政費の不足で行政効率が低下しています。",
                 country.name
@@ -151,10 +153,10 @@ pub(crate) fn apply_budget_effects(
                 .fiscal_mut()
                 .record_expense(ExpenseKind::Infrastructure, actual);
             country.gdp += actual * 0.9;
-            let intensity = (actual / 80.0).round() as i32;
+            let intensity = actual / 80.0;
             country.stability = clamp_metric(country.stability + intensity);
-            country.approval = clamp_metric(country.approval + (intensity / 2));
-            country.resources = clamp_resource(country.resources - (actual / 25.0) as i32);
+            country.approval = clamp_metric(country.approval + intensity / 2.0);
+            country.adjust_resources(-actual / 25.0);
             reports.push(format!(
                 "{} がインフラ投資を実施中です (支出 {:.1})",
                 country.name, actual
@@ -170,9 +172,9 @@ pub(crate) fn apply_budget_effects(
             country
                 .fiscal_mut()
                 .record_expense(ExpenseKind::Welfare, actual);
-            let intensity = (actual / 70.0).round() as i32;
+            let intensity = actual / 70.0;
             country.approval = clamp_metric(country.approval + intensity);
-            country.stability = clamp_metric(country.stability + (intensity / 2));
+            country.stability = clamp_metric(country.stability + intensity / 2.0);
             country.gdp = (country.gdp - actual * 0.25).max(0.0);
             reports.push(format!(
                 "{} が社会福祉を拡充しました (支出 {:.1})",
@@ -190,9 +192,9 @@ pub(crate) fn apply_budget_effects(
             country
                 .fiscal_mut()
                 .record_expense(ExpenseKind::Research, actual);
+            country.research_mut().add_points(actual);
             country.gdp += actual * 0.6;
-            let innovation = (actual / 90.0).round() as i32;
-            country.resources = clamp_resource(country.resources + innovation);
+            country.adjust_resources(actual / 90.0);
             reports.push(format!(
                 "{} は研究開発に {:.1} を投資しました。",
                 country.name, actual
@@ -232,14 +234,13 @@ pub(crate) fn apply_budget_effects(
                 country
                     .fiscal_mut()
                     .record_expense(ExpenseKind::Military, actual);
-                let intensity = (actual / 80.0).round() as i32;
+                let intensity = actual / 80.0;
                 country.military = clamp_metric(country.military + intensity);
-                country.stability = clamp_metric(country.stability + (intensity / 2));
-                country.approval = clamp_metric(country.approval - (intensity / 2));
-                country.resources = clamp_resource(country.resources - (actual / 40.0) as i32);
+                country.stability = clamp_metric(country.stability + intensity / 2.0);
+                country.approval = clamp_metric(country.approval - intensity / 2.0);
+                country.adjust_resources(-actual / 40.0);
             }
-            let relation_penalty = -((2.0 * scale.max(1.0)).round() as i32);
-            diplomacy::penalise_after_military(countries, idx, relation_penalty);
+            diplomacy::penalise_after_military(countries, idx, scale);
             reports.push(format!(
                 "{} が軍事強化に予算を充当しました (支出 {:.1})",
                 country_name, actual
@@ -253,8 +254,8 @@ fn estimate_employment_ratio(countries: &[CountryState], idx: usize) -> f64 {
     countries
         .get(idx)
         .map(|country| {
-            let stability_factor = country.stability as f64 / MAX_METRIC as f64;
-            let approval_factor = country.approval as f64 / MAX_METRIC as f64;
+            let stability_factor = country.stability / MAX_METRIC;
+            let approval_factor = country.approval / MAX_METRIC;
             ((stability_factor * 0.6) + (approval_factor * 0.4)).clamp(0.4, 1.2)
         })
         .unwrap_or(0.9)
@@ -262,18 +263,15 @@ fn estimate_employment_ratio(countries: &[CountryState], idx: usize) -> f64 {
 
 fn essential_debt_target(countries: &[CountryState], idx: usize) -> f64 {
     let country = &countries[idx];
-    (country.fiscal.debt * country.fiscal.interest_rate / 24.0).clamp(50.0, 300.0)
+    (country.fiscal.debt() * country.fiscal.interest_rate / 24.0).clamp(50.0, 300.0)
 }
 
 fn essential_administration_target(countries: &[CountryState], idx: usize) -> f64 {
     let country = &countries[idx];
     (country.population_millions * 2.0).max(35.0)
+        * country.research().administration_cost_multiplier()
 }
 
-fn clamp_metric(value: i32) -> i32 {
+fn clamp_metric(value: f64) -> f64 {
     value.clamp(MIN_METRIC, MAX_METRIC)
 }
-
-fn clamp_resource(value: i32) -> i32 {
-    value.clamp(MIN_RESOURCES, MAX_RESOURCES)
-}
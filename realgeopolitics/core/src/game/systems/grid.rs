@@ -0,0 +1,63 @@
+use crate::game::country::CountryState;
+use crate::game::economy::BlackoutRecord;
+use crate::game::{MAX_METRIC, MIN_METRIC};
+
+const APPROVAL_PER_OUTPUT_CUT: f64 = 10.0;
+const BLACKOUT_HOURS_PER_APPROVAL: f64 = 12.0;
+
+/// 計画停電の開始時に、規模と予定時間に応じて各国の支持率を下げる。
+pub(crate) fn apply_blackout(
+    countries: &mut [CountryState],
+    blackout: BlackoutRecord,
+) -> Vec<String> {
+    let hours = blackout.duration_minutes / 60.0;
+    let penalty = blackout.output_cut.max(0.0) * APPROVAL_PER_OUTPUT_CUT
+        + hours / BLACKOUT_HOURS_PER_APPROVAL;
+    let mut reports = Vec::new();
+    for country in countries.iter_mut() {
+        country.approval = clamp_metric(country.approval - penalty);
+        reports.push(format!(
+            "{} で計画停電が始まり、支持率が {:.1} 低下しました。",
+            country.name, penalty
+        ));
+    }
+    reports
+}
+
+fn clamp_metric(value: f64) -> f64 {
+    value.clamp(MIN_METRIC, MAX_METRIC)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::country::BudgetAllocation;
+    use crate::game::economy::{CreditRating, FiscalAccount, TaxPolicy};
+
+    #[test]
+    fn short_blackout_costs_a_fraction_of_approval() {
+        let mut countries = vec![CountryState::new(
+            "Asteria".to_string(),
+            "Republic".to_string(),
+            30.0,
+            1500.0,
+            60.0,
+            55.0,
+            50.0,
+            70,
+            FiscalAccount::new(300.0, CreditRating::BBB),
+            TaxPolicy::default(),
+            BudgetAllocation::default(),
+        )];
+        let blackout = BlackoutRecord {
+            started_at_minutes: 0.0,
+            duration_minutes: 60.0,
+            shortfall_ratio: 0.05,
+            output_cut: 0.02,
+        };
+        let reports = apply_blackout(&mut countries, blackout);
+        assert_eq!(reports.len(), 1);
+        let expected = 50.0 - (0.02 * APPROVAL_PER_OUTPUT_CUT + 1.0 / BLACKOUT_HOURS_PER_APPROVAL);
+        assert!((countries[0].approval - expected).abs() < 1e-9);
+    }
+}
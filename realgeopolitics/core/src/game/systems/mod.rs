@@ -1,6 +1,11 @@
 pub mod diplomacy;
+pub mod environment;
 pub mod events;
 pub mod facade;
 pub mod fiscal;
+pub mod grid;
 pub mod policy;
+pub mod prices;
+pub mod sovereign;
 pub mod tasks;
+pub mod taxation;
@@ -1,5 +1,6 @@
 use crate::game::country::CountryState;
 use crate::game::economy::{RevenueKind, downgrade_rating};
+use crate::game::systems::sovereign;
 use crate::game::{MAX_METRIC, MIN_METRIC};
 
 pub(crate) fn resolve(countries: &mut [CountryState]) -> Vec<String> {
@@ -9,13 +10,13 @@ pub(crate) fn resolve(countries: &mut [CountryState]) -> Vec<String> {
         let gdp = countries[idx].gdp.max(0.0);
 
         if allocation.ensure_core_minimum {
-            let min_debt = (countries[idx].fiscal.debt * countries[idx].fiscal.interest_rate
+            let min_debt = (countries[idx].fiscal.debt() * countries[idx].fiscal.interest_rate
                 / 360.0)
                 .max(40.0);
             let allocated_debt = (gdp * (allocation.debt_service / 100.0)).max(0.0);
             if allocated_debt + f64::EPSILON < min_debt {
                 let country = &mut countries[idx];
-                country.fiscal.add_debt(min_debt * 0.2);
+                country.fiscal.borrow(min_debt * 0.2);
                 let downgraded = downgrade_rating(country.fiscal.credit_rating);
                 country.fiscal.set_credit_rating(downgraded);
                 reports.push(format!(
@@ -28,7 +29,7 @@ pub(crate) fn resolve(countries: &mut [CountryState]) -> Vec<String> {
             let allocated_admin = (gdp * (allocation.administration / 100.0)).max(0.0);
             if allocated_admin + f64::EPSILON < admin_target {
                 let country = &mut countries[idx];
-                country.stability = clamp_metric(country.stability - 2);
+                country.stability = clamp_metric(country.stability - 2.0);
                 reports.push(format!(
                     "{} は行政維持費が不足し、行政効率が悪化しています。",
                     country.name
@@ -76,6 +77,12 @@ pub(crate) fn resolve(countries: &mut [CountryState]) -> Vec<String> {
                 countries[idx].name, outcome.principal_repaid
             ));
         }
+        if outcome.rolled_over > 0.0 {
+            reports.push(format!(
+                "{} は満期を迎えた国債 {:.1} のうち {:.1} を借り換えました。",
+                countries[idx].name, outcome.matured, outcome.rolled_over
+            ));
+        }
         if outcome.new_issuance > 0.0 {
             reports.push(format!(
                 "{} は新たに {:.1} を起債し、流動性を確保しました。",
@@ -88,9 +95,10 @@ pub(crate) fn resolve(countries: &mut [CountryState]) -> Vec<String> {
                 countries[idx].name, new_rating
             ));
         }
-        if let Some(alert) = outcome.crisis {
+        if let Some(alert) = outcome.crisis.clone() {
             reports.push(alert);
         }
+        reports.extend(sovereign::review_debt_status(countries, idx, &outcome));
     }
 
     reports
@@ -98,8 +106,9 @@ pub(crate) fn resolve(countries: &mut [CountryState]) -> Vec<String> {
 
 fn essential_administration_target(country: &CountryState) -> f64 {
     (country.population_millions * 2.0).max(35.0)
+        * country.research().administration_cost_multiplier()
 }
 
-fn clamp_metric(value: i32) -> i32 {
+fn clamp_metric(value: f64) -> f64 {
     value.clamp(MIN_METRIC, MAX_METRIC)
 }
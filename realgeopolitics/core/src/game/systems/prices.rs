@@ -0,0 +1,84 @@
+use crate::game::country::CountryState;
+use crate::game::economy::{HouseholdPrices, TaxRateKind};
+use crate::game::{MAX_METRIC, MIN_METRIC};
+
+pub(crate) fn apply_household_prices(
+    countries: &mut [CountryState],
+    prices: HouseholdPrices,
+    elapsed_minutes: f64,
+    simulation_minutes: f64,
+    scale: f64,
+) -> Vec<String> {
+    let mut reports = Vec::new();
+    for country in countries.iter_mut() {
+        let consumption_rate = country.tax_policy.effective_rate(TaxRateKind::Consumption);
+        let penalty = country.consumer_prices_mut().observe(
+            prices,
+            consumption_rate,
+            elapsed_minutes,
+            simulation_minutes,
+            scale,
+        );
+        if penalty > 0.0 {
+            country.approval = clamp_metric(country.approval - penalty);
+            reports.push(format!(
+                "{} でエネルギー価格が急騰し、支持率が {:.1} 低下しました (CPI {:.1})。",
+                country.name,
+                penalty,
+                country.consumer_prices().cpi()
+            ));
+        }
+    }
+    reports
+}
+
+fn clamp_metric(value: f64) -> f64 {
+    value.clamp(MIN_METRIC, MAX_METRIC)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::country::BudgetAllocation;
+    use crate::game::economy::{CreditRating, FiscalAccount, TaxPolicy};
+
+    fn sample_country(name: &str) -> CountryState {
+        CountryState::new(
+            name.to_string(),
+            "Republic".to_string(),
+            30.0,
+            1500.0,
+            60.0,
+            55.0,
+            50.0,
+            70,
+            FiscalAccount::new(300.0, CreditRating::BBB),
+            TaxPolicy::default(),
+            BudgetAllocation::default(),
+        )
+    }
+
+    #[test]
+    fn energy_spike_lowers_approval_for_every_country() {
+        let mut countries = vec![sample_country("Asteria"), sample_country("Borealis")];
+        let spike = HouseholdPrices {
+            overall: 1.2,
+            energy: 1.8,
+        };
+        let mut reports = Vec::new();
+        for step in 1..=4 {
+            reports.extend(apply_household_prices(
+                &mut countries,
+                spike,
+                60.0,
+                60.0 * step as f64,
+                1.0,
+            ));
+        }
+        assert!(!reports.is_empty());
+        for country in &countries {
+            assert!(country.approval < 50.0);
+            assert!(country.consumer_prices().cpi() > 100.0);
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use crate::game::country::CountryState;
+use crate::game::economy::{DebtCycleOutcome, RestructuringTerms, SovereignEvent};
+use crate::game::systems::diplomacy::adjust_bilateral_relation;
+use crate::game::{MAX_METRIC, MIN_METRIC};
+
+const AUSTERITY_SPENDING_CUT: f64 = 0.8;
+
+/// 日次の債務処理結果をもとに返済状況を更新し、国内外への影響を反映する。
+pub(crate) fn review_debt_status(
+    countries: &mut [CountryState],
+    idx: usize,
+    outcome: &DebtCycleOutcome,
+) -> Vec<String> {
+    let gdp = countries[idx].gdp.max(0.0);
+    let events = countries[idx].review_sovereign_debt(outcome, gdp);
+    let mut reports = Vec::new();
+    for event in events {
+        let name = countries[idx].name.clone();
+        let report = match event {
+            SovereignEvent::PaymentMissed { count, arrears } => {
+                let country = &mut countries[idx];
+                country.approval = clamp_metric(country.approval - 1.0);
+                format!(
+                    "{} は国債の支払いを履行できませんでした (遅延 {} 回目 / 延滞額 {:.1})。",
+                    name, count, arrears
+                )
+            }
+            SovereignEvent::PaymentsCured => {
+                format!("{} は延滞を解消し、債務返済が正常化しました。", name)
+            }
+            SovereignEvent::Defaulted => {
+                let country = &mut countries[idx];
+                country.approval = clamp_metric(country.approval - 8.0);
+                country.stability = clamp_metric(country.stability - 5.0);
+                adjust_all_relations(countries, idx, -10);
+                format!(
+                    "{} がデフォルトを宣言しました。国債市場へのアクセスを失い、債務再編の方針決定が必要です。",
+                    name
+                )
+            }
+            SovereignEvent::BailoutOffered { total } => {
+                let conditions = countries[idx]
+                    .sovereign_debt()
+                    .offer()
+                    .map(|offer| offer.conditions.describe())
+                    .unwrap_or_default();
+                format!(
+                    "{} に総額 {:.1} の支援融資が提案されました (条件: {})。",
+                    name, total, conditions
+                )
+            }
+            SovereignEvent::BailoutExpired => {
+                format!("{} への支援融資の提案は期限切れになりました。", name)
+            }
+            SovereignEvent::RestructuringSettled { terms, written_off } => {
+                apply_settlement(countries, idx, terms);
+                match terms {
+                    RestructuringTerms::Haircut => format!(
+                        "{} の債務再編が妥結し、元本 {:.1} が削減されました。市場への復帰が認められました。",
+                        name, written_off
+                    ),
+                    RestructuringTerms::Austerity => format!(
+                        "{} の債務再編が緊縮条件付きで妥結し、返済期限の延長と金利減免が認められました。",
+                        name
+                    ),
+                }
+            }
+            SovereignEvent::Upgraded(rating) => {
+                let country = &mut countries[idx];
+                country.approval = clamp_metric(country.approval + 1.0);
+                format!(
+                    "{} の返済実績が評価され、信用格付けが {:?} に引き上げられました。",
+                    name, rating
+                )
+            }
+            SovereignEvent::InvestmentGradeRestored => {
+                format!("{} は投資適格の格付けを回復しました。", name)
+            }
+            SovereignEvent::TrancheDisbursed { amount, remaining } => format!(
+                "{} に支援融資 {:.1} が実行されました (残り {} 回)。",
+                name, amount, remaining
+            ),
+            SovereignEvent::TrancheWithheld { remaining } => {
+                adjust_all_relations(countries, idx, -2);
+                format!(
+                    "{} は予算配分が支援条件を満たさず、融資の実行が見送られました (残り {} 回)。",
+                    name, remaining
+                )
+            }
+            SovereignEvent::ProgramCompleted => {
+                format!("{} の支援融資プログラムが終了しました。", name)
+            }
+        };
+        reports.push(report);
+    }
+    reports
+}
+
+fn apply_settlement(countries: &mut [CountryState], idx: usize, terms: RestructuringTerms) {
+    match terms {
+        RestructuringTerms::Haircut => {
+            let country = &mut countries[idx];
+            country.approval = clamp_metric(country.approval - 2.0);
+            adjust_all_relations(countries, idx, -12);
+        }
+        RestructuringTerms::Austerity => {
+            let country = &mut countries[idx];
+            country.approval = clamp_metric(country.approval - 10.0);
+            country.stability = clamp_metric(country.stability - 3.0);
+            let mut allocation = country.allocations();
+            allocation.welfare *= AUSTERITY_SPENDING_CUT;
+            allocation.infrastructure *= AUSTERITY_SPENDING_CUT;
+            country.set_allocations(allocation);
+        }
+    }
+}
+
+fn adjust_all_relations(countries: &mut [CountryState], idx: usize, delta: i32) {
+    for partner in 0..countries.len() {
+        if partner != idx {
+            adjust_bilateral_relation(countries, idx, partner, delta, delta);
+        }
+    }
+}
+
+fn clamp_metric(value: f64) -> f64 {
+    value.clamp(MIN_METRIC, MAX_METRIC)
+}
@@ -8,5 +8,10 @@ pub(crate) fn execute(task: &ScheduledTask, game: &mut GameState, scale: f64) ->
         TaskKind::PolicyResolution => game.process_policy_resolution(),
         TaskKind::DiplomaticPulse => game.process_diplomatic_pulse(),
         TaskKind::ScriptedEvent(template_idx) => game.process_scripted_event(template_idx),
+        TaskKind::ScriptedFollowUp {
+            template,
+            country,
+            target,
+        } => game.process_scripted_follow_up(template, country, target),
     }
 }
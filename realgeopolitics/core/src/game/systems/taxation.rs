@@ -0,0 +1,66 @@
+use crate::game::country::CountryState;
+use crate::game::economy::TaxRateKind;
+use crate::game::{MAX_METRIC, MIN_METRIC};
+
+pub(crate) fn advance_tax_transitions(countries: &mut [CountryState]) -> Vec<String> {
+    let mut reports = Vec::new();
+    for country in countries.iter_mut() {
+        let step = match country.tax_policy_mut().advance_transition() {
+            Some(step) => step,
+            None => continue,
+        };
+        country.gdp = (country.gdp * step.gdp_multiplier).max(0.0);
+        country.approval = clamp_metric(country.approval + step.approval_delta as f64);
+
+        let reactions: Vec<String> = step
+            .segments
+            .iter()
+            .filter(|(_, impact)| impact.abs() >= 0.05)
+            .map(|(segment, impact)| format!("{} {:+.1}", segment.label(), impact))
+            .collect();
+        let tax = country.tax_policy();
+        let mut report = format!(
+            "{} の税制改定が進行しました (所得 {:.1}% / 法人 {:.1}% / 消費 {:.1}% / 控除 {:.1}",
+            country.name,
+            tax.effective_rate(TaxRateKind::Income) * 100.0,
+            tax.effective_rate(TaxRateKind::Corporate) * 100.0,
+            tax.effective_rate(TaxRateKind::Consumption) * 100.0,
+            tax.deductions
+        );
+        if step.remaining_steps > 0 {
+            report.push_str(&format!(", 残り {} 段階)", step.remaining_steps));
+        } else {
+            report.push_str(", 移行完了)");
+        }
+        if !reactions.is_empty() {
+            report.push_str(&format!(" 世論反応: {}", reactions.join(" / ")));
+        }
+        reports.push(report);
+    }
+    reports
+}
+
+/// イベントで一時的に変更された税率のうち、期限を迎えたものを元に戻す。
+pub(crate) fn expire_temporary_taxes(
+    countries: &mut [CountryState],
+    elapsed_minutes: f64,
+) -> Vec<String> {
+    let mut reports = Vec::new();
+    for country in countries.iter_mut() {
+        for kind in country
+            .tax_policy_mut()
+            .expire_temporary_changes(elapsed_minutes)
+        {
+            reports.push(format!(
+                "{} の{}率の一時変更が期限を迎え、元に戻りました。",
+                country.name,
+                kind.label()
+            ));
+        }
+    }
+    reports
+}
+
+fn clamp_metric(value: f64) -> f64 {
+    value.clamp(MIN_METRIC, MAX_METRIC)
+}
@@ -1,30 +1,42 @@
 use anyhow::{Result, ensure};
 
-use super::{BASE_TICK_MINUTES, MINUTES_PER_DAY};
-use crate::{CalendarDate, GameClock, ScheduledTask, Scheduler};
+use super::{BASE_TICK_MINUTES, MAX_STEP_MINUTES, MINUTES_PER_DAY};
+use crate::{
+    CalendarDate, GameClock, PeriodEnd, ScheduledTask, Scheduler, TaskCategory, TaskId, TaskKind,
+};
 
 pub(crate) struct SimulationClock {
     clock: GameClock,
+    start_date: CalendarDate,
     calendar: CalendarDate,
     day_progress_minutes: u32,
     time_multiplier: f64,
     scheduler: Scheduler,
+    /// 直前の正時から進めた実効分。正時に達したときにまとめて返す。
+    pending_step_minutes: f64,
 }
 
 pub(crate) struct TickOutcome {
+    /// 直前の正時からこのステップまでに進めた実効分。
     pub effective_minutes: f64,
     pub scale: f64,
+    /// このステップで正時に達したか。時間幅に比例する処理は正時ごとにまとめて進める。
+    pub hour_completed: bool,
     pub ready_tasks: Vec<ScheduledTask>,
+    /// この進行で締め日を越えた月末・四半期末・年末。古い順に並ぶ。
+    pub period_ends: Vec<PeriodEnd>,
 }
 
 impl SimulationClock {
-    pub fn new(scheduler: Scheduler) -> Self {
+    pub fn new(scheduler: Scheduler, start_date: CalendarDate) -> Self {
         Self {
             clock: GameClock::new(),
-            calendar: CalendarDate::from_start(),
+            start_date,
+            calendar: start_date,
             day_progress_minutes: 0,
             time_multiplier: 1.0,
             scheduler,
+            pending_step_minutes: 0.0,
         }
     }
 
@@ -41,6 +53,10 @@ impl SimulationClock {
         Ok(())
     }
 
+    pub fn start_date(&self) -> CalendarDate {
+        self.start_date
+    }
+
     pub fn calendar_date(&self) -> CalendarDate {
         self.calendar
     }
@@ -56,24 +72,93 @@ impl SimulationClock {
             .map(|next| next.saturating_sub(current))
     }
 
-    pub fn advance(&mut self, minutes: f64) -> Result<TickOutcome> {
+    pub fn schedule_after(
+        &mut self,
+        kind: TaskKind,
+        category: TaskCategory,
+        delay_minutes: u64,
+    ) -> TaskId {
+        let execute_at = self.clock.total_minutes() + delay_minutes;
+        self.scheduler
+            .schedule(ScheduledTask::new(kind, execute_at).with_category(category))
+    }
+
+    pub fn set_category_paused(&mut self, category: TaskCategory, paused: bool) {
+        if paused {
+            self.scheduler.pause(category);
+        } else {
+            self.scheduler.resume(category);
+        }
+    }
+
+    pub fn is_category_paused(&self, category: TaskCategory) -> bool {
+        self.scheduler.is_paused(category)
+    }
+
+    pub fn set_category_throttle(
+        &mut self,
+        category: TaskCategory,
+        limit: Option<usize>,
+    ) -> Result<()> {
+        self.scheduler.set_throttle(category, limit)
+    }
+
+    pub fn cancel_task(&mut self, id: TaskId) -> Option<ScheduledTask> {
+        self.scheduler.cancel(id)
+    }
+
+    pub fn upcoming_tasks(&self) -> impl Iterator<Item = &ScheduledTask> {
+        self.scheduler.upcoming()
+    }
+
+    /// `minutes` を検証し、時間倍率を掛けた実効分を返す。
+    pub fn effective_minutes(&self, minutes: f64) -> Result<f64> {
         ensure!(minutes.is_finite(), "時間が不正です");
         ensure!(minutes > 0.0, "時間は正の値で指定してください");
-
         let effective_minutes = minutes * self.time_multiplier;
-        let advanced_minutes = self.clock.advance_minutes(effective_minutes);
-        self.update_calendar(advanced_minutes);
+        ensure!(effective_minutes.is_finite(), "時間が不正です");
+        Ok(effective_minutes)
+    }
+
+    /// `date` の 0 時までの実効分。`date` は現在の日付より後でなければならない。
+    pub fn minutes_until(&self, date: CalendarDate) -> Result<f64> {
+        date.validate()?;
+        ensure!(
+            date > self.calendar,
+            "{} より後の日付を指定してください",
+            self.calendar
+        );
+        let days = self.calendar.days_until(date) as u64;
+        Ok((days * MINUTES_PER_DAY - self.day_progress_minutes as u64) as f64)
+    }
+
+    /// `TickSteps` で区切った 1 ステップ分だけ時計を進め、実行時刻を迎えたタスクを返す。
+    pub fn advance(&mut self, step_minutes: f64) -> TickOutcome {
+        let advanced_minutes = self.clock.advance_minutes(step_minutes);
+        let period_ends = self.update_calendar(advanced_minutes);
+        let effective_minutes = self.pending_step_minutes + step_minutes;
+        let hour_completed = self
+            .clock
+            .total_minutes()
+            .is_multiple_of(MAX_STEP_MINUTES as u64);
+        self.pending_step_minutes = if hour_completed {
+            0.0
+        } else {
+            effective_minutes
+        };
         let scale = effective_minutes / BASE_TICK_MINUTES;
         let ready_tasks = self.scheduler.next_ready_tasks(&self.clock);
 
-        Ok(TickOutcome {
+        TickOutcome {
             effective_minutes,
             scale,
+            hour_completed,
             ready_tasks,
-        })
+            period_ends,
+        }
     }
 
-    fn update_calendar(&mut self, advanced_minutes: u64) {
+    fn update_calendar(&mut self, advanced_minutes: u64) -> Vec<PeriodEnd> {
         let mut total_days = advanced_minutes / MINUTES_PER_DAY;
         let remainder = advanced_minutes % MINUTES_PER_DAY;
         self.day_progress_minutes += remainder as u32;
@@ -81,9 +166,53 @@ impl SimulationClock {
             total_days += (self.day_progress_minutes as u64) / MINUTES_PER_DAY;
             self.day_progress_minutes %= MINUTES_PER_DAY as u32;
         }
-        if total_days > 0 {
-            self.calendar.advance_days(total_days);
+        // 月末ごとに区切って進め、越えた締め日を記録する。
+        let mut period_ends = Vec::new();
+        while total_days > 0 {
+            let to_month_end = (self.calendar.month_length() - self.calendar.day) as u64;
+            if total_days <= to_month_end {
+                self.calendar.advance_days(total_days);
+                break;
+            }
+            self.calendar.advance_days(to_month_end);
+            period_ends.extend(PeriodEnd::closing_on(self.calendar));
+            self.calendar.advance_days(1);
+            total_days -= to_month_end + 1;
+        }
+        period_ends
+    }
+}
+
+/// 1 回の進行を時計上の `MAX_STEP_MINUTES` の倍数 (毎正時) で区切った実効分の列。
+///
+/// 区切りを呼び出しの先頭ではなく時計に揃えるので、進行をどう分けて呼んでも同じ時刻でステップが切れる。
+pub(crate) struct TickSteps {
+    position: f64,
+    end: f64,
+}
+
+impl TickSteps {
+    pub fn new(start_minutes: f64, effective_minutes: f64) -> Self {
+        Self {
+            position: start_minutes,
+            end: start_minutes + effective_minutes,
+        }
+    }
+}
+
+impl Iterator for TickSteps {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<f64> {
+        // 浮動小数の引き算で残る端数は切り捨てる。
+        if self.end - self.position <= 1e-9 {
+            return None;
         }
+        let boundary = ((self.position / MAX_STEP_MINUTES).floor() + 1.0) * MAX_STEP_MINUTES;
+        let next = boundary.min(self.end);
+        let step = next - self.position;
+        self.position = next;
+        Some(step)
     }
 }
 
@@ -93,7 +222,7 @@ mod tests {
 
     #[test]
     fn set_time_multiplier_rejects_non_positive() {
-        let mut clock = SimulationClock::new(Scheduler::new());
+        let mut clock = SimulationClock::new(Scheduler::new(), CalendarDate::from_start());
         let err = clock
             .set_time_multiplier(0.0)
             .expect_err("0.0 should be rejected");
@@ -105,29 +234,114 @@ mod tests {
     }
 
     #[test]
-    fn advance_rejects_non_positive_minutes() {
-        let mut clock = SimulationClock::new(Scheduler::new());
-        let err = clock.advance(0.0).err().expect("0.0 should be rejected");
+    fn effective_minutes_rejects_non_positive_minutes() {
+        let clock = SimulationClock::new(Scheduler::new(), CalendarDate::from_start());
+        let err = clock
+            .effective_minutes(0.0)
+            .expect_err("0.0 should be rejected");
         assert!(err.to_string().contains("時間は正の値"));
         let err = clock
-            .advance(-5.0)
-            .err()
-            .expect("negative should be rejected");
+            .effective_minutes(-5.0)
+            .expect_err("negative should be rejected");
         assert!(err.to_string().contains("時間は正の値"));
     }
 
     #[test]
-    fn advance_rejects_non_finite_minutes() {
-        let mut clock = SimulationClock::new(Scheduler::new());
+    fn tick_steps_bound_each_step_after_the_time_multiplier() {
+        let mut clock = SimulationClock::new(Scheduler::new(), CalendarDate::from_start());
+        let plan = |clock: &SimulationClock, minutes: f64| -> Vec<f64> {
+            let effective = clock.effective_minutes(minutes).expect("minutes");
+            TickSteps::new(clock.simulation_minutes(), effective).collect()
+        };
+        assert_eq!(plan(&clock, 90.5), vec![60.0, 30.5]);
+
+        // 正時の途中から始まる進行は、最初のステップを次の正時までで切る。
+        assert!(clock.advance(60.0).hour_completed);
+        let tick = clock.advance(30.0);
+        assert!(!tick.hour_completed);
+        assert_eq!(plan(&clock, 90.0), vec![30.0, 60.0]);
+        assert_eq!(plan(&clock, 20.0), vec![20.0]);
+        // 正時に達したステップは、前の正時からの分をまとめて返す。
+        let tick = clock.advance(30.0);
+        assert!(tick.hour_completed);
+        assert_eq!(tick.effective_minutes, 60.0);
+        assert_eq!(tick.scale, 1.0);
+
+        clock.set_time_multiplier(2.0).expect("multiplier");
+        assert_eq!(plan(&clock, 75.0), vec![60.0, 60.0, 30.0]);
+        let year = plan(&clock, 262_800.0);
+        assert_eq!(year.len(), 8_760);
+        assert!(year.iter().all(|step| *step == MAX_STEP_MINUTES));
+    }
+
+    #[test]
+    fn advance_reports_each_crossed_period_end_from_the_start_date() {
+        let start = CalendarDate::new(2030, 3, 30);
+        let mut clock = SimulationClock::new(Scheduler::new(), start);
+        assert_eq!(clock.start_date(), start);
+
+        let tick = clock.advance(MINUTES_PER_DAY as f64);
+        assert!(tick.period_ends.is_empty());
+        assert_eq!(clock.calendar_date(), CalendarDate::new(2030, 3, 31));
+
+        let tick = clock.advance(MINUTES_PER_DAY as f64);
+        assert_eq!(
+            tick.period_ends,
+            vec![
+                PeriodEnd::MonthEnd {
+                    year: 2030,
+                    month: 3
+                },
+                PeriodEnd::QuarterEnd {
+                    year: 2030,
+                    quarter: 1
+                },
+            ]
+        );
+
+        // 1 回の進行で複数の締め日を越えても、すべて古い順に返す。
+        let tick = clock.advance((275 * MINUTES_PER_DAY) as f64);
+        assert_eq!(clock.calendar_date(), CalendarDate::new(2031, 1, 1));
+        assert_eq!(
+            tick.period_ends
+                .iter()
+                .filter(|period| matches!(period, PeriodEnd::MonthEnd { .. }))
+                .count(),
+            9
+        );
+        assert_eq!(
+            tick.period_ends.last(),
+            Some(&PeriodEnd::YearEnd { year: 2030 })
+        );
+    }
+
+    #[test]
+    fn effective_minutes_rejects_non_finite_minutes() {
+        let clock = SimulationClock::new(Scheduler::new(), CalendarDate::from_start());
         let err = clock
-            .advance(f64::NAN)
-            .err()
-            .expect("NaN should be rejected");
+            .effective_minutes(f64::NAN)
+            .expect_err("NaN should be rejected");
         assert!(err.to_string().contains("時間が不正"));
         let err = clock
-            .advance(f64::INFINITY)
-            .err()
-            .expect("infinity should be rejected");
+            .effective_minutes(f64::INFINITY)
+            .expect_err("infinity should be rejected");
         assert!(err.to_string().contains("時間が不正"));
     }
+
+    #[test]
+    fn minutes_until_counts_to_midnight_of_a_later_date() {
+        let mut clock = SimulationClock::new(Scheduler::new(), CalendarDate::new(2030, 2, 27));
+        clock.set_time_multiplier(3.0).expect("multiplier");
+        clock.advance(90.0);
+        let minutes = clock
+            .minutes_until(CalendarDate::new(2030, 3, 2))
+            .expect("later date");
+        assert_eq!(minutes, (3 * MINUTES_PER_DAY - 90) as f64);
+
+        let err = clock
+            .minutes_until(CalendarDate::new(2030, 2, 27))
+            .expect_err("today should be rejected");
+        assert!(err.to_string().contains("より後の日付"));
+        assert!(clock.minutes_until(CalendarDate::new(2030, 2, 30)).is_err());
+    }
 }
@@ -3,8 +3,16 @@ mod scheduler;
 mod time;
 
 pub use game::{
-    BudgetAllocation, CountryDefinition, CountryState, FiscalSnapshot, FiscalTrendPoint, GameState,
-    IndustryCategory, SectorOverview, TaxPolicy, TaxPolicyConfig, TimeStatus,
+    BailoutConditions, BailoutOffer, BailoutProgram, BlackoutRecord, BondIssue, BudgetAllocation,
+    CarbonPolicy, CatalogIssue, CatalogLint, ConsumerPriceIndex, CountryDefinition, CountryState,
+    CpiPoint, DebtStatus, DependencyGraph, EnvironmentState, FiscalSnapshot, FiscalTrendPoint,
+    GameBuilder, GameState, GraphEdge, GraphFormat, GraphNode, IndustryCatalog, IndustryCategory,
+    LintSeverity, MaturityBucket, PowerGridStatus, ResearchState, RestructuringTerms,
+    RunUntilOutcome, SectorId, SectorOverview, SovereignDebt, StoryState, TaxPolicy,
+    TaxPolicyChange, TaxPolicyConfig, TaxRateKind, TaxSegment, TaxTransition, TechEffect, TechTree,
+    TechnologyDefinition, TemporaryTaxChange, TimeStatus,
 };
-pub use scheduler::{ScheduleSpec, ScheduledTask, Scheduler, TaskKind};
-pub use time::{CalendarDate, GameClock};
+pub use scheduler::{
+    CalendarRule, ScheduleSpec, ScheduledTask, Scheduler, TaskCategory, TaskId, TaskKind,
+};
+pub use time::{CalendarDate, GameClock, PeriodEnd, Weekday};
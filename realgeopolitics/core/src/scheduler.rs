@@ -1,13 +1,30 @@
-use std::collections::{BinaryHeap, VecDeque};
+mod calendar;
+mod wheel;
 
-use crate::time::{GameClock, ScheduledTime};
+use std::str::FromStr;
+
+use anyhow::{Result, anyhow, ensure};
+
+pub use calendar::CalendarRule;
+
+use crate::time::{CalendarDate, GameClock, ScheduledTime};
+
+use wheel::TimingWheel;
 
-pub const ONE_YEAR_MINUTES: u64 = 365 * 24 * 60;
-const IMMEDIATE_THRESHOLD_MINUTES: u64 = 10;
-const COMPRESSED_BUCKET_MINUTES: u64 = 24 * 60;
 const DAY_MINUTES: u64 = 24 * 60;
 const WEEK_MINUTES: u64 = 7 * DAY_MINUTES;
 
+/// `Scheduler::schedule` が採番するタスクの識別子。繰り返しタスクは再登録後も同じ ID を保つ。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TaskId(u64);
+
+impl TaskId {
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+}
+
+/// 同時刻のタスクは `priority` の小さい順、同じ優先度なら `TaskId` の小さい (先に登録した) 順に実行する。
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TaskKind {
     EconomicTick,
@@ -15,28 +32,176 @@ pub enum TaskKind {
     PolicyResolution,
     DiplomaticPulse,
     ScriptedEvent(usize),
+    /// `schedule_event` で予約された 1 か国向けの後続イベント。
+    ScriptedFollowUp {
+        template: usize,
+        country: usize,
+        target: Option<usize>,
+    },
+}
+
+impl TaskKind {
+    /// 経済の更新を先に済ませ、政策・外交の処理を経てからイベントが最新の状態を見るようにする。
+    pub fn priority(&self) -> u8 {
+        match self {
+            TaskKind::EconomicTick => 0,
+            TaskKind::PolicyResolution => 1,
+            TaskKind::DiplomaticPulse => 2,
+            TaskKind::EventTrigger => 3,
+            TaskKind::ScriptedEvent(_) => 4,
+            TaskKind::ScriptedFollowUp { .. } => 5,
+        }
+    }
+
+    /// 登録時に明示しない場合のカテゴリ。スクリプトイベントはテンプレートに応じて上書きされる。
+    pub fn default_category(&self) -> TaskCategory {
+        match self {
+            TaskKind::DiplomaticPulse => TaskCategory::International,
+            _ => TaskCategory::Domestic,
+        }
+    }
+}
+
+/// 一時停止や実行数の制限をまとめて掛けるためのタスク分類。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TaskCategory {
+    Domestic,
+    International,
+}
+
+impl TaskCategory {
+    pub const ALL: [TaskCategory; 2] = [TaskCategory::Domestic, TaskCategory::International];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TaskCategory::Domestic => "国内",
+            TaskCategory::International => "国際",
+        }
+    }
+
+    fn index(self) -> usize {
+        match self {
+            TaskCategory::Domestic => 0,
+            TaskCategory::International => 1,
+        }
+    }
 }
 
+/// 繰り返しタスクの規則。暦ベースの規則はスケジューラの起点日から数えた日付で解決する。
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ScheduleSpec {
     EveryMinutes(u64),
     Daily,
     Weekly,
+    /// 毎月 `day` 日 0:00。その月の日数を超える日は月末に丸める。
+    Monthly {
+        day: u8,
+    },
+    /// 1・4・7・10 月の `day` 日 0:00。
+    Quarterly {
+        day: u8,
+    },
+    /// 毎年 `month` 月 `day` 日 0:00。日は `Monthly` と同じく月末に丸める。
+    Yearly {
+        month: u8,
+        day: u8,
+    },
+    Calendar(CalendarRule),
 }
 
 impl ScheduleSpec {
-    fn next_execution_minutes(&self, last_execution: u64) -> u64 {
+    /// `month` 月末を期末とする会計年度の締め日 (`fiscal_year_end(3)` で毎年 3 月 31 日)。
+    pub fn fiscal_year_end(month: u8) -> Self {
+        ScheduleSpec::Yearly { month, day: 31 }
+    }
+
+    fn next_execution_minutes(&self, epoch: CalendarDate, last_execution: u64) -> Option<u64> {
         match self {
-            ScheduleSpec::EveryMinutes(minutes) => last_execution + minutes,
-            ScheduleSpec::Daily => last_execution + DAY_MINUTES,
-            ScheduleSpec::Weekly => last_execution + WEEK_MINUTES,
+            ScheduleSpec::EveryMinutes(minutes) => Some(last_execution + minutes),
+            ScheduleSpec::Daily => Some(last_execution + DAY_MINUTES),
+            ScheduleSpec::Weekly => Some(last_execution + WEEK_MINUTES),
+            _ => self.first_at_or_after(epoch, last_execution + 1),
+        }
+    }
+
+    /// `from` 分以降で最初の実行時刻。間隔指定の規則は `from` をそのまま返す。
+    fn first_at_or_after(&self, epoch: CalendarDate, from: u64) -> Option<u64> {
+        let at_midnight = |minute: u64| minute == 0;
+        let day_of_month = |date: &CalendarDate, day: u8| date.day == day.min(date.month_length());
+        match *self {
+            ScheduleSpec::EveryMinutes(_) | ScheduleSpec::Daily | ScheduleSpec::Weekly => {
+                Some(from)
+            }
+            ScheduleSpec::Monthly { day } => {
+                calendar::first_match_from(epoch, from, |date| day_of_month(date, day), at_midnight)
+            }
+            ScheduleSpec::Quarterly { day } => calendar::first_match_from(
+                epoch,
+                from,
+                |date| date.month % 3 == 1 && day_of_month(date, day),
+                at_midnight,
+            ),
+            ScheduleSpec::Yearly { month, day } => calendar::first_match_from(
+                epoch,
+                from,
+                |date| date.month == month && day_of_month(date, day),
+                at_midnight,
+            ),
+            ScheduleSpec::Calendar(rule) => rule.first_from(epoch, from),
         }
     }
 }
 
+/// データファイル向けのテキスト表現。cron 風の 5 項目 (`0 0 L 3 *` で毎年 3 月末) か、
+/// `@daily` / `@weekly` / `@monthly` / `@quarterly` / `@yearly` / `@every 90m` (`m`・`h`・`d`) を受け付ける。
+impl FromStr for ScheduleSpec {
+    type Err = anyhow::Error;
+
+    fn from_str(text: &str) -> Result<Self> {
+        let text = text.trim();
+        let Some(alias) = text.strip_prefix('@') else {
+            return CalendarRule::parse(text).map(ScheduleSpec::Calendar);
+        };
+        let mut words = alias.split_whitespace();
+        match (words.next(), words.next(), words.next()) {
+            (Some("daily"), None, _) => Ok(ScheduleSpec::Daily),
+            (Some("weekly"), None, _) => Ok(ScheduleSpec::Weekly),
+            (Some("monthly"), None, _) => Ok(ScheduleSpec::Monthly { day: 1 }),
+            (Some("quarterly"), None, _) => Ok(ScheduleSpec::Quarterly { day: 1 }),
+            (Some("yearly" | "annually"), None, _) => Ok(ScheduleSpec::Yearly { month: 1, day: 1 }),
+            (Some("every"), Some(interval), None) => {
+                parse_interval(interval).map(ScheduleSpec::EveryMinutes)
+            }
+            _ => Err(anyhow!("未知のスケジュール指定 '{}' です", text)),
+        }
+    }
+}
+
+fn parse_interval(text: &str) -> Result<u64> {
+    let (number, factor) = if let Some(number) = text.strip_suffix('m') {
+        (number, 1)
+    } else if let Some(number) = text.strip_suffix('h') {
+        (number, 60)
+    } else if let Some(number) = text.strip_suffix('d') {
+        (number, DAY_MINUTES)
+    } else {
+        return Err(anyhow!(
+            "間隔 '{}' には m・h・d のいずれかの単位が必要です",
+            text
+        ));
+    };
+    let value: u64 = number
+        .parse()
+        .map_err(|_| anyhow!("間隔 '{}' の数値を解釈できません", text))?;
+    ensure!(value >= 1, "間隔 '{}' は 1 以上で指定してください", text);
+    Ok(value * factor)
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ScheduledTask {
+    id: TaskId,
     pub kind: TaskKind,
+    pub category: TaskCategory,
     pub execute_at: ScheduledTime,
     pub schedule_spec: Option<ScheduleSpec>,
 }
@@ -44,30 +209,45 @@ pub struct ScheduledTask {
 impl ScheduledTask {
     pub fn new(kind: TaskKind, execute_at: u64) -> Self {
         Self {
+            id: TaskId(0),
             kind,
+            category: kind.default_category(),
             execute_at: ScheduledTime::new(execute_at),
             schedule_spec: None,
         }
     }
 
+    /// スケジューラに登録されるまでは `TaskId(0)` のまま。
+    pub fn id(&self) -> TaskId {
+        self.id
+    }
+
+    pub fn with_category(mut self, category: TaskCategory) -> Self {
+        self.category = category;
+        self
+    }
+
     pub fn with_schedule(mut self, spec: ScheduleSpec) -> Self {
         self.schedule_spec = Some(spec);
         self
     }
 
-    fn reschedule(&self) -> Option<Self> {
-        self.schedule_spec.map(|spec| {
-            let next_minutes = spec.next_execution_minutes(self.execute_at.minutes);
-            let mut next_task = self.clone();
-            next_task.execute_at = ScheduledTime::new(next_minutes);
-            next_task
-        })
+    fn reschedule(&self, epoch: CalendarDate) -> Option<Self> {
+        let spec = self.schedule_spec?;
+        let next_minutes = spec.next_execution_minutes(epoch, self.execute_at.minutes)?;
+        let mut next_task = self.clone();
+        next_task.execute_at = ScheduledTime::new(next_minutes);
+        Some(next_task)
     }
 }
 
+/// 先に実行すべきタスクほど大きい (`BinaryHeap` の先頭に来る) 順序。
 impl Ord for ScheduledTask {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.execute_at.cmp(&other.execute_at)
+        self.execute_at
+            .cmp(&other.execute_at)
+            .then_with(|| other.kind.priority().cmp(&self.kind.priority()))
+            .then_with(|| other.id.cmp(&self.id))
     }
 }
 
@@ -77,137 +257,179 @@ impl PartialOrd for ScheduledTask {
     }
 }
 
-#[derive(Default, Debug)]
+/// 予約済みタスクを現在時刻からの相対時間で管理するスケジューラ。
+/// 何十年分のゲームでも登録は O(1) で、保持するのは予約中のタスクだけになる。
+#[derive(Debug)]
 pub struct Scheduler {
-    immediate_queue: VecDeque<ScheduledTask>,
-    short_term_tasks: BinaryHeap<ScheduledTask>,
-    long_term_buckets: VecDeque<Vec<ScheduledTask>>,
+    wheel: TimingWheel,
+    /// 一時停止中のカテゴリで実行時刻を迎え、再開を待っているタスク。
+    held: Vec<ScheduledTask>,
+    controls: [CategoryControl; 2],
+    last_id: u64,
+    epoch: CalendarDate,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct CategoryControl {
+    paused: bool,
+    throttle: Option<usize>,
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Scheduler {
     pub fn new() -> Self {
         Self {
-            immediate_queue: VecDeque::new(),
-            short_term_tasks: BinaryHeap::new(),
-            long_term_buckets: VecDeque::new(),
+            wheel: TimingWheel::new(),
+            held: Vec::new(),
+            controls: [CategoryControl::default(); 2],
+            last_id: 0,
+            epoch: CalendarDate::from_start(),
         }
     }
 
-    pub fn schedule(&mut self, task: ScheduledTask) {
-        if task.execute_at.minutes <= IMMEDIATE_THRESHOLD_MINUTES {
-            self.immediate_queue.push_back(task);
-            return;
-        }
+    /// 暦ベースの規則を解決する起点 (通算 0 分に当たる日付) を指定する。
+    pub fn with_epoch(mut self, epoch: CalendarDate) -> Self {
+        self.epoch = epoch;
+        self
+    }
 
-        if task.execute_at.minutes <= ONE_YEAR_MINUTES {
-            self.short_term_tasks.push(task);
-        } else {
-            let bucket_index =
-                ((task.execute_at.minutes - ONE_YEAR_MINUTES) / COMPRESSED_BUCKET_MINUTES) as usize;
-            while self.long_term_buckets.len() <= bucket_index {
-                self.long_term_buckets.push_back(Vec::new());
-            }
-            if let Some(bucket) = self.long_term_buckets.get_mut(bucket_index) {
-                bucket.push(task);
-            }
-        }
+    /// `spec` に従った `from` 分以降の最初の実行時刻。規則に当てはまる日付がなければ `None`。
+    pub fn first_occurrence(&self, spec: ScheduleSpec, from: u64) -> Option<u64> {
+        spec.first_at_or_after(self.epoch, from)
+    }
+
+    /// `spec` に従って `from` 分以降の最初の実行時刻に `kind` を登録する。
+    /// 規則に当てはまる日付が見つからなければ登録せずに `None` を返す。
+    pub fn schedule_recurring(
+        &mut self,
+        kind: TaskKind,
+        spec: ScheduleSpec,
+        from: u64,
+    ) -> Option<TaskId> {
+        let execute_at = self.first_occurrence(spec, from)?;
+        Some(self.schedule(ScheduledTask::new(kind, execute_at).with_schedule(spec)))
+    }
+
+    pub fn schedule(&mut self, mut task: ScheduledTask) -> TaskId {
+        self.last_id += 1;
+        task.id = TaskId(self.last_id);
+        let id = task.id;
+        self.wheel.insert(task);
+        id
     }
 
-    fn promote_long_term(&mut self, current_minutes: u64) {
-        if current_minutes < ONE_YEAR_MINUTES {
-            return;
+    /// 予約済みのタスクを取り除いて返す。繰り返しタスクは以降の実行もすべて止まる。
+    pub fn cancel(&mut self, id: TaskId) -> Option<ScheduledTask> {
+        if let Some(pos) = self.held.iter().position(|task| task.id == id) {
+            return Some(self.held.remove(pos));
         }
-        let elapsed_since_threshold = current_minutes - ONE_YEAR_MINUTES;
-        let buckets_to_promote = (elapsed_since_threshold / COMPRESSED_BUCKET_MINUTES) as usize;
-        for _ in 0..=buckets_to_promote {
-            let promote_now = self
-                .long_term_buckets
-                .front()
-                .map(|bucket| {
-                    bucket
-                        .iter()
-                        .map(|task| task.execute_at.minutes)
-                        .min()
-                        .unwrap_or(u64::MAX)
-                        <= current_minutes
-                })
-                .unwrap_or(false);
-            if !promote_now {
-                break;
-            }
-            if let Some(bucket) = self.long_term_buckets.pop_front() {
-                for task in bucket {
-                    if task.execute_at.minutes > current_minutes {
-                        self.short_term_tasks.push(task);
-                    } else {
-                        self.immediate_queue.push_back(task);
-                    }
-                }
-            } else {
-                break;
-            }
+        self.wheel.remove(id)
+    }
+
+    /// 予約済みタスクの実行時刻を `execute_at` (通算分) に変更する。繰り返し間隔は維持する。
+    pub fn reschedule(&mut self, id: TaskId, execute_at: u64) -> bool {
+        let Some(mut task) = self.cancel(id) else {
+            return false;
+        };
+        task.execute_at = ScheduledTime::new(execute_at);
+        self.wheel.insert(task);
+        true
+    }
+
+    /// 予約済みのタスクを実行される順 (時刻、優先度、登録順) に列挙する。
+    pub fn upcoming(&self) -> impl Iterator<Item = &ScheduledTask> {
+        let mut tasks: Vec<&ScheduledTask> = self.wheel.iter().chain(self.held.iter()).collect();
+        tasks.sort_by(|a, b| b.cmp(a));
+        tasks.into_iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.wheel.len() + self.held.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// カテゴリを一時停止する。停止中に実行時刻を迎えたタスクは再開まで保留し、
+    /// 繰り返しタスクも次回分を登録しない。
+    pub fn pause(&mut self, category: TaskCategory) {
+        self.controls[category.index()].paused = true;
+    }
+
+    /// 一時停止を解除し、保留していたタスクを次の `next_ready_tasks` で 1 回ずつ実行させる。
+    pub fn resume(&mut self, category: TaskCategory) {
+        self.controls[category.index()].paused = false;
+        let now = self.wheel.elapsed();
+        let (resumed, held): (Vec<_>, Vec<_>) = std::mem::take(&mut self.held)
+            .into_iter()
+            .partition(|task| task.category == category);
+        self.held = held;
+        for mut task in resumed {
+            task.execute_at = ScheduledTime::new(task.execute_at.minutes.max(now));
+            self.wheel.insert(task);
         }
     }
 
+    pub fn is_paused(&self, category: TaskCategory) -> bool {
+        self.controls[category.index()].paused
+    }
+
+    /// `next_ready_tasks` 1 回あたりに実行するカテゴリ内のタスク数の上限。
+    /// 上限を超えた分は実行時刻を過ぎたまま残り、次の呼び出しで優先して返る。
+    pub fn set_throttle(&mut self, category: TaskCategory, limit: Option<usize>) -> Result<()> {
+        ensure!(
+            limit != Some(0),
+            "{}タスクの実行上限は 1 以上で指定してください",
+            category.label()
+        );
+        self.controls[category.index()].throttle = limit;
+        Ok(())
+    }
+
+    pub fn throttle(&self, category: TaskCategory) -> Option<usize> {
+        self.controls[category.index()].throttle
+    }
+
+    /// 時計の時刻までに実行時刻を迎えたタスクを、時刻・優先度・登録順で返す。繰り返しタスクは
+    /// 次回分を登録し、それも時計の時刻までに来るなら同じ呼び出しの中で続けて返す。
     pub fn next_ready_tasks(&mut self, clock: &GameClock) -> Vec<ScheduledTask> {
         let current_minutes = clock.total_minutes();
-        self.promote_long_term(current_minutes);
-
         let mut ready = Vec::new();
-        while let Some(task) = self.short_term_tasks.peek() {
-            if task.execute_at.minutes > current_minutes {
-                break;
+        let mut executed = [0usize; 2];
+        let mut deferred = Vec::new();
+        while let Some(task) = self.wheel.pop_due(current_minutes) {
+            let index = task.category.index();
+            let control = self.controls[index];
+            if control.paused {
+                self.held.push(task);
+                continue;
             }
-            let task = self.short_term_tasks.pop().expect("task popped after peek");
-            if let Some(next_task) = task.reschedule() {
-                self.schedule(next_task);
+            if control
+                .throttle
+                .is_some_and(|limit| executed[index] >= limit)
+            {
+                deferred.push(task);
+                continue;
             }
-            ready.push(task);
-        }
-
-        let mut postponed = Vec::new();
-        while let Some(task) = self.immediate_queue.pop_front() {
-            if task.execute_at.minutes <= current_minutes {
-                if let Some(next_task) = task.reschedule() {
-                    self.schedule(next_task);
-                }
-                ready.push(task);
-            } else {
-                postponed.push(task);
+            executed[index] += 1;
+            if let Some(next_task) = task.reschedule(self.epoch) {
+                self.wheel.insert(next_task);
             }
+            ready.push(task);
         }
-        for task in postponed.into_iter() {
-            self.immediate_queue.push_back(task);
+        for task in deferred {
+            self.wheel.insert(task);
         }
-
         ready
     }
 
     pub fn peek_next_minutes(&self, current_minutes: u64) -> Option<u64> {
-        let immediate = self
-            .immediate_queue
-            .front()
-            .map(|task| task.execute_at.minutes);
-        let short_term = self
-            .short_term_tasks
-            .peek()
-            .map(|task| task.execute_at.minutes);
-        let long_term = self
-            .long_term_buckets
-            .iter()
-            .flat_map(|bucket| bucket.iter().map(|task| task.execute_at.minutes))
-            .min();
-
-        [immediate, short_term, long_term]
-            .into_iter()
-            .flatten()
-            .filter(|minutes| *minutes >= current_minutes)
-            .min()
-            .or_else(|| {
-                [immediate, short_term, long_term]
-                    .into_iter()
-                    .flatten()
-                    .min()
-            })
+        self.wheel.peek_minutes(current_minutes)
     }
 }
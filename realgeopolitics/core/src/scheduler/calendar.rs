@@ -0,0 +1,242 @@
+use anyhow::{Result, anyhow, ensure};
+
+use crate::time::CalendarDate;
+
+const MINUTES_PER_DAY: u64 = 24 * 60;
+/// グレゴリオ暦は 400 年 (146,097 日、ちょうど 20,871 週) で日付と曜日の並びが一巡する。
+/// この範囲を探せば、2 月 29 日の月曜日 (最大 40 年おき) のような疎な規則でも必ず次の日付が見つかる。
+const MAX_SEARCH_DAYS: u64 = 146_097;
+
+/// cron 風の 5 項目 `分 時 日 月 曜日` で表した暦ベースの繰り返し規則。
+///
+/// 各項目は `*`、数値、範囲 `a-b`、刻み `*/n` / `a-b/n`、カンマ区切りの列挙を受け付ける。
+/// 日には月末を表す `L`、曜日には 0 (日曜) 〜 6 (土曜) と日曜としての 7 を指定できる。
+/// cron と異なり、日と曜日の両方を指定した場合は両方を満たす日だけが対象になる
+/// (`0 0 1-7 * 1` で毎月第 1 月曜)。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CalendarRule {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    last_day: bool,
+    months: u64,
+    weekdays: u64,
+}
+
+impl CalendarRule {
+    pub fn parse(text: &str) -> Result<Self> {
+        let fields: Vec<&str> = text.split_whitespace().collect();
+        ensure!(
+            fields.len() == 5,
+            "スケジュール '{}' は「分 時 日 月 曜日」の 5 項目で指定してください",
+            text
+        );
+        let (minutes, _) = parse_field(fields[0], "分", 0, 59, false)?;
+        let (hours, _) = parse_field(fields[1], "時", 0, 23, false)?;
+        let (days, last_day) = parse_field(fields[2], "日", 1, 31, true)?;
+        let (months, _) = parse_field(fields[3], "月", 1, 12, false)?;
+        let (mut weekdays, _) = parse_field(fields[4], "曜日", 0, 7, false)?;
+        if weekdays & (1 << 7) != 0 {
+            weekdays = (weekdays & !(1 << 7)) | 1;
+        }
+        let rule = Self {
+            minutes,
+            hours,
+            days,
+            last_day,
+            months,
+            weekdays,
+        };
+        // 暦の 1 周期に 1 度も該当しなければ永久に実行されない。
+        ensure!(
+            rule.first_from(CalendarDate::from_start(), 0).is_some(),
+            "スケジュール '{}' の日・月・曜日の組み合わせに該当する日付がありません",
+            text
+        );
+        Ok(rule)
+    }
+
+    fn matches_day(&self, date: &CalendarDate) -> bool {
+        let day_matches =
+            self.days & (1 << date.day) != 0 || (self.last_day && date.day == date.month_length());
+        day_matches
+            && self.months & (1 << date.month) != 0
+            && self.weekdays & (1 << date.weekday_index()) != 0
+    }
+
+    fn matches_minute(&self, minute_of_day: u64) -> bool {
+        self.hours & (1 << (minute_of_day / 60)) != 0
+            && self.minutes & (1 << (minute_of_day % 60)) != 0
+    }
+
+    pub(super) fn first_from(&self, epoch: CalendarDate, from: u64) -> Option<u64> {
+        first_match_from(
+            epoch,
+            from,
+            |date| self.matches_day(date),
+            |minute| self.matches_minute(minute),
+        )
+    }
+}
+
+/// `from` 分以降で `day_matches` を満たす日のうち、`minute_matches` を満たす最初の時刻 (通算分) を探す。
+/// 通算 0 分は `epoch` の 0:00 に対応する。
+pub(super) fn first_match_from(
+    epoch: CalendarDate,
+    from: u64,
+    day_matches: impl Fn(&CalendarDate) -> bool,
+    minute_matches: impl Fn(u64) -> bool,
+) -> Option<u64> {
+    let first_day = from / MINUTES_PER_DAY;
+    let mut date = epoch.plus_days(first_day);
+    for offset in 0..MAX_SEARCH_DAYS {
+        if day_matches(&date) {
+            let start = if offset == 0 {
+                from % MINUTES_PER_DAY
+            } else {
+                0
+            };
+            if let Some(minute) = (start..MINUTES_PER_DAY).find(|minute| minute_matches(*minute)) {
+                return Some((first_day + offset) * MINUTES_PER_DAY + minute);
+            }
+        }
+        date.advance_days(1);
+    }
+    None
+}
+
+/// 1 項目を解析し、該当する値のビット集合と `L` (月末) の有無を返す。
+fn parse_field(
+    text: &str,
+    label: &str,
+    min: u32,
+    max: u32,
+    allow_last: bool,
+) -> Result<(u64, bool)> {
+    let mut bits = 0u64;
+    let mut last = false;
+    for item in text.split(',') {
+        if allow_last && item.eq_ignore_ascii_case("L") {
+            last = true;
+            continue;
+        }
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => {
+                let step = parse_number(step, label)?;
+                ensure!(
+                    step >= 1,
+                    "{} の刻み '{}' は 1 以上で指定してください",
+                    label,
+                    item
+                );
+                (range, Some(step))
+            }
+            None => (item, None),
+        };
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (parse_number(start, label)?, parse_number(end, label)?)
+        } else {
+            let value = parse_number(range, label)?;
+            (value, if step.is_some() { max } else { value })
+        };
+        ensure!(
+            min <= start && start <= end && end <= max,
+            "{} の指定 '{}' は {}〜{} の範囲で指定してください",
+            label,
+            item,
+            min,
+            max
+        );
+        for value in (start..=end).step_by(step.unwrap_or(1) as usize) {
+            bits |= 1 << value;
+        }
+    }
+    Ok((bits, last))
+}
+
+fn parse_number(text: &str, label: &str) -> Result<u32> {
+    text.parse()
+        .map_err(|_| anyhow!("{} に数値でない指定 '{}' があります", label, text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn epoch() -> CalendarDate {
+        CalendarDate::new(2025, 1, 1)
+    }
+
+    fn date_at(minutes: u64) -> (CalendarDate, u64) {
+        (
+            epoch().plus_days(minutes / MINUTES_PER_DAY),
+            minutes % MINUTES_PER_DAY,
+        )
+    }
+
+    #[test]
+    fn calendar_rules_resolve_month_ends_and_weekdays() {
+        let fiscal_year_end = CalendarRule::parse("0 0 L 2,3 *").expect("parse");
+        let first = fiscal_year_end.first_from(epoch(), 0).expect("found");
+        assert_eq!(date_at(first), (CalendarDate::new(2025, 2, 28), 0));
+        let next = fiscal_year_end.first_from(epoch(), first + 1).unwrap();
+        assert_eq!(date_at(next), (CalendarDate::new(2025, 3, 31), 0));
+        let next_year = fiscal_year_end
+            .first_from(epoch(), 365 * MINUTES_PER_DAY)
+            .unwrap();
+        assert_eq!(date_at(next_year).0, CalendarDate::new(2026, 2, 28));
+
+        // 2025-01-06 は月曜。
+        let first_monday = CalendarRule::parse("30 9 1-7 * 1").expect("parse");
+        let found = first_monday.first_from(epoch(), 0).unwrap();
+        assert_eq!(date_at(found), (CalendarDate::new(2025, 1, 6), 9 * 60 + 30));
+        let found = first_monday.first_from(epoch(), found + 1).unwrap();
+        assert_eq!(date_at(found).0, CalendarDate::new(2025, 2, 3));
+
+        let sundays = CalendarRule::parse("0 */12 * * 7").expect("parse");
+        let found = sundays.first_from(epoch(), 0).unwrap();
+        assert_eq!(date_at(found), (CalendarDate::new(2025, 1, 5), 0));
+        let found = sundays.first_from(epoch(), found + 1).unwrap();
+        assert_eq!(date_at(found), (CalendarDate::new(2025, 1, 5), 12 * 60));
+
+        let leap_day = CalendarRule::parse("0 0 29 2 *").expect("parse");
+        let found = leap_day.first_from(epoch(), 0).unwrap();
+        assert_eq!(date_at(found).0, CalendarDate::new(2028, 2, 29));
+    }
+
+    #[test]
+    fn sparse_calendar_rules_find_matches_decades_apart() {
+        // 2 月 29 日の月曜日は 2072 年の次が 2112 年で、40 年空く。
+        let rule = CalendarRule::parse("0 0 29 2 1").expect("parse");
+        let mut from = 0;
+        for expected in [2044, 2072, 2112] {
+            let found = rule.first_from(epoch(), from).expect("match");
+            assert_eq!(date_at(found).0, CalendarDate::new(expected, 2, 29));
+            from = found + 1;
+        }
+    }
+
+    #[test]
+    fn calendar_rules_reject_malformed_fields() {
+        for invalid in [
+            "0 0 1 *",
+            "60 0 1 * *",
+            "0 0 0 * *",
+            "0 0 L * L",
+            "0 0 5-1 * *",
+            "*/0 0 1 * *",
+            "0 0 1 jan *",
+            "0 0 31 4,6 *",
+            "0 0 30,31 2 *",
+            "0 0 30 2 1",
+        ] {
+            assert!(
+                CalendarRule::parse(invalid).is_err(),
+                "{} should be rejected",
+                invalid
+            );
+        }
+    }
+}
@@ -0,0 +1,303 @@
+use std::collections::BinaryHeap;
+
+use super::{ScheduledTask, TaskId};
+
+const LEVEL_BITS: u32 = 6;
+const SLOTS: usize = 1 << LEVEL_BITS;
+/// 64^6 分 (約 13 万年) 先までを車輪で扱い、それ以上は `overflow` に置く。
+const LEVELS: usize = 6;
+
+/// 現在時刻からの相対時間で振り分ける階層型タイミングホイール。
+///
+/// レベル `n` の 1 スロットは 64^n 分を表し、登録時刻と現在時刻の差が大きいほど上のレベルに入る。
+/// 時計が上位スロットの先頭に達すると中身を下位レベルへ振り直すため、登録は常に O(1) で、
+/// 保持するのはタスク本体と固定数のスロットだけになる。
+#[derive(Debug)]
+pub(super) struct TimingWheel {
+    elapsed: u64,
+    levels: [Level; LEVELS],
+    /// 実行時刻を迎えたタスク。同時刻のものも `ScheduledTask` の順序で取り出す。
+    due: BinaryHeap<ScheduledTask>,
+    overflow: Vec<ScheduledTask>,
+    len: usize,
+}
+
+#[derive(Debug)]
+struct Level {
+    occupied: u64,
+    slots: [Vec<ScheduledTask>; SLOTS],
+}
+
+impl Default for Level {
+    fn default() -> Self {
+        Self {
+            occupied: 0,
+            slots: std::array::from_fn(|_| Vec::new()),
+        }
+    }
+}
+
+impl TimingWheel {
+    pub(super) fn new() -> Self {
+        Self {
+            elapsed: 0,
+            levels: Default::default(),
+            due: BinaryHeap::new(),
+            overflow: Vec::new(),
+            len: 0,
+        }
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// 内部の時計が指している通算分。
+    pub(super) fn elapsed(&self) -> u64 {
+        self.elapsed
+    }
+
+    pub(super) fn insert(&mut self, task: ScheduledTask) {
+        self.len += 1;
+        self.place(task);
+    }
+
+    fn place(&mut self, task: ScheduledTask) {
+        let when = task.execute_at.minutes;
+        if when <= self.elapsed {
+            self.due.push(task);
+            return;
+        }
+        let level = level_for(self.elapsed, when);
+        if level >= LEVELS {
+            self.overflow.push(task);
+            return;
+        }
+        let slot = slot_for(when, level);
+        let entry = &mut self.levels[level];
+        entry.slots[slot].push(task);
+        entry.occupied |= 1 << slot;
+    }
+
+    /// `now` 分までに実行時刻を迎えたタスクを時刻順に 1 件ずつ取り出す。
+    /// 取り出すものがなくなると内部の時計を `now` まで進めて `None` を返す。
+    pub(super) fn pop_due(&mut self, now: u64) -> Option<ScheduledTask> {
+        loop {
+            if let Some(task) = self.due.pop() {
+                self.len -= 1;
+                return Some(task);
+            }
+            match self.next_expiration() {
+                Some((level, slot, deadline)) if deadline <= now => {
+                    self.elapsed = deadline;
+                    let entry = &mut self.levels[level];
+                    entry.occupied &= !(1 << slot);
+                    for task in std::mem::take(&mut entry.slots[slot]) {
+                        self.place(task);
+                    }
+                }
+                _ => {
+                    self.elapsed = self.elapsed.max(now);
+                    if !self.cascade_overflow() {
+                        return None;
+                    }
+                }
+            }
+        }
+    }
+
+    /// 最も早く処理すべきスロットと、その先頭時刻。
+    fn next_expiration(&self) -> Option<(usize, usize, u64)> {
+        self.levels.iter().enumerate().find_map(|(level, entry)| {
+            if entry.occupied == 0 {
+                return None;
+            }
+            let shift = level as u32 * LEVEL_BITS;
+            let slot_range = 1u64 << shift;
+            let level_range = slot_range << LEVEL_BITS;
+            let current = ((self.elapsed >> shift) as usize) % SLOTS;
+            let slot = (entry.occupied.rotate_right(current as u32).trailing_zeros() as usize
+                + current)
+                % SLOTS;
+            let level_start = self.elapsed & !(level_range - 1);
+            let mut deadline = level_start + slot as u64 * slot_range;
+            if deadline <= self.elapsed {
+                deadline += level_range;
+            }
+            Some((level, slot, deadline))
+        })
+    }
+
+    /// 車輪の範囲に入った `overflow` のタスクを振り直し、1 件でも動かしたら true。
+    fn cascade_overflow(&mut self) -> bool {
+        if self.overflow.is_empty() {
+            return false;
+        }
+        let elapsed = self.elapsed;
+        let (near, far): (Vec<_>, Vec<_>) = std::mem::take(&mut self.overflow)
+            .into_iter()
+            .partition(|task| {
+                let when = task.execute_at.minutes;
+                when <= elapsed || level_for(elapsed, when) < LEVELS
+            });
+        self.overflow = far;
+        let moved = !near.is_empty();
+        for task in near {
+            self.place(task);
+        }
+        moved
+    }
+
+    /// 次に実行されるタスクの時刻。`current` 以降のものを優先し、なければ期限切れの最古を返す。
+    pub(super) fn peek_minutes(&self, current: u64) -> Option<u64> {
+        let upcoming = self
+            .next_expiration()
+            .and_then(|(level, slot, _)| {
+                self.levels[level].slots[slot]
+                    .iter()
+                    .map(|task| task.execute_at.minutes)
+                    .min()
+            })
+            .into_iter()
+            .chain(self.overflow.iter().map(|task| task.execute_at.minutes));
+        let due = self.due.iter().map(|task| task.execute_at.minutes);
+        upcoming
+            .chain(due.clone())
+            .filter(|minutes| *minutes >= current)
+            .min()
+            .or_else(|| due.min())
+    }
+
+    pub(super) fn remove(&mut self, id: TaskId) -> Option<ScheduledTask> {
+        let removed = if self.due.iter().any(|task| task.id == id) {
+            let mut removed = None;
+            self.due.retain(|task| {
+                if task.id == id {
+                    removed = Some(task.clone());
+                }
+                task.id != id
+            });
+            removed
+        } else if let Some(pos) = self.overflow.iter().position(|task| task.id == id) {
+            Some(self.overflow.swap_remove(pos))
+        } else {
+            self.levels.iter_mut().find_map(|entry| {
+                let (slot, pos) = entry.slots.iter().enumerate().find_map(|(slot, tasks)| {
+                    tasks
+                        .iter()
+                        .position(|task| task.id == id)
+                        .map(|pos| (slot, pos))
+                })?;
+                let task = entry.slots[slot].remove(pos);
+                if entry.slots[slot].is_empty() {
+                    entry.occupied &= !(1 << slot);
+                }
+                Some(task)
+            })
+        };
+        if removed.is_some() {
+            self.len -= 1;
+        }
+        removed
+    }
+
+    pub(super) fn iter(&self) -> impl Iterator<Item = &ScheduledTask> {
+        self.due.iter().chain(self.overflow.iter()).chain(
+            self.levels
+                .iter()
+                .flat_map(|entry| entry.slots.iter().flatten()),
+        )
+    }
+}
+
+/// `elapsed` と `when` が最初に食い違う桁からレベルを決める。
+fn level_for(elapsed: u64, when: u64) -> usize {
+    let masked = (elapsed ^ when) | (SLOTS as u64 - 1);
+    let significant = 63 - masked.leading_zeros();
+    (significant / LEVEL_BITS) as usize
+}
+
+fn slot_for(when: u64, level: usize) -> usize {
+    ((when >> (level as u32 * LEVEL_BITS)) as usize) % SLOTS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TaskKind;
+
+    fn task(id: u64, when: u64) -> ScheduledTask {
+        let mut task = ScheduledTask::new(TaskKind::ScriptedEvent(id as usize), when);
+        task.id = TaskId(id);
+        task
+    }
+
+    #[test]
+    fn timing_wheel_pops_tasks_in_time_order_across_levels() {
+        let mut wheel = TimingWheel::new();
+        let times = [5, 64, 65, 4_095, 4_096, 300_000, 40 * 525_600, 1 << 40, 63];
+        for (idx, when) in times.iter().enumerate() {
+            wheel.insert(task(idx as u64, *when));
+        }
+        assert_eq!(wheel.len(), times.len());
+        assert_eq!(wheel.peek_minutes(0), Some(5));
+
+        let mut fired = Vec::new();
+        for now in [0, 64, 10_000, 50 * 525_600, u64::MAX / 2] {
+            while let Some(task) = wheel.pop_due(now) {
+                assert!(task.execute_at.minutes <= now);
+                fired.push(task.execute_at.minutes);
+            }
+        }
+        let mut sorted = times.to_vec();
+        sorted.sort_unstable();
+        assert_eq!(fired, sorted);
+        assert_eq!(wheel.len(), 0);
+        assert_eq!(wheel.peek_minutes(0), None);
+    }
+
+    #[test]
+    fn timing_wheel_is_relative_to_the_current_time() {
+        let mut wheel = TimingWheel::new();
+        let late = 30 * 525_600;
+        assert!(wheel.pop_due(late).is_none());
+        wheel.insert(task(1, late + 10));
+        wheel.insert(task(2, late - 10));
+        assert_eq!(wheel.peek_minutes(late), Some(late + 10));
+        assert_eq!(wheel.pop_due(late).map(|task| task.id), Some(TaskId(2)));
+        assert!(wheel.pop_due(late + 9).is_none());
+        assert!(wheel.remove(TaskId(1)).is_some());
+        assert!(wheel.remove(TaskId(1)).is_none());
+        assert_eq!(wheel.len(), 0);
+        assert!(wheel.pop_due(late + 100).is_none());
+    }
+
+    #[test]
+    fn timing_wheel_matches_a_sorted_reference_under_random_steps() {
+        use rand::rngs::StdRng;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(45);
+        let mut wheel = TimingWheel::new();
+        let mut pending = Vec::new();
+        let mut now = 0u64;
+        for id in 0..2_000u64 {
+            let when = now + rng.gen_range(0..3_000_000);
+            wheel.insert(task(id, when));
+            pending.push(when);
+            if id % 7 == 0 {
+                now += rng.gen_range(0..200_000);
+                let mut last = 0;
+                while let Some(task) = wheel.pop_due(now) {
+                    let when = task.execute_at.minutes;
+                    assert!(when <= now && when >= last);
+                    last = when;
+                    let pos = pending.iter().position(|p| *p == when).unwrap();
+                    pending.swap_remove(pos);
+                }
+                assert!(pending.iter().all(|when| *when > now));
+                assert_eq!(wheel.len(), pending.len());
+            }
+        }
+    }
+}
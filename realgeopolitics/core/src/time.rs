@@ -1,4 +1,8 @@
 use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{Result, anyhow, ensure};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct GameClock {
@@ -32,7 +36,8 @@ impl Default for GameClock {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// フィールドを年・月・日の順に並べているので、導出した順序がそのまま日付の前後になる。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct CalendarDate {
     pub year: u16,
     pub month: u8,
@@ -44,10 +49,102 @@ impl CalendarDate {
         Self { year, month, day }
     }
 
+    /// 開始日を指定しないゲームの初日 (2025-01-01)。
     pub fn from_start() -> Self {
         Self::new(2025, 1, 1)
     }
 
+    /// 実在する日付かどうかを確かめてから作る。
+    pub fn try_new(year: u16, month: u8, day: u8) -> Result<Self> {
+        let date = Self::new(year, month, day);
+        date.validate()?;
+        Ok(date)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        // 曜日の計算は西暦 1 年以降を前提にしている。
+        ensure!(
+            self.year >= 1,
+            "年は 1 以上で指定してください: {}",
+            self.year
+        );
+        ensure!(
+            (1..=12).contains(&self.month),
+            "月は 1〜12 で指定してください: {}",
+            self.month
+        );
+        ensure!(
+            (1..=self.month_length()).contains(&self.day),
+            "{}年{}月に{}日は存在しません",
+            self.year,
+            self.month,
+            self.day
+        );
+        Ok(())
+    }
+
+    /// `days` 日後の日付を返す。
+    pub(crate) fn plus_days(mut self, days: u64) -> Self {
+        self.advance_days(days);
+        self
+    }
+
+    /// その月の日数。
+    pub fn month_length(&self) -> u8 {
+        days_in_month(self.year, self.month)
+    }
+
+    pub fn weekday(&self) -> Weekday {
+        Weekday::ALL[self.weekday_index() as usize]
+    }
+
+    /// 四半期を 1〜4 で返す。
+    pub fn quarter(&self) -> u8 {
+        (self.month - 1) / 3 + 1
+    }
+
+    /// 1 月 1 日を 1 とした年内の通し日数。
+    pub fn day_of_year(&self) -> u16 {
+        let before: u16 = (1..self.month)
+            .map(|month| days_in_month(self.year, month) as u16)
+            .sum();
+        before + self.day as u16
+    }
+
+    /// `other` までの日数。`other` のほうが前なら負になる。
+    pub fn days_until(&self, other: CalendarDate) -> i64 {
+        other.day_number() - self.day_number()
+    }
+
+    /// 西暦 1 年 1 月 1 日を 0 とした通し日数。
+    fn day_number(&self) -> i64 {
+        let years = self.year as i64 - 1;
+        years * 365 + years / 4 - years / 100 + years / 400 + self.day_of_year() as i64 - 1
+    }
+
+    pub fn is_month_end(&self) -> bool {
+        self.day == self.month_length()
+    }
+
+    pub fn is_quarter_end(&self) -> bool {
+        self.is_month_end() && self.month.is_multiple_of(3)
+    }
+
+    pub fn is_year_end(&self) -> bool {
+        self.month == 12 && self.day == 31
+    }
+
+    /// 曜日を 0 (日曜) 〜 6 (土曜) で返す。
+    pub(crate) fn weekday_index(&self) -> u8 {
+        const OFFSETS: [u32; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+        let year = self.year as u32 - u32::from(self.month < 3);
+        let index = year + year / 4 - year / 100
+            + year / 400
+            + OFFSETS[self.month as usize - 1]
+            + self.day as u32;
+        (index % 7) as u8
+    }
+
     pub fn advance_days(&mut self, days: u64) {
         let mut remaining = days;
         while remaining > 0 {
@@ -69,6 +166,114 @@ impl CalendarDate {
     }
 }
 
+impl Default for CalendarDate {
+    fn default() -> Self {
+        Self::from_start()
+    }
+}
+
+impl fmt::Display for CalendarDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// `YYYY-MM-DD` 形式の日付を読み取る。
+impl FromStr for CalendarDate {
+    type Err = anyhow::Error;
+
+    fn from_str(text: &str) -> Result<Self> {
+        let invalid = || anyhow!("日付 '{}' は YYYY-MM-DD 形式で指定してください", text);
+        let mut parts = text.trim().splitn(3, '-');
+        let mut next = || parts.next().ok_or_else(invalid);
+        let year = next()?.parse().map_err(|_| invalid())?;
+        let month = next()?.parse().map_err(|_| invalid())?;
+        let day = next()?.parse().map_err(|_| invalid())?;
+        Self::try_new(year, month, day)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Weekday {
+    Sunday,
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+}
+
+impl Weekday {
+    pub const ALL: [Weekday; 7] = [
+        Weekday::Sunday,
+        Weekday::Monday,
+        Weekday::Tuesday,
+        Weekday::Wednesday,
+        Weekday::Thursday,
+        Weekday::Friday,
+        Weekday::Saturday,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Weekday::Sunday => "日曜",
+            Weekday::Monday => "月曜",
+            Weekday::Tuesday => "火曜",
+            Weekday::Wednesday => "水曜",
+            Weekday::Thursday => "木曜",
+            Weekday::Friday => "金曜",
+            Weekday::Saturday => "土曜",
+        }
+    }
+
+    pub fn is_weekend(&self) -> bool {
+        matches!(self, Weekday::Saturday | Weekday::Sunday)
+    }
+}
+
+/// 暦の区切り。`SimulationClock::advance` が月・四半期・年の最終日を越えるたびに発生する。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PeriodEnd {
+    MonthEnd { year: u16, month: u8 },
+    QuarterEnd { year: u16, quarter: u8 },
+    YearEnd { year: u16 },
+}
+
+impl PeriodEnd {
+    /// `last_day` を締め日とする区切りを、月末・四半期末・年末の順に返す。
+    pub fn closing_on(last_day: CalendarDate) -> Vec<PeriodEnd> {
+        let mut periods = Vec::new();
+        if !last_day.is_month_end() {
+            return periods;
+        }
+        periods.push(PeriodEnd::MonthEnd {
+            year: last_day.year,
+            month: last_day.month,
+        });
+        if last_day.is_quarter_end() {
+            periods.push(PeriodEnd::QuarterEnd {
+                year: last_day.year,
+                quarter: last_day.quarter(),
+            });
+        }
+        if last_day.is_year_end() {
+            periods.push(PeriodEnd::YearEnd {
+                year: last_day.year,
+            });
+        }
+        periods
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            PeriodEnd::MonthEnd { year, month } => format!("{}年{}月末", year, month),
+            PeriodEnd::QuarterEnd { year, quarter } => format!("{}年第{}四半期末", year, quarter),
+            PeriodEnd::YearEnd { year } => format!("{}年末", year),
+        }
+    }
+}
+
 #[inline]
 fn days_in_month(year: u16, month: u8) -> u8 {
     match month {
@@ -112,3 +317,82 @@ impl PartialOrd for ScheduledTime {
         Some(self.cmp(other))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calendar_date_reports_weekday_quarter_and_day_of_year() {
+        let date = CalendarDate::new(2025, 1, 1);
+        assert_eq!(date.weekday(), Weekday::Wednesday);
+        assert_eq!(date.quarter(), 1);
+        assert_eq!(date.day_of_year(), 1);
+
+        let leap = CalendarDate::new(2024, 12, 31);
+        assert_eq!(leap.weekday(), Weekday::Tuesday);
+        assert_eq!(leap.quarter(), 4);
+        assert_eq!(leap.day_of_year(), 366);
+        assert!(leap.is_month_end() && leap.is_quarter_end() && leap.is_year_end());
+
+        let september = CalendarDate::new(2025, 9, 30);
+        assert!(september.is_quarter_end() && !september.is_year_end());
+        assert!(!CalendarDate::new(2025, 2, 27).is_month_end());
+        assert!(CalendarDate::new(2025, 2, 28).is_month_end());
+        assert!(Weekday::Sunday.is_weekend() && !Weekday::Friday.is_weekend());
+
+        assert!(leap < date && date < september);
+        assert_eq!(leap.days_until(date), 1);
+        assert_eq!(date.days_until(september), 272);
+        assert_eq!(date.days_until(CalendarDate::new(2029, 1, 1)), 1_461);
+        assert_eq!(september.days_until(date), -272);
+    }
+
+    #[test]
+    fn calendar_date_parses_and_rejects_impossible_dates() {
+        let date: CalendarDate = "2030-04-01".parse().expect("valid date");
+        assert_eq!(date, CalendarDate::new(2030, 4, 1));
+        assert_eq!(date.to_string(), "2030-04-01");
+        assert!("2028-02-29".parse::<CalendarDate>().is_ok());
+        for invalid in [
+            "2027-02-29",
+            "2025-13-01",
+            "2025-04-31",
+            "2025/01/01",
+            "2025-01",
+            "0000-01-15",
+        ] {
+            assert!(
+                invalid.parse::<CalendarDate>().is_err(),
+                "{} should be rejected",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn period_ends_follow_month_quarter_and_year() {
+        assert!(PeriodEnd::closing_on(CalendarDate::new(2025, 5, 30)).is_empty());
+        assert_eq!(
+            PeriodEnd::closing_on(CalendarDate::new(2025, 5, 31)),
+            vec![PeriodEnd::MonthEnd {
+                year: 2025,
+                month: 5
+            }]
+        );
+        assert_eq!(
+            PeriodEnd::closing_on(CalendarDate::new(2025, 12, 31)),
+            vec![
+                PeriodEnd::MonthEnd {
+                    year: 2025,
+                    month: 12
+                },
+                PeriodEnd::QuarterEnd {
+                    year: 2025,
+                    quarter: 4
+                },
+                PeriodEnd::YearEnd { year: 2025 },
+            ]
+        );
+    }
+}
@@ -4,8 +4,8 @@ use realgeopolitics_core::CountryDefinition;
 
 #[cfg(target_arch = "wasm32")]
 use realgeopolitics_core::{
-    BudgetAllocation, FiscalSnapshot, FiscalTrendPoint, GameState, IndustryCategory,
-    SectorOverview, TimeStatus,
+    BudgetAllocation, CarbonPolicy, DebtStatus, FiscalSnapshot, FiscalTrendPoint, GameState,
+    IndustryCategory, RestructuringTerms, SectorOverview, TaxPolicy, TaxPolicyChange, TimeStatus,
 };
 use serde_json::Error as SerdeError;
 
@@ -172,6 +172,75 @@ enum AllocationField {
     Research,
 }
 
+#[cfg(target_arch = "wasm32")]
+#[derive(Clone, Copy, PartialEq)]
+struct TaxForm {
+    income: f64,
+    corporate: f64,
+    consumption: f64,
+    deductions: f64,
+    steps: f64,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl TaxForm {
+    fn from_policy(policy: &TaxPolicy) -> Self {
+        let target = policy.transition();
+        Self {
+            income: target.map_or(policy.income_rate, |t| t.income_rate) * 100.0,
+            corporate: target.map_or(policy.corporate_rate, |t| t.corporate_rate) * 100.0,
+            consumption: target.map_or(policy.consumption_rate, |t| t.consumption_rate) * 100.0,
+            deductions: target.map_or(policy.deductions, |t| t.deductions),
+            steps: 4.0,
+        }
+    }
+
+    fn update(mut self, field: TaxField, value: f64) -> Self {
+        match field {
+            TaxField::Income => self.income = value,
+            TaxField::Corporate => self.corporate = value,
+            TaxField::Consumption => self.consumption = value,
+            TaxField::Deductions => self.deductions = value,
+            TaxField::Steps => self.steps = value,
+        }
+        self
+    }
+
+    fn to_change(&self) -> TaxPolicyChange {
+        TaxPolicyChange {
+            income_rate: Some(self.income / 100.0),
+            corporate_rate: Some(self.corporate / 100.0),
+            consumption_rate: Some(self.consumption / 100.0),
+            deductions: Some(self.deductions),
+            phase_steps: self.steps.max(0.0).round() as u32,
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[derive(Clone, Copy)]
+enum TaxField {
+    Income,
+    Corporate,
+    Consumption,
+    Deductions,
+    Steps,
+}
+
+#[cfg(target_arch = "wasm32")]
+#[derive(Clone, Copy)]
+enum DebtAction {
+    Haircut,
+    Austerity,
+    AcceptBailout,
+    DeclineBailout,
+}
+
+#[cfg(target_arch = "wasm32")]
+const DEFAULT_CARBON_TAX: f64 = 25.0;
+#[cfg(target_arch = "wasm32")]
+const CAP_TIGHTENING: f64 = 0.9;
+
 #[cfg(target_arch = "wasm32")]
 #[derive(Clone, Copy, PartialEq)]
 enum MainTab {
@@ -238,6 +307,7 @@ fn app() -> Html {
     let main_tab = use_state(|| MainTab::Controls);
     let dashboard_tab = use_state(|| DashboardTab::DebtTrend);
     let industry_selection = use_state(String::new);
+    let tax_form = use_state(|| Option::<TaxForm>::None);
 
     {
         let game = game.clone();
@@ -281,6 +351,7 @@ fn app() -> Html {
 
     let on_country_change = {
         let selected_country = selected_country.clone();
+        let tax_form = tax_form.clone();
         Callback::from(move |event: Event| {
             if let Some(value) = event
                 .target()
@@ -288,6 +359,7 @@ fn app() -> Html {
             {
                 if let Ok(idx) = value.value().parse::<usize>() {
                     selected_country.set(idx);
+                    tax_form.set(None);
                 }
             }
         })
@@ -380,6 +452,7 @@ fn app() -> Html {
 
     let countries_snapshot = game.borrow();
     let industry_overview = countries_snapshot.industry_overview();
+    let grid_status = countries_snapshot.power_grid_status();
     let status: TimeStatus = countries_snapshot.time_status();
     let countries = countries_snapshot.countries();
     let sim_minutes = status.simulation_minutes;
@@ -411,6 +484,274 @@ fn app() -> Html {
     let total_budget = current_allocation.total();
 
     let current_country = countries.get(current_idx);
+    let current_tax_form = (*tax_form).unwrap_or_else(|| {
+        current_country
+            .map(|country| TaxForm::from_policy(country.tax_policy()))
+            .unwrap_or_else(|| TaxForm::from_policy(&TaxPolicy::default()))
+    });
+    let tax_status = current_country
+        .and_then(|country| country.tax_policy().transition())
+        .map(|transition| format!("移行中: 残り {} 段階", transition.remaining_steps))
+        .unwrap_or_else(|| "予約中の改定はありません".to_string());
+
+    let update_tax_field = {
+        let tax_form = tax_form.clone();
+        Callback::from(move |(field, value): (TaxField, f64)| {
+            tax_form.set(Some(current_tax_form.update(field, value)));
+        })
+    };
+
+    let on_tax_submit = {
+        let game = game.clone();
+        let message = message.clone();
+        let tax_form = tax_form.clone();
+        let refresh = refresh.clone();
+        Callback::from(move |_event: MouseEvent| {
+            match game
+                .borrow_mut()
+                .update_tax_policy(current_idx, current_tax_form.to_change())
+            {
+                Ok(()) => {
+                    message.set(None);
+                    tax_form.set(None);
+                    refresh.set(refresh.wrapping_add(1));
+                }
+                Err(err) => message.set(Some(err.to_string())),
+            }
+        })
+    };
+
+    let on_debt_action = {
+        let game = game.clone();
+        let message = message.clone();
+        let refresh = refresh.clone();
+        Callback::from(move |action: DebtAction| {
+            let mut game = game.borrow_mut();
+            let result = match action {
+                DebtAction::Haircut => {
+                    game.restructure_debt(current_idx, RestructuringTerms::Haircut)
+                }
+                DebtAction::Austerity => {
+                    game.restructure_debt(current_idx, RestructuringTerms::Austerity)
+                }
+                DebtAction::AcceptBailout => game.accept_bailout(current_idx).map(|_| ()),
+                DebtAction::DeclineBailout => game.decline_bailout(current_idx),
+            };
+            match result {
+                Ok(()) => {
+                    message.set(None);
+                    refresh.set(refresh.wrapping_add(1));
+                }
+                Err(err) => message.set(Some(err.to_string())),
+            }
+        })
+    };
+    let debt_status_line = current_country
+        .map(|country| {
+            let sovereign = country.sovereign_debt();
+            let mut line = format!(
+                "{} / 格付け {:?} / 延滞 {} 回",
+                sovereign.status().label(),
+                country.fiscal.credit_rating,
+                sovereign.missed_payments()
+            );
+            if let Some((terms, days)) = sovereign.negotiation() {
+                line.push_str(&format!(
+                    " / 再編交渉: {} (残り {} 日)",
+                    terms.label(),
+                    days
+                ));
+            }
+            if let Some(program) = sovereign.program() {
+                line.push_str(&format!(
+                    " / 支援プログラム残り {} 回",
+                    program.remaining_tranches
+                ));
+            }
+            line
+        })
+        .unwrap_or_default();
+    let bailout_offer_line = current_country
+        .and_then(|country| country.sovereign_debt().offer())
+        .map(|offer| {
+            format!(
+                "{} から {:.1} の支援提案 (条件: {} / 期限 {} 日)",
+                offer.lender,
+                offer.total,
+                offer.conditions.describe(),
+                offer.expires_in_days
+            )
+        });
+    let in_default = current_country
+        .map(|country| country.sovereign_debt().status() == DebtStatus::Default)
+        .unwrap_or(false);
+    let debt_action_button = |label: &'static str, action: DebtAction, enabled: bool| {
+        let callback = on_debt_action.clone();
+        html! {
+            <button disabled={!enabled} onclick={Callback::from(move |_event: MouseEvent| callback.emit(action))}>{ label }</button>
+        }
+    };
+    let debt_section = html! {
+        <section class="sovereign-debt">
+            <h2>{ "債務状況" }</h2>
+            <div class="allocation-summary">
+                <span>{ debt_status_line }</span>
+            </div>
+            <div class="allocation-summary">
+                { debt_action_button("元本削減で再編", DebtAction::Haircut, in_default) }
+                { debt_action_button("緊縮条件で再編", DebtAction::Austerity, in_default) }
+            </div>
+            {
+                match bailout_offer_line {
+                    Some(line) => html! {
+                        <div class="allocation-summary">
+                            <span>{ line }</span>
+                            { debt_action_button("受諾", DebtAction::AcceptBailout, true) }
+                            { debt_action_button("辞退", DebtAction::DeclineBailout, true) }
+                        </div>
+                    },
+                    None => Html::default(),
+                }
+            }
+        </section>
+    };
+
+    let on_carbon_policy = {
+        let game = game.clone();
+        let message = message.clone();
+        let refresh = refresh.clone();
+        Callback::from(move |policy: CarbonPolicy| {
+            match game.borrow_mut().set_carbon_policy(current_idx, policy) {
+                Ok(()) => {
+                    message.set(None);
+                    refresh.set(refresh.wrapping_add(1));
+                }
+                Err(err) => message.set(Some(err.to_string())),
+            }
+        })
+    };
+    let environment_line = current_country
+        .map(|country| {
+            let environment = country.environment();
+            format!(
+                "排出 {:.0}/日 / 汚染指数 {:.1} / 炭素価格 {:.1} ({})",
+                environment.emissions_per_day(),
+                environment.pollution(),
+                environment.carbon_price(),
+                environment.policy().label()
+            )
+        })
+        .unwrap_or_default();
+    let current_emissions = current_country
+        .map(|country| country.environment().emissions_per_day())
+        .unwrap_or(0.0);
+    let carbon_button = |label: &'static str, policy: CarbonPolicy, enabled: bool| {
+        let callback = on_carbon_policy.clone();
+        html! {
+            <button disabled={!enabled} onclick={Callback::from(move |_event: MouseEvent| callback.emit(policy))}>{ label }</button>
+        }
+    };
+    let environment_section = html! {
+        <section class="environment">
+            <h2>{ "環境と炭素価格" }</h2>
+            <div class="allocation-summary">
+                <span>{ environment_line }</span>
+            </div>
+            <div class="allocation-summary">
+                { carbon_button("炭素税を導入", CarbonPolicy::Tax { price: DEFAULT_CARBON_TAX }, true) }
+                { carbon_button(
+                    "排出枠を設定 (現状の9割)",
+                    CarbonPolicy::Cap { limit_per_day: current_emissions * CAP_TIGHTENING },
+                    current_emissions > 0.0,
+                ) }
+                { carbon_button("炭素価格を廃止", CarbonPolicy::None, true) }
+            </div>
+        </section>
+    };
+
+    let on_prioritize_research = {
+        let game = game.clone();
+        let message = message.clone();
+        let refresh = refresh.clone();
+        Callback::from(move |key: String| {
+            match game.borrow_mut().prioritize_research(current_idx, &key) {
+                Ok(()) => {
+                    message.set(None);
+                    refresh.set(refresh.wrapping_add(1));
+                }
+                Err(err) => message.set(Some(err.to_string())),
+            }
+        })
+    };
+    let research_rows: Vec<Html> = current_country
+        .map(|country| {
+            let research = country.research();
+            let tree = countries_snapshot.technology_tree();
+            let target = research.current_target(tree).map(|tech| tech.key.clone());
+            tree.technologies()
+                .iter()
+                .map(|tech| {
+                    let completed = research.is_completed(&tech.key);
+                    let available = tech.prerequisites_met(research.completed());
+                    let status = if completed {
+                        "完了"
+                    } else if target.as_deref() == Some(tech.key.as_str()) {
+                        "研究中"
+                    } else if available {
+                        "利用可"
+                    } else {
+                        "前提未達"
+                    };
+                    let callback = on_prioritize_research.clone();
+                    let key = tech.key.clone();
+                    html! {
+                        <tr>
+                            <td title={tech.description.clone()}>{ tech.name.clone() }</td>
+                            <td>{ status }</td>
+                            <td>{ format!("{:.0} / {:.0}", research.progress(&tech.key), tech.cost) }</td>
+                            <td>
+                                <button disabled={completed} onclick={Callback::from(move |_event: MouseEvent| callback.emit(key.clone()))}>{ "優先" }</button>
+                            </td>
+                        </tr>
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let research_line = current_country
+        .map(|country| {
+            let research = country.research();
+            let priorities = if research.priorities().is_empty() {
+                "なし".to_string()
+            } else {
+                research.priorities().join(" > ")
+            };
+            format!(
+                "未配分ポイント {:.1} / 優先順位 {}",
+                research.points(),
+                priorities
+            )
+        })
+        .unwrap_or_default();
+    let research_section = html! {
+        <section class="research">
+            <h2>{ "研究開発" }</h2>
+            <div class="allocation-summary">
+                <span>{ research_line }</span>
+            </div>
+            <table>
+                <thead>
+                    <tr>
+                        <th>{ "技術" }</th>
+                        <th>{ "状態" }</th>
+                        <th>{ "進捗" }</th>
+                        <th></th>
+                    </tr>
+                </thead>
+                <tbody>{ for research_rows }</tbody>
+            </table>
+        </section>
+    };
 
     let snapshots_ref: &Vec<FiscalSnapshot> = &*fiscal_snapshots;
     let current_snapshot = snapshots_ref
@@ -422,7 +763,7 @@ fn app() -> Html {
     let debt_value = current_snapshot.debt;
     let debt_ratio_value = current_snapshot.debt_ratio;
     let approval_value = current_country
-        .map(|country| country.approval as f64)
+        .map(|country| country.approval)
         .unwrap_or(0.0);
     let dashboard_trend: Vec<FiscalTrendPoint> = {
         let history = &current_snapshot.history;
@@ -553,6 +894,23 @@ fn app() -> Html {
                 { render_amount_input("行政維持", current_allocation.administration, current_idx, AllocationField::Administration, update_amount.clone()) }
                 { render_amount_input("研究開発", current_allocation.research, current_idx, AllocationField::Research, update_amount.clone()) }
             </section>
+
+            <section class="tax-policy">
+                <h2>{ "税制改定" }</h2>
+                { render_tax_input("所得税率 (%)", current_tax_form.income, "0.5", TaxField::Income, update_tax_field.clone()) }
+                { render_tax_input("法人税率 (%)", current_tax_form.corporate, "0.5", TaxField::Corporate, update_tax_field.clone()) }
+                { render_tax_input("消費税率 (%)", current_tax_form.consumption, "0.5", TaxField::Consumption, update_tax_field.clone()) }
+                { render_tax_input("控除額", current_tax_form.deductions, "5", TaxField::Deductions, update_tax_field.clone()) }
+                { render_tax_input("移行段階数 (日)", current_tax_form.steps, "1", TaxField::Steps, update_tax_field.clone()) }
+                <div class="allocation-summary">
+                    <span>{ tax_status }</span>
+                    <button onclick={on_tax_submit}>{ "改定を予約" }</button>
+                </div>
+            </section>
+
+            { debt_section }
+            { environment_section }
+            { research_section }
         </>
     };
 
@@ -678,11 +1036,42 @@ fn app() -> Html {
                     <p>{ format!("収益 {:.1}", overview.last_revenue) }</p>
                     <p>{ format!("コスト {:.1}", overview.last_cost) }</p>
                     <p>{ format!("補助金 {:.1}%", overview.subsidy_percent) }</p>
+                    <p>{ format!("資本ストック {:.1} (直近投資 {:.3})", overview.capital_stock, overview.last_investment) }</p>
                 </div>
             }
         })
         .unwrap_or_else(|| html! { <div class="industry-summary">{ "セクターを選択してください。" }</div> });
 
+    let grid_summary = {
+        let active_line = grid_status
+            .active
+            .map(|active| {
+                format!(
+                    "計画停電中: 生産能力 -{:.0}% / 残り {:.1} 時間",
+                    active.output_cut * 100.0,
+                    grid_status.remaining_minutes / 60.0
+                )
+            })
+            .unwrap_or_else(|| "電力供給は平常です".to_string());
+        html! {
+            <section class="power-grid">
+                <h2>{ "電力需給" }</h2>
+                <p>{ format!("予備率 {:+.1}%", grid_status.reserve_margin * 100.0) }</p>
+                <p>{ active_line }</p>
+                <ul>
+                    { for grid_status.history.iter().rev().map(|record| html! {
+                        <li>{ format!(
+                            "{:.1} 日目: 供給不足 {:.1}% / {:.0} 時間",
+                            record.started_at_minutes / (60.0 * 24.0),
+                            record.shortfall_ratio * 100.0,
+                            record.duration_minutes / 60.0
+                        ) }</li>
+                    }) }
+                </ul>
+            </section>
+        }
+    };
+
     let on_industry_select = {
         let selection_handle = industry_selection.clone();
         Callback::from(move |event: Event| {
@@ -715,6 +1104,7 @@ fn app() -> Html {
                     </tbody>
                 </table>
             </section>
+            { grid_summary }
             <section class="industry-kpi">
                 <h2>{ "KPI グラフ" }</h2>
                 <div class="industry-kpi-header">
@@ -775,9 +1165,9 @@ fn app() -> Html {
                                         <td>{ &country.name }</td>
                                         <td>{ &country.government }</td>
                                         <td>{ format!("{:.1}", country.gdp) }</td>
-                                        <td>{ country.stability }</td>
-                                        <td>{ country.military }</td>
-                                        <td>{ country.approval }</td>
+                                        <td>{ format!("{:.0}", country.stability) }</td>
+                                        <td>{ format!("{:.0}", country.military) }</td>
+                                        <td>{ format!("{:.0}", country.approval) }</td>
                                         <td>{ format!("{:.1}", country.cash_reserve()) }</td>
                                         <td>{ format!("{:.1}", country.total_revenue()) }</td>
                                         <td>{ format!("{:.1}", country.total_expense()) }</td>
@@ -823,7 +1213,7 @@ fn app() -> Html {
             <header>
                 <div class="time-panel">
                     <h1>{ "リアル・ジオポリティクス シミュレーター" }</h1>
-                    <p>{ format!("シミュレーション時間 {:.1} 分 (日付 {:04}-{:02}-{:02})", sim_minutes, calendar.year, calendar.month, calendar.day) }</p>
+                    <p>{ format!("シミュレーション時間 {:.1} 分 (日付 {} {})", sim_minutes, calendar, calendar.weekday().label()) }</p>
                     <p>{ format!("次イベントまで: {}", next_event) }</p>
                 </div>
                 <div class="summary">
@@ -1120,6 +1510,33 @@ fn render_amount_input(
     }
 }
 
+#[cfg(target_arch = "wasm32")]
+fn render_tax_input(
+    label: &str,
+    value: f64,
+    step: &'static str,
+    field: TaxField,
+    callback: Callback<(TaxField, f64)>,
+) -> Html {
+    let oninput = Callback::from(move |event: InputEvent| {
+        if let Some(input) = event
+            .target()
+            .and_then(|target| target.dyn_into::<HtmlInputElement>().ok())
+        {
+            if let Ok(value) = input.value().parse::<f64>() {
+                callback.emit((field, value));
+            }
+        }
+    });
+
+    html! {
+        <div class="amount-row">
+            <label>{ label }</label>
+            <input type="number" min="0" step={step} value={format!("{:.1}", value)} oninput={oninput} />
+        </div>
+    }
+}
+
 #[cfg(target_arch = "wasm32")]
 fn render_core_toggle(
     enabled: bool,
@@ -1247,6 +1664,19 @@ fn render_dashboard(
     };
 
     let balance = cash - debt;
+    let maturity_summary = snapshot
+        .maturity_profile
+        .iter()
+        .filter(|bucket| bucket.principal > 0.0)
+        .map(|bucket| {
+            format!(
+                "{} {}",
+                bucket.label,
+                format_compact_number(bucket.principal)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" / ");
 
     html! {
         <section class="dashboard">
@@ -1269,6 +1699,16 @@ fn render_dashboard(
                     <span class="label">{ "世論指数" }</span>
                     <span class="value">{ format!("{:.0}", approval) }</span>
                 </div>
+                <div class="metric-card">
+                    <span class="label">{ "消費者物価指数" }</span>
+                    <span class="value">{ format!("{:.1}", snapshot.cpi) }</span>
+                    <span class="sub">{ format!("インフレ率 {:+.2}%", snapshot.inflation) }</span>
+                </div>
+                <div class="metric-card">
+                    <span class="label">{ "平均調達金利" }</span>
+                    <span class="value">{ format!("{:.2}%", snapshot.average_cost_of_debt * 100.0) }</span>
+                    <span class="sub">{ maturity_summary }</span>
+                </div>
             </div>
             <div class="dashboard-tabs">
                 <button class={debt_class} onclick={on_debt_tab}>{ DashboardTab::DebtTrend.label() }</button>